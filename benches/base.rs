@@ -159,6 +159,52 @@ pub mod sha {
     }
 }
 
+/// Compare the sequential and the parallel verification of a composite proof of 3 segments.
+mod parallel {
+    use super::*;
+
+    const PATH: &str = "resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json";
+
+    #[divan::bench]
+    fn sequential() {
+        let case: Case = read_json(PATH).unwrap();
+        let proof = read_bin(case.receipt_path).unwrap();
+
+        v1_2()
+            .verify(
+                divan::black_box(case.vk.into()),
+                divan::black_box(proof),
+                divan::black_box(case.journal),
+            )
+            .unwrap()
+    }
+
+    #[divan::bench]
+    fn parallel() {
+        let case: Case = read_json(PATH).unwrap();
+        let proof = read_bin(case.receipt_path).unwrap();
+
+        Verifier::verify_parallel(
+            v1_2,
+            divan::black_box(case.vk.into()),
+            divan::black_box(proof),
+            divan::black_box(case.journal),
+        )
+        .unwrap()
+    }
+}
+
+/// Create 100 verifiers in a row: with the `std` feature the default hash suites are built
+/// only once.
+#[divan::bench]
+fn create_100_verifiers() {
+    for _ in 0..100 {
+        divan::black_box(v1_2());
+        divan::black_box(v2_1());
+        divan::black_box(v3_0());
+    }
+}
+
 #[divan::bench]
 fn case_limit() {
     let verifier = v1_2();
@@ -0,0 +1,67 @@
+// Copyright Copyright 2024, Horizen Labs, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Compare the verification of a batch of proofs with
+//! [Verifier::verify_batch](risc0_verifier::Verifier::verify_batch) and with a
+//! [Verifier::verify](risc0_verifier::Verifier::verify) call for every proof: the throughput is
+//! reported in proofs per second.
+
+fn main() {
+    // Run registered benchmarks.
+    divan::main();
+}
+
+use divan::{counter::ItemsCount, Bencher};
+use risc0_verifier::{v3_0, Journal, Proof, Verifier, Vk};
+use utils::*;
+
+mod utils;
+
+const PATH: &str = "resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json";
+const SIZES: [usize; 3] = [1, 8, 32];
+
+fn items(n: usize) -> Vec<(Vk, Proof, Journal)> {
+    let case: Case = read_json(PATH).unwrap();
+    let proof: Proof = read_bin(case.receipt_path).unwrap();
+    vec![(case.vk, proof, case.journal); n]
+}
+
+#[divan::bench(args = SIZES, sample_count = 10)]
+fn verify_batch(bencher: Bencher, n: usize) {
+    let verifier = v3_0();
+    let items = items(n);
+
+    bencher.counter(ItemsCount::new(n)).bench_local(|| {
+        verifier
+            .verify_batch(divan::black_box(&items))
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+    });
+}
+
+#[divan::bench(args = SIZES, sample_count = 10)]
+fn verify_loop(bencher: Bencher, n: usize) {
+    let verifier = v3_0();
+    let items = items(n);
+
+    bencher.counter(ItemsCount::new(n)).bench_local(|| {
+        for (vk, proof, journal) in divan::black_box(&items) {
+            compute(&verifier, proof.clone(), vk.0, journal.clone())
+        }
+    });
+}
@@ -0,0 +1,90 @@
+// Copyright Copyright 2024, Horizen Labs, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Compare the verification of a poseidon2 proof with the built-in poseidon2 hash suite and
+//! with the injected [Poseidon2Mix](risc0_verifier::poseidon2_injection::Poseidon2Mix)
+//! implementations.
+
+fn main() {
+    // Run registered benchmarks.
+    divan::main();
+}
+
+use divan::Bencher;
+use risc0_verifier::{
+    poseidon2_injection::{DefaultPoseidon2, NoOpPoseidon2},
+    v1_2, Verifier,
+};
+use utils::*;
+
+mod utils;
+
+const PATH: &str = "resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json";
+
+#[divan::bench]
+fn native() {
+    let verifier = v1_2();
+    let case: Case = read_json(PATH).unwrap();
+    let proof = read_bin(case.receipt_path).unwrap();
+
+    compute(
+        divan::black_box(&verifier),
+        divan::black_box(proof),
+        divan::black_box(case.vk.into()),
+        divan::black_box(case.journal),
+    )
+}
+
+#[divan::bench]
+fn injected_default() {
+    let verifier = v1_2().with_poseidon2(DefaultPoseidon2);
+    let case: Case = read_json(PATH).unwrap();
+    let proof = read_bin(case.receipt_path).unwrap();
+
+    compute(
+        divan::black_box(&verifier),
+        divan::black_box(proof),
+        divan::black_box(case.vk.into()),
+        divan::black_box(case.journal),
+    )
+}
+
+/// The verification fails: this measures just the overhead of the injection layer.
+#[divan::bench]
+fn injected_no_op() {
+    let verifier = v1_2().with_poseidon2(NoOpPoseidon2);
+    let case: Case = read_json(PATH).unwrap();
+    let proof = read_bin(case.receipt_path).unwrap();
+
+    divan::black_box(&verifier)
+        .verify(
+            divan::black_box(case.vk.into()),
+            divan::black_box(proof),
+            divan::black_box(case.journal),
+        )
+        .unwrap_err();
+}
+
+#[divan::bench]
+fn set_poseidon2_mix_impl(bencher: Bencher) {
+    bencher
+        .with_inputs(v1_2)
+        .bench_local_values(|mut verifier| {
+            verifier.set_poseidon2_mix_impl(Box::new(DefaultPoseidon2));
+            verifier
+        });
+}
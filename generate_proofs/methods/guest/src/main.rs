@@ -20,6 +20,7 @@ use risc0_zkvm::guest::env;
 fn main() {
     // read the input
     let cycles: u64 = env::read();
+    let pause: bool = env::read();
 
     let c = loop {
         let v = env::cycle_count();
@@ -30,4 +31,9 @@ fn main() {
 
     // write public output to the journal
     env::commit(&c);
+
+    if pause {
+        // The receipt ends with `Paused(0)` and commits to the journal written so far.
+        env::pause(0);
+    }
 }
\ No newline at end of file
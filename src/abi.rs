@@ -0,0 +1,872 @@
+// Copyright Copyright 2024, Horizen Labs, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Solidity ABI encoding of a [Proof], to be used as Ethereum calldata. Available only with the
+//! `ethereum` feature.
+//!
+//! The proof is encoded as `abi.encode(uint8 proof_type, ...)` where `proof_type` is `1` for
+//! composite proofs and `2` for succinct proofs:
+//! - composite: `(uint8, (bytes seal, string hashfn, bytes32 verifier_parameters, uint32 index)[]
+//!   segments, bytes32 verifier_parameters)`
+//! - succinct: `(uint8, (bytes seal, string hashfn, bytes32 verifier_parameters))`
+//!
+//! Seals are encoded as the little-endian bytes of their words. The encoding doesn't carry the
+//! data that has no Solidity counterpart: [decode_abi] rebuilds it from the seals with the prover
+//! version identified by `verifier_parameters`. The segment claims are decoded from the segment
+//! seals, while a succinct claim is pruned to the claim digest committed in the seal; the control
+//! id of a succinct receipt is the one committed in its seal and its inclusion proof is computed
+//! from the control ids allowed by the prover version.
+//!
+//! Composite proofs with assumption receipts, Groth16 proofs and fake proofs are not supported.
+
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+use core::fmt;
+use risc0_zkp_v1::{
+    core::{
+        digest::{Digest, DIGEST_BYTES},
+        hash::poseidon2::Poseidon2HashSuite,
+    },
+    verify::VerificationError,
+};
+
+use crate::{
+    circuit,
+    context::{v1::V1, v2::V2, v3::V3, CircuitInfo, VerifierContext},
+    receipt::{merkle::MerkleProof, succinct::SuccinctReceipt},
+    receipt_claim::MaybePruned,
+    segment::SegmentReceipt,
+    sha::Digestible,
+    CompositeReceipt, HashFunction, InnerReceipt, Proof,
+};
+
+const WORD: usize = 32;
+const COMPOSITE: u8 = 1;
+const SUCCINCT: u8 = 2;
+
+/// Error returned when a [Proof] cannot be ABI encoded.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AbiEncodeError {
+    /// The proof type has no ABI encoding (e.g. composite proofs with assumption receipts).
+    UnsupportedProofType,
+    /// A field of the proof cannot be encoded.
+    Encoding,
+}
+
+impl fmt::Display for AbiEncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnsupportedProofType => write!(f, "unsupported proof type"),
+            Self::Encoding => write!(f, "failed to encode proof"),
+        }
+    }
+}
+
+/// Error returned when ABI encoded data cannot be decoded in a [Proof].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AbiDecodeError {
+    /// The proof type discriminant is unknown.
+    UnknownProofType(u8),
+    /// The data is not a valid ABI encoding (e.g. truncated data or invalid offsets).
+    InvalidData,
+    /// A field of the proof cannot be decoded.
+    Decoding,
+}
+
+impl fmt::Display for AbiDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownProofType(t) => write!(f, "unknown proof type {t}"),
+            Self::InvalidData => write!(f, "invalid abi encoded data"),
+            Self::Decoding => write!(f, "failed to decode proof"),
+        }
+    }
+}
+
+impl Proof {
+    /// Encode this proof following the Solidity ABI spec: see the [module](crate::abi)
+    /// documentation for the layout.
+    pub fn to_abi_encoded(&self) -> Result<Vec<u8>, AbiEncodeError> {
+        let tokens = match &self.inner {
+            InnerReceipt::Composite(c) => {
+                if !c.assumption_receipts.is_empty() {
+                    return Err(AbiEncodeError::UnsupportedProofType);
+                }
+                vec![
+                    Token::Uint(COMPOSITE as u64),
+                    Token::Array(c.segments.iter().map(segment_token).collect()),
+                    Token::Bytes32(c.verifier_parameters),
+                ]
+            }
+            InnerReceipt::Succinct(s) => vec![
+                Token::Uint(SUCCINCT as u64),
+                Token::Tuple(vec![
+                    Token::Bytes(words_to_bytes(&s.seal)),
+                    Token::String(s.hashfn.as_str().into()),
+                    Token::Bytes32(s.verifier_parameters),
+                ]),
+            ],
+            InnerReceipt::Groth16(_) => return Err(AbiEncodeError::UnsupportedProofType),
+            #[cfg(feature = "dev-mode")]
+            InnerReceipt::Fake(_) => return Err(AbiEncodeError::UnsupportedProofType),
+        };
+        Ok(Token::Tuple(tokens).encode())
+    }
+}
+
+/// Decode a [Proof] encoded by [Proof::to_abi_encoded], rebuilding the data that the encoding
+/// doesn't carry from the seals: see the [module](crate::abi) documentation.
+pub fn decode_abi(data: &[u8]) -> Result<Proof, AbiDecodeError> {
+    let proof_type = Kind::Uint.decode(data, 0, &mut data.len())?.uint()?;
+    let receipt = match proof_type {
+        t if t == COMPOSITE as u64 => {
+            let mut fields = Kind::Tuple(vec![
+                Kind::Uint,
+                Kind::Array(Box::new(segment_kind())),
+                Kind::Bytes32,
+            ])
+            .decode(data, 0, &mut data.len())?
+            .fields()?
+            .into_iter()
+            .skip(1);
+            let segments = next(&mut fields)?
+                .items()?
+                .into_iter()
+                .map(segment_from_token)
+                .collect::<Result<_, _>>()?;
+            let verifier_parameters = next(&mut fields)?.bytes32()?;
+            AbiReceipt::Composite {
+                segments,
+                verifier_parameters,
+            }
+        }
+        t if t == SUCCINCT as u64 => {
+            let succinct_kind = Kind::Tuple(vec![Kind::Bytes, Kind::String, Kind::Bytes32]);
+            let mut fields = Kind::Tuple(vec![Kind::Uint, succinct_kind])
+                .decode(data, 0, &mut data.len())?
+                .fields()?
+                .into_iter()
+                .skip(1);
+            let mut fields = next(&mut fields)?.fields()?.into_iter();
+            AbiReceipt::Succinct {
+                seal: bytes_to_words(&next(&mut fields)?.bytes()?)?,
+                hashfn: hash_function(next(&mut fields)?)?,
+                verifier_parameters: next(&mut fields)?.bytes32()?,
+            }
+        }
+        t => {
+            return Err(AbiDecodeError::UnknownProofType(
+                u8::try_from(t).unwrap_or(u8::MAX),
+            ))
+        }
+    };
+    use circuit::*;
+    let inner = receipt
+        .rebuild(V1::v1_0(), v1_0::recursive::control_id::ALLOWED_CONTROL_IDS)
+        .or_else(|| receipt.rebuild(V1::v1_1(), v1_1::recursive::control_id::ALLOWED_CONTROL_IDS))
+        .or_else(|| receipt.rebuild(V1::v1_2(), v1_2::recursive::control_id::ALLOWED_CONTROL_IDS))
+        .or_else(|| {
+            receipt.rebuild(
+                V2::v2_0(),
+                bytemuck::checked::cast_slice(v2_0::recursive::control_id::ALLOWED_CONTROL_IDS),
+            )
+        })
+        .or_else(|| {
+            receipt.rebuild(
+                V2::v2_1(),
+                bytemuck::checked::cast_slice(v2_1::recursive::control_id::ALLOWED_CONTROL_IDS),
+            )
+        })
+        .or_else(|| {
+            receipt.rebuild(
+                V2::v2_2(),
+                bytemuck::checked::cast_slice(v2_2::recursive::control_id::ALLOWED_CONTROL_IDS),
+            )
+        })
+        .or_else(|| {
+            receipt.rebuild(
+                V2::v2_3(),
+                bytemuck::checked::cast_slice(v2_3::recursive::control_id::ALLOWED_CONTROL_IDS),
+            )
+        })
+        .or_else(|| {
+            receipt.rebuild(
+                V3::v3_0(),
+                bytemuck::checked::cast_slice(v3_0::recursive::control_id::ALLOWED_CONTROL_IDS),
+            )
+        })
+        .ok_or(AbiDecodeError::Decoding)??;
+    Ok(Proof::new(inner))
+}
+
+/// The fields of a receipt carried by the ABI encoding.
+enum AbiReceipt {
+    Composite {
+        segments: Vec<AbiSegment>,
+        verifier_parameters: Digest,
+    },
+    Succinct {
+        seal: Vec<u32>,
+        hashfn: HashFunction,
+        verifier_parameters: Digest,
+    },
+}
+
+/// The fields of a segment receipt carried by the ABI encoding.
+struct AbiSegment {
+    seal: Vec<u32>,
+    hashfn: HashFunction,
+    verifier_parameters: Digest,
+    index: u32,
+}
+
+impl AbiReceipt {
+    /// Rebuild the receipt with `ctx`, whose recursion programs have the `control_ids` ids, if
+    /// `ctx` has the verifier parameters of the receipt: `None` otherwise.
+    fn rebuild(
+        &self,
+        ctx: impl VerifierContext,
+        control_ids: &[Digest],
+    ) -> Option<Result<InnerReceipt, AbiDecodeError>> {
+        match self {
+            AbiReceipt::Composite {
+                segments,
+                verifier_parameters,
+            } => {
+                let params = ctx.verifier_parameters().segment_verifier_parameters()?;
+                (Some(params.digest()) == segments.first().map(|s| s.verifier_parameters)).then(
+                    || {
+                        let segments = segments
+                            .iter()
+                            .map(|s| {
+                                // Don't let a short seal make the claim decoding panic.
+                                if s.seal.len()
+                                    < ctx.segment_seal_offset()
+                                        + ctx.verifier_parameters().segment.size()
+                                {
+                                    return Err(AbiDecodeError::Decoding);
+                                }
+                                Ok(SegmentReceipt {
+                                    claim: ctx
+                                        .decode_from_seal(&s.seal)
+                                        .map_err(|_| AbiDecodeError::Decoding)?,
+                                    seal: s.seal.clone(),
+                                    index: s.index,
+                                    hashfn: s.hashfn,
+                                    verifier_parameters: s.verifier_parameters,
+                                })
+                            })
+                            .collect::<Result<_, _>>()?;
+                        Ok(InnerReceipt::Composite(CompositeReceipt {
+                            segments,
+                            assumption_receipts: Vec::new(),
+                            verifier_parameters: *verifier_parameters,
+                        }))
+                    },
+                )
+            }
+            AbiReceipt::Succinct {
+                seal,
+                hashfn,
+                verifier_parameters,
+            } => {
+                let params = ctx.verifier_parameters().succinct_verifier_parameters()?;
+                (params.digest() == *verifier_parameters).then(|| {
+                    // The control id is the root of the code committed in the seal: the verifier
+                    // computes it before checking its inclusion proof, so an empty proof makes
+                    // the verification stop there and report it.
+                    let control_id = match ctx.verify_succinct(
+                        hashfn.as_str(),
+                        seal,
+                        &MerkleProof {
+                            index: 0,
+                            digests: Vec::new(),
+                        },
+                        params,
+                    ) {
+                        Err(VerificationError::ControlVerificationError { control_id }) => {
+                            control_id
+                        }
+                        _ => return Err(AbiDecodeError::Decoding),
+                    };
+                    let index = control_ids
+                        .iter()
+                        .position(|id| *id == control_id)
+                        .ok_or(AbiDecodeError::Decoding)?;
+                    let mut receipt = SuccinctReceipt {
+                        seal: seal.clone(),
+                        control_id,
+                        claim: MaybePruned::Pruned(Digest::ZERO),
+                        hashfn: *hashfn,
+                        verifier_parameters: *verifier_parameters,
+                        control_inclusion_proof: MerkleProof::inclusion(
+                            control_ids,
+                            index as u32,
+                            Poseidon2HashSuite::new_suite().hashfn.as_ref(),
+                        ),
+                    };
+                    receipt.claim = MaybePruned::Pruned(
+                        receipt
+                            .decode_output_hash_from_seal()
+                            .map_err(|_| AbiDecodeError::Decoding)?,
+                    );
+                    Ok(InnerReceipt::Succinct(receipt))
+                })
+            }
+        }
+    }
+}
+
+fn segment_kind() -> Kind {
+    Kind::Tuple(vec![Kind::Bytes, Kind::String, Kind::Bytes32, Kind::Uint])
+}
+
+fn segment_token(segment: &SegmentReceipt) -> Token {
+    Token::Tuple(vec![
+        Token::Bytes(segment.get_seal_bytes()),
+        Token::String(segment.hashfn.as_str().into()),
+        Token::Bytes32(segment.verifier_parameters),
+        Token::Uint(segment.index as u64),
+    ])
+}
+
+fn segment_from_token(token: Token) -> Result<AbiSegment, AbiDecodeError> {
+    let mut fields = token.fields()?.into_iter();
+    Ok(AbiSegment {
+        seal: bytes_to_words(&next(&mut fields)?.bytes()?)?,
+        hashfn: hash_function(next(&mut fields)?)?,
+        verifier_parameters: next(&mut fields)?.bytes32()?,
+        index: u32::try_from(next(&mut fields)?.uint()?)
+            .map_err(|_| AbiDecodeError::InvalidData)?,
+    })
+}
+
+fn hash_function(token: Token) -> Result<HashFunction, AbiDecodeError> {
+    HashFunction::try_from(token.string()?.as_str()).map_err(|_| AbiDecodeError::Decoding)
+}
+
+fn next(fields: &mut impl Iterator<Item = Token>) -> Result<Token, AbiDecodeError> {
+    fields.next().ok_or(AbiDecodeError::InvalidData)
+}
+
+fn words_to_bytes(words: &[u32]) -> Vec<u8> {
+    words.iter().flat_map(|w| w.to_le_bytes()).collect()
+}
+
+fn bytes_to_words(bytes: &[u8]) -> Result<Vec<u32>, AbiDecodeError> {
+    let words = bytes.chunks_exact(size_of::<u32>());
+    if !words.remainder().is_empty() {
+        return Err(AbiDecodeError::InvalidData);
+    }
+    Ok(words
+        .map(|w| u32::from_le_bytes(w.try_into().unwrap()))
+        .collect())
+}
+
+/// The subset of the Solidity ABI values used to encode a [Proof].
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Uint(u64),
+    Bytes32(Digest),
+    Bytes(Vec<u8>),
+    String(String),
+    Tuple(Vec<Token>),
+    Array(Vec<Token>),
+}
+
+impl Token {
+    fn is_dynamic(&self) -> bool {
+        match self {
+            Token::Uint(_) | Token::Bytes32(_) => false,
+            Token::Bytes(_) | Token::String(_) | Token::Array(_) => true,
+            Token::Tuple(items) => items.iter().any(Token::is_dynamic),
+        }
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        match self {
+            Token::Uint(v) => uint_word(*v).to_vec(),
+            Token::Bytes32(d) => d.as_bytes().to_vec(),
+            Token::Bytes(b) => encode_bytes(b),
+            Token::String(s) => encode_bytes(s.as_bytes()),
+            Token::Tuple(items) => encode_sequence(items),
+            Token::Array(items) => {
+                let mut out = uint_word(items.len() as u64).to_vec();
+                out.extend(encode_sequence(items));
+                out
+            }
+        }
+    }
+}
+
+fn uint_word(v: u64) -> [u8; WORD] {
+    let mut word = [0; WORD];
+    word[WORD - size_of::<u64>()..].copy_from_slice(&v.to_be_bytes());
+    word
+}
+
+fn encode_bytes(bytes: &[u8]) -> Vec<u8> {
+    let mut out = uint_word(bytes.len() as u64).to_vec();
+    out.extend_from_slice(bytes);
+    out.resize(WORD + bytes.len().div_ceil(WORD) * WORD, 0);
+    out
+}
+
+fn encode_sequence(items: &[Token]) -> Vec<u8> {
+    let head_size = items
+        .iter()
+        .map(|t| match t {
+            t if t.is_dynamic() => WORD,
+            t => t.encode().len(),
+        })
+        .sum::<usize>();
+    let mut head = Vec::with_capacity(head_size);
+    let mut tail = Vec::new();
+    for item in items {
+        if item.is_dynamic() {
+            head.extend(uint_word((head_size + tail.len()) as u64));
+            tail.extend(item.encode());
+        } else {
+            head.extend(item.encode());
+        }
+    }
+    head.extend(tail);
+    head
+}
+
+/// The ABI type of a [Token], used to drive the decoding.
+enum Kind {
+    Uint,
+    Bytes32,
+    Bytes,
+    String,
+    Tuple(Vec<Kind>),
+    Array(Box<Kind>),
+}
+
+impl Kind {
+    fn is_dynamic(&self) -> bool {
+        match self {
+            Kind::Uint | Kind::Bytes32 => false,
+            Kind::Bytes | Kind::String | Kind::Array(_) => true,
+            Kind::Tuple(kinds) => kinds.iter().any(Kind::is_dynamic),
+        }
+    }
+
+    fn head_size(&self) -> usize {
+        match self {
+            Kind::Tuple(kinds) if !self.is_dynamic() => kinds.iter().map(Kind::head_size).sum(),
+            _ => WORD,
+        }
+    }
+
+    /// Decode the value whose encoding starts at `offset` in `data`.
+    ///
+    /// Every decoded value is charged to `budget` with the size of the words that encode it:
+    /// in a valid encoding they don't overlap, so they fit in `data.len()`. Offsets that share a
+    /// tail (e.g. many array elements pointing to the same large `bytes`) exhaust the budget
+    /// instead of making the decoder allocate more than the input size.
+    fn decode(
+        &self,
+        data: &[u8],
+        offset: usize,
+        budget: &mut usize,
+    ) -> Result<Token, AbiDecodeError> {
+        match self {
+            Kind::Uint => {
+                charge(budget, WORD)?;
+                Ok(Token::Uint(read_uint(data, offset)?))
+            }
+            Kind::Bytes32 => {
+                charge(budget, WORD)?;
+                Ok(Token::Bytes32(
+                    Digest::try_from(read(data, offset, DIGEST_BYTES)?)
+                        .map_err(|_| AbiDecodeError::InvalidData)?,
+                ))
+            }
+            Kind::Bytes => Ok(Token::Bytes(read_bytes(data, offset, budget)?.to_vec())),
+            Kind::String => Ok(Token::String(
+                String::from_utf8(read_bytes(data, offset, budget)?.to_vec())
+                    .map_err(|_| AbiDecodeError::InvalidData)?,
+            )),
+            Kind::Tuple(kinds) => Ok(Token::Tuple(decode_sequence(
+                kinds.iter(),
+                data,
+                offset,
+                budget,
+            )?)),
+            Kind::Array(kind) => {
+                charge(budget, WORD)?;
+                let len = read_len(data, offset)?;
+                // Every element needs at least a word: reject lengths that cannot fit the data.
+                if len > data.len() / WORD {
+                    return Err(AbiDecodeError::InvalidData);
+                }
+                Ok(Token::Array(decode_sequence(
+                    core::iter::repeat_n(kind.as_ref(), len),
+                    data,
+                    offset + WORD,
+                    budget,
+                )?))
+            }
+        }
+    }
+}
+
+fn decode_sequence<'a>(
+    kinds: impl Iterator<Item = &'a Kind>,
+    data: &[u8],
+    offset: usize,
+    budget: &mut usize,
+) -> Result<Vec<Token>, AbiDecodeError> {
+    let mut head = offset;
+    kinds
+        .map(|kind| {
+            let token = if kind.is_dynamic() {
+                let tail = read_len(data, head)?;
+                kind.decode(
+                    data,
+                    offset
+                        .checked_add(tail)
+                        .ok_or(AbiDecodeError::InvalidData)?,
+                    budget,
+                )
+            } else {
+                kind.decode(data, head, budget)
+            };
+            head += kind.head_size();
+            token
+        })
+        .collect()
+}
+
+fn read(data: &[u8], offset: usize, len: usize) -> Result<&[u8], AbiDecodeError> {
+    data.get(offset..offset.checked_add(len).ok_or(AbiDecodeError::InvalidData)?)
+        .ok_or(AbiDecodeError::InvalidData)
+}
+
+fn read_uint(data: &[u8], offset: usize) -> Result<u64, AbiDecodeError> {
+    let word = read(data, offset, WORD)?;
+    let (high, low) = word.split_at(WORD - size_of::<u64>());
+    if high.iter().any(|&b| b != 0) {
+        return Err(AbiDecodeError::InvalidData);
+    }
+    Ok(u64::from_be_bytes(low.try_into().unwrap()))
+}
+
+fn read_len(data: &[u8], offset: usize) -> Result<usize, AbiDecodeError> {
+    usize::try_from(read_uint(data, offset)?).map_err(|_| AbiDecodeError::InvalidData)
+}
+
+fn read_bytes<'a>(
+    data: &'a [u8],
+    offset: usize,
+    budget: &mut usize,
+) -> Result<&'a [u8], AbiDecodeError> {
+    let len = read_len(data, offset)?;
+    charge(
+        budget,
+        len.checked_add(WORD).ok_or(AbiDecodeError::InvalidData)?,
+    )?;
+    read(data, offset + WORD, len)
+}
+
+fn charge(budget: &mut usize, size: usize) -> Result<(), AbiDecodeError> {
+    *budget = budget
+        .checked_sub(size)
+        .ok_or(AbiDecodeError::InvalidData)?;
+    Ok(())
+}
+
+impl Token {
+    fn uint(self) -> Result<u64, AbiDecodeError> {
+        match self {
+            Token::Uint(v) => Ok(v),
+            _ => Err(AbiDecodeError::InvalidData),
+        }
+    }
+
+    fn bytes32(self) -> Result<Digest, AbiDecodeError> {
+        match self {
+            Token::Bytes32(d) => Ok(d),
+            _ => Err(AbiDecodeError::InvalidData),
+        }
+    }
+
+    fn bytes(self) -> Result<Vec<u8>, AbiDecodeError> {
+        match self {
+            Token::Bytes(b) => Ok(b),
+            _ => Err(AbiDecodeError::InvalidData),
+        }
+    }
+
+    fn string(self) -> Result<String, AbiDecodeError> {
+        match self {
+            Token::String(s) => Ok(s),
+            _ => Err(AbiDecodeError::InvalidData),
+        }
+    }
+
+    fn fields(self) -> Result<Vec<Token>, AbiDecodeError> {
+        match self {
+            Token::Tuple(t) => Ok(t),
+            _ => Err(AbiDecodeError::InvalidData),
+        }
+    }
+
+    fn items(self) -> Result<Vec<Token>, AbiDecodeError> {
+        match self {
+            Token::Array(t) => Ok(t),
+            _ => Err(AbiDecodeError::InvalidData),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use std::format;
+
+    fn words(hex_words: &[&str]) -> Vec<u8> {
+        hex_words
+            .iter()
+            .flat_map(|w| hex::decode(format!("{w:0>64}")).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn encode_succinct_layout() {
+        let token = Token::Tuple(vec![
+            Token::Uint(2),
+            Token::Tuple(vec![
+                Token::Bytes(vec![1, 2]),
+                Token::String("ab".to_string()),
+                Token::Bytes32(Digest::from_bytes([0x11; 32])),
+            ]),
+        ]);
+
+        let expected = words(&[
+            "2",
+            "40",
+            "60",
+            "a0",
+            &"11".repeat(32),
+            "2",
+            &format!("{:0<64}", "0102"),
+            "2",
+            &format!("{:0<64}", "6162"),
+        ]);
+
+        assert_eq!(expected, token.encode());
+    }
+
+    #[test]
+    fn encode_composite_layout() {
+        let token = Token::Tuple(vec![
+            Token::Uint(1),
+            Token::Array(vec![Token::Tuple(vec![
+                Token::Bytes(vec![1, 2]),
+                Token::String("ab".to_string()),
+                Token::Bytes32(Digest::from_bytes([0x11; 32])),
+                Token::Uint(3),
+            ])]),
+            Token::Bytes32(Digest::from_bytes([0x22; 32])),
+        ]);
+
+        let expected = words(&[
+            "1",
+            "60",
+            &"22".repeat(32),
+            // segments array
+            "1",
+            "20",
+            // segment tuple
+            "80",
+            "c0",
+            &"11".repeat(32),
+            "3",
+            "2",
+            &format!("{:0<64}", "0102"),
+            "2",
+            &format!("{:0<64}", "6162"),
+        ]);
+
+        assert_eq!(expected, token.encode());
+    }
+
+    #[test]
+    fn decode_encoded_layout() {
+        let kind = Kind::Tuple(vec![
+            Kind::Uint,
+            Kind::Array(Box::new(segment_kind())),
+            Kind::Bytes32,
+        ]);
+        let token = Token::Tuple(vec![
+            Token::Uint(1),
+            Token::Array(vec![
+                Token::Tuple(vec![
+                    Token::Bytes(vec![1, 2, 3, 4]),
+                    Token::String("poseidon2".to_string()),
+                    Token::Bytes32(Digest::from_bytes([0x11; 32])),
+                    Token::Uint(0),
+                ]),
+                Token::Tuple(vec![
+                    Token::Bytes(vec![]),
+                    Token::String("sha-256".to_string()),
+                    Token::Bytes32(Digest::from_bytes([0x33; 32])),
+                    Token::Uint(1),
+                ]),
+            ]),
+            Token::Bytes32(Digest::from_bytes([0x22; 32])),
+        ]);
+
+        let data = token.encode();
+        assert_eq!(Ok(token), kind.decode(&data, 0, &mut data.len()));
+    }
+
+    #[test]
+    fn encode_succinct_proof() {
+        let proof: Proof = ciborium::from_reader(
+            std::fs::File::open("./resources/receipts/1.2.0-1.2.0/succinct_16.bin").unwrap(),
+        )
+        .unwrap();
+        let succinct = proof.inner.succinct().unwrap();
+
+        let encoded = proof.to_abi_encoded().unwrap();
+
+        assert_eq!(words(&["2", "40", "60"]), encoded[..3 * WORD]);
+        assert_eq!(
+            succinct.verifier_parameters.as_bytes(),
+            &encoded[4 * WORD..5 * WORD]
+        );
+        let seal_len = succinct.seal.len() * 4;
+        assert_eq!(
+            words(&[&format!("{seal_len:x}")]),
+            encoded[5 * WORD..6 * WORD]
+        );
+        assert_eq!(
+            words_to_bytes(&succinct.seal),
+            encoded[6 * WORD..6 * WORD + seal_len]
+        );
+        assert_eq!(
+            6 * WORD + seal_len.div_ceil(WORD) * WORD + 2 * WORD,
+            encoded.len()
+        );
+
+        let decoded = decode_abi(&encoded).unwrap();
+        let decoded = decoded.inner.succinct().unwrap();
+        assert_eq!(succinct.control_id, decoded.control_id);
+        assert_eq!(
+            succinct.control_inclusion_proof,
+            decoded.control_inclusion_proof
+        );
+        assert_eq!(succinct.claim.digest(), decoded.claim.digest());
+        assert_eq!(succinct.seal, decoded.seal);
+    }
+
+    #[test]
+    fn encode_composite_proof() {
+        let proof: Proof = ciborium::from_reader(
+            std::fs::File::open("./resources/receipts/1.2.0-1.2.0/poseidon2_16.bin").unwrap(),
+        )
+        .unwrap();
+        let composite = proof.inner.composite().unwrap();
+
+        let encoded = proof.to_abi_encoded().unwrap();
+
+        assert_eq!(words(&["1", "60"]), encoded[..2 * WORD]);
+        assert_eq!(
+            composite.verifier_parameters.as_bytes(),
+            &encoded[2 * WORD..3 * WORD]
+        );
+        assert_eq!(
+            words(&[&format!("{:x}", composite.segments.len())]),
+            encoded[3 * WORD..4 * WORD]
+        );
+
+        let decoded = decode_abi(&encoded).unwrap();
+        let decoded = decoded.inner.composite().unwrap();
+        assert_eq!(composite.segments.len(), decoded.segments.len());
+        for (segment, decoded) in composite.segments.iter().zip(&decoded.segments) {
+            assert_eq!(segment.seal, decoded.seal);
+            assert_eq!(segment.index, decoded.index);
+            assert_eq!(segment.claim.digest(), decoded.claim.digest());
+        }
+    }
+
+    #[test]
+    fn reject_composite_with_assumptions() {
+        let proof: Proof = ciborium::from_reader(
+            std::fs::File::open("./resources/receipts/1.2.0-1.2.0/poseidon2_16.bin").unwrap(),
+        )
+        .unwrap();
+        let assumption = proof.inner.clone().into();
+        let composite = proof
+            .inner
+            .composite()
+            .unwrap()
+            .clone()
+            .with_assumption_receipt(assumption);
+
+        assert_eq!(
+            Err(AbiEncodeError::UnsupportedProofType),
+            Proof::new(composite.into()).to_abi_encoded()
+        );
+    }
+
+    #[test]
+    fn decode_invalid_data() {
+        assert_eq!(
+            AbiDecodeError::UnknownProofType(3),
+            decode_abi(&words(&["3"])).unwrap_err()
+        );
+        assert_eq!(
+            AbiDecodeError::InvalidData,
+            decode_abi(&words(&["1", "60"])).unwrap_err()
+        );
+        assert_eq!(AbiDecodeError::InvalidData, decode_abi(&[]).unwrap_err());
+    }
+
+    #[test]
+    fn reject_offsets_sharing_a_tail() {
+        // A `bytes[]` of 64 items whose offsets all point to the same 4 KiB tail: decoding it
+        // would allocate 256 KiB out of less than 7 KiB of data.
+        let items = 64;
+        let len = 4096;
+        let shared_tail = format!("{:x}", items * WORD);
+        let mut data = words(&[&format!("{items:x}")]);
+        data.extend(words(&vec![shared_tail.as_str(); items]));
+        data.extend(words(&[&format!("{len:x}")]));
+        data.resize(data.len() + len, 0xff);
+
+        assert_eq!(
+            Err(AbiDecodeError::InvalidData),
+            Kind::Array(Box::new(Kind::Bytes)).decode(&data, 0, &mut data.len())
+        );
+    }
+
+    #[test]
+    fn decode_items_with_distinct_tails() {
+        let token = Token::Array(vec![Token::Bytes(vec![0xff; 4096]); 2]);
+        let data = token.encode();
+
+        assert_eq!(
+            Ok(token),
+            Kind::Array(Box::new(Kind::Bytes)).decode(&data, 0, &mut data.len())
+        );
+    }
+}
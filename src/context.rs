@@ -17,28 +17,64 @@
 
 use crate::receipt_claim::ReceiptClaim;
 use crate::{
-    poseidon2_injection::Poseidon2Mix, receipt::merkle::MerkleProof,
-    receipt::succinct::SuccinctReceiptVerifierParameters, receipt_claim::Assumption,
-    segment::SegmentReceiptVerifierParameters, Proof,
+    poseidon2_injection::Poseidon2Mix, receipt::groth16::Groth16ReceiptVerifierParameters,
+    receipt::merkle::MerkleProof, receipt::succinct::SuccinctReceiptVerifierParameters,
+    receipt_claim::Assumption, segment::SegmentReceiptVerifierParameters, Proof,
 };
-use alloc::{boxed::Box, collections::BTreeMap, string::String};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::fmt;
 use risc0_zkp_v1::{adapter::ProtocolInfo, core::digest::Digest, verify::VerificationError};
 
+mod suites;
 pub mod v1;
 pub mod v2;
 pub mod v3;
 
+pub use suites::SuiteMap;
+
+/// Default maximum number of bytes of the seals of a proof accepted by a verifier: 8 MB.
+pub const DEFAULT_MAX_PROOF_BYTES: usize = 8 * 1024 * 1024;
+
+/// Verifier options that don't depend on the zkVM version.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct VerifierOptions {
+    /// Proofs whose seals exceed this number of bytes are rejected.
+    pub max_proof_bytes: usize,
+    /// Journals that exceed this number of bytes are rejected, if set.
+    pub max_journal_bytes: Option<usize>,
+    /// Fake receipts are rejected unless this is set, even with the `dev-mode` feature.
+    #[cfg(feature = "dev-mode")]
+    pub allow_fake_receipts: bool,
+}
+
+impl Default for VerifierOptions {
+    fn default() -> Self {
+        Self {
+            max_proof_bytes: DEFAULT_MAX_PROOF_BYTES,
+            max_journal_bytes: None,
+            #[cfg(feature = "dev-mode")]
+            allow_fake_receipts: false,
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct VerifierParameters<Segment, Succinct, HashSuite> {
     /// Parameters for verification of [SuccinctReceipt].
     pub succinct_verifier_parameters: Option<SuccinctReceiptVerifierParameters>,
     /// A registry of hash functions to be used by the verification process.
-    pub suites: BTreeMap<String, HashSuite>,
+    pub suites: SuiteMap<HashSuite>,
     /// Parameters for verification of [SegmentReceipt].
     pub segment_verifier_parameters: Option<SegmentReceiptVerifierParameters>,
+    /// Parameters for verification of [Groth16Receipt](crate::Groth16Receipt).
+    pub groth16_verifier_parameters: Option<Groth16ReceiptVerifierParameters>,
 
     pub segment: Segment,
 
     pub succinct: Succinct,
+
+    /// Options that apply to every proof version.
+    pub options: VerifierOptions,
 }
 
 impl<Segment: CircuitInfo, Succinct: CircuitInfo, HashSuite>
@@ -52,9 +88,47 @@ impl<Segment: CircuitInfo, Succinct: CircuitInfo, HashSuite>
         self.succinct_verifier_parameters.as_ref()
     }
 
+    pub fn groth16_verifier_parameters(&self) -> Option<&Groth16ReceiptVerifierParameters> {
+        self.groth16_verifier_parameters.as_ref()
+    }
+
     pub fn suite(&self, hashfn: &str) -> Option<&HashSuite> {
         self.suites.get(hashfn)
     }
+
+    /// Format these parameters as the fields of the `name` verifier labeled with `version`: the
+    /// [fmt::Debug] implementation of the versioned contexts.
+    pub(crate) fn debug_fmt(
+        &self,
+        name: &str,
+        version: &str,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        fn presence<T>(value: &Option<T>) -> fmt::Arguments<'static> {
+            match value {
+                Some(_) => format_args!("present"),
+                None => format_args!("missing"),
+            }
+        }
+
+        f.debug_struct(name)
+            .field("version", &version)
+            .field("circuit", &format_args!("{}", self.segment.protocol()))
+            .field(
+                "segment_params",
+                &presence(&self.segment_verifier_parameters),
+            )
+            .field(
+                "succinct_params",
+                &presence(&self.succinct_verifier_parameters),
+            )
+            .field(
+                "groth16_params",
+                &presence(&self.groth16_verifier_parameters),
+            )
+            .field("suites", &self.suites.keys().collect::<Vec<_>>())
+            .finish()
+    }
 }
 
 pub trait CircuitInfo {
@@ -82,6 +156,12 @@ pub trait VerifierContext {
     fn mut_verifier_parameters(
         &mut self,
     ) -> &mut VerifierParameters<Self::Segment, Self::Succinct, Self::HashSuite>;
+
+    /// The zkVM version label of this context, e.g. `risc0/v1.2`. The default is `risc0/custom`.
+    fn version(&self) -> &'static str {
+        "risc0/custom"
+    }
+
     fn boxed_clone(&self) -> BoxedVC<Self>;
 
     fn boxed_succinct_verifier_with_control_root(&self, control_root: Digest) -> BoxedVC<Self>;
@@ -127,7 +207,31 @@ pub trait VerifierContext {
 
     fn segment_seal_offset(&self) -> usize;
 
+    /// The number of words of the seal of a succinct receipt generated by the recursion circuit.
+    fn succinct_seal_len(&self) -> usize {
+        crate::receipt::succinct::SUCCINCT_SEAL_LEN
+    }
+
     fn set_poseidon2_mix_impl(&mut self, poseidon2: Box<dyn Poseidon2Mix + Send + Sync + 'static>);
+
+    /// Verify the proof against the given image id and journal digest and return the verified
+    /// claim. Fails with [VerificationError::ReceiptFormatError] if the claim is pruned.
+    fn verify_full(
+        &self,
+        vk: Digest,
+        proof: &Proof,
+        pubs_digest: Digest,
+    ) -> Result<ReceiptClaim, VerificationError>
+    where
+        Self: Sized,
+    {
+        proof.verify(self, vk, pubs_digest)?;
+        proof
+            .inner
+            .claim()?
+            .value()
+            .map_err(|_| VerificationError::ReceiptFormatError)
+    }
 }
 
 impl<Seg: CircuitInfo, Suc: CircuitInfo, T> VerifierContext
@@ -147,6 +251,10 @@ impl<Seg: CircuitInfo, Suc: CircuitInfo, T> VerifierContext
         self.as_mut().mut_verifier_parameters()
     }
 
+    fn version(&self) -> &'static str {
+        self.as_ref().version()
+    }
+
     fn boxed_clone(&self) -> BoxedVC<Self> {
         self.as_ref().boxed_clone()
     }
@@ -199,12 +307,16 @@ impl<Seg: CircuitInfo, Suc: CircuitInfo, T> VerifierContext
         self.as_ref().segment_seal_offset()
     }
 
+    fn succinct_seal_len(&self) -> usize {
+        self.as_ref().succinct_seal_len()
+    }
+
     fn set_poseidon2_mix_impl(&mut self, poseidon2: Box<dyn Poseidon2Mix + Send + Sync + 'static>) {
         self.as_mut().set_poseidon2_mix_impl(poseidon2)
     }
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
 /// The segment info.
 pub struct SegmentInfo {
     /// Hash function name
@@ -218,4 +330,342 @@ impl SegmentInfo {
     pub fn new(hash: String, po2: u32) -> Self {
         Self { hash, po2 }
     }
+
+    /// Build a [SegmentInfo] from a borrowed hash function name: handy to write the expected
+    /// values in tests.
+    pub fn dummy(hash: &str, po2: u32) -> Self {
+        Self::new(hash.into(), po2)
+    }
+
+    /// The number of cycles proved by the segment, i.e. `2^po2`: saturates to `u64::MAX` for a
+    /// `po2` that doesn't fit.
+    pub fn cycles(&self) -> u64 {
+        1u64.checked_shl(self.po2).unwrap_or(u64::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{circuit, translate::Translate};
+    use risc0_zkp_v1::adapter::ProtocolInfo;
+    use rstest::rstest;
+
+    const FORKED_CIRCUIT_INFO: ProtocolInfo = ProtocolInfo(*b"FORKED:rv32im___");
+
+    fn forked_segment_params(
+        params: SegmentReceiptVerifierParameters,
+    ) -> SegmentReceiptVerifierParameters {
+        SegmentReceiptVerifierParameters {
+            circuit_info: FORKED_CIRCUIT_INFO,
+            ..params
+        }
+    }
+
+    #[rstest]
+    #[case::v1(
+        v1::V1::custom(
+            &circuit::v1_2::CIRCUIT,
+            &circuit::v1_2::recursive::CIRCUIT,
+            forked_segment_params(SegmentReceiptVerifierParameters::v1_2()),
+            SuccinctReceiptVerifierParameters::v1_2(),
+        ),
+        "./resources/receipts/1.2.0-1.2.0/poseidon2_16.bin"
+    )]
+    #[case::v2(
+        v2::V2::custom(
+            &circuit::v2_1::CIRCUIT,
+            &circuit::v2_1::recursive::CIRCUIT,
+            forked_segment_params(SegmentReceiptVerifierParameters::v2_1()),
+            SuccinctReceiptVerifierParameters::v2_1(),
+        ),
+        "./resources/receipts/2.1.0-2.1.0/poseidon2_16.bin"
+    )]
+    #[case::v3(
+        v3::V3::custom(
+            &circuit::v3_0::CIRCUIT,
+            &circuit::v3_0::recursive::CIRCUIT,
+            forked_segment_params(SegmentReceiptVerifierParameters::v3_0()),
+            SuccinctReceiptVerifierParameters::v3_0(),
+        ),
+        "./resources/receipts/3.0.0-3.0.0/poseidon2_16.bin"
+    )]
+    fn custom_context_with_mismatched_circuit_info(
+        #[case] ctx: impl VerifierContext,
+        #[case] path: &str,
+    ) {
+        let proof: Proof = ciborium::from_reader(std::fs::File::open(path).unwrap()).unwrap();
+
+        let res = proof.inner.verify_integrity_with_context(&ctx);
+
+        assert_eq!(
+            Err(VerificationError::CircuitInfoMismatch {
+                expected: ctx.segment_circuit_info(),
+                received: FORKED_CIRCUIT_INFO,
+            }),
+            res
+        );
+    }
+
+    #[rstest]
+    #[case::v1(
+        v1::V1::v1_2(),
+        v1::V1::v1_2(),
+        "./resources/receipts/1.2.0-1.2.0/poseidon2_16.bin"
+    )]
+    #[case::v1_succinct(
+        v1::V1::v1_2(),
+        v1::V1::v1_2(),
+        "./resources/receipts/1.2.0-1.2.0/succinct_16.bin"
+    )]
+    #[case::v2(
+        v2::V2::v2_1(),
+        v2::V2::v2_1(),
+        "./resources/receipts/2.1.0-2.1.0/poseidon2_16.bin"
+    )]
+    #[case::v2_succinct(
+        v2::V2::v2_1(),
+        v2::V2::v2_1(),
+        "./resources/receipts/2.1.0-2.1.0/succinct_16.bin"
+    )]
+    #[case::v3(
+        v3::V3::v3_0(),
+        v3::V3::v3_0(),
+        "./resources/receipts/3.0.0-3.0.0/poseidon2_16.bin"
+    )]
+    #[case::v3_succinct(
+        v3::V3::v3_0(),
+        v3::V3::v3_0(),
+        "./resources/receipts/3.0.0-3.0.0/succinct_16.bin"
+    )]
+    fn cloned_parameters_verify_as_the_original<C: VerifierContext>(
+        #[case] ctx: C,
+        #[case] mut cloned: C,
+        #[case] path: &str,
+    ) where
+        C::Segment: Clone,
+        C::Succinct: Clone,
+        C::HashSuite: Clone,
+    {
+        let proof: Proof = ciborium::from_reader(std::fs::File::open(path).unwrap()).unwrap();
+        cloned.mut_verifier_parameters().suites.clear();
+        *cloned.mut_verifier_parameters() = ctx.verifier_parameters().clone();
+
+        let expected = proof.inner.verify_integrity_with_context(&ctx);
+
+        assert!(expected.is_ok());
+        assert_eq!(expected, proof.inner.verify_integrity_with_context(&cloned));
+    }
+
+    macro_rules! assert_suites_are_shared {
+        ($ctx:expr) => {
+            let ctx = $ctx;
+            let params = ctx.verifier_parameters();
+            let cloned = params.clone();
+
+            assert!(!params.suites.is_empty());
+            assert_eq!(params.suites.len(), cloned.suites.len());
+            for (name, suite) in &params.suites {
+                let cloned = &cloned.suites[name];
+                assert!(alloc::rc::Rc::ptr_eq(&suite.hashfn, &cloned.hashfn));
+                assert!(alloc::rc::Rc::ptr_eq(&suite.rng, &cloned.rng));
+            }
+        };
+    }
+
+    #[test]
+    fn cloned_parameters_share_the_hash_suites() {
+        assert_suites_are_shared!(v1::V1::v1_2());
+        assert_suites_are_shared!(v2::V2::v2_1());
+        assert_suites_are_shared!(v3::V3::v3_0());
+    }
+
+    #[cfg(feature = "std")]
+    macro_rules! assert_default_suites_are_shared {
+        ($first:expr, $second:expr) => {
+            let (first, second) = ($first, $second);
+            let first = &first.verifier_parameters().suites;
+            let second = &second.verifier_parameters().suites;
+
+            assert_eq!(first.len(), second.len());
+            for (name, suite) in first {
+                assert!(alloc::rc::Rc::ptr_eq(&suite.hashfn, &second[name].hashfn));
+                assert!(alloc::rc::Rc::ptr_eq(&suite.rng, &second[name].rng));
+            }
+        };
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn verifiers_share_the_default_hash_suites() {
+        assert_default_suites_are_shared!(v1::V1::v1_0(), v1::V1::v1_2());
+        assert_default_suites_are_shared!(v2::V2::v2_1(), v2::V2::v2_3());
+        assert_default_suites_are_shared!(v3::V3::v3_0(), v3::V3::v3_0());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn default_hash_suites_ref_is_built_once_per_thread() {
+        use crate::circuit::{v1_0, v1_2, v2_1};
+        type V1_0 = v1::V1<v1_0::CircuitImpl, v1_0::recursive::CircuitImpl>;
+        type V1_2 = v1::V1<v1_2::CircuitImpl, v1_2::recursive::CircuitImpl>;
+        type V2_1 = v2::V2<v2_1::CircuitImpl, v2_1::recursive::CircuitImpl>;
+
+        let suites = V1_2::default_hash_suites_ref();
+        assert!(core::ptr::eq(suites, V1_0::default_hash_suites_ref()));
+        for (name, suite) in V1_2::v1_2().verifier_parameters().suites.iter() {
+            assert!(alloc::rc::Rc::ptr_eq(&suite.hashfn, &suites[name].hashfn));
+        }
+        let address = |suites: &SuiteMap<_>| suites as *const _ as usize;
+        let other = std::thread::spawn(move || address(V2_1::default_hash_suites_ref()));
+        assert_ne!(
+            address(V2_1::default_hash_suites_ref()),
+            other.join().unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case::composite("./resources/receipts/1.2.0-1.2.0/poseidon2_16.bin")]
+    #[case::succinct("./resources/receipts/1.2.0-1.2.0/succinct_16.bin")]
+    fn verify_full_returns_the_verified_claim(#[case] path: &str) {
+        let proof: Proof = ciborium::from_reader(std::fs::File::open(path).unwrap()).unwrap();
+        let claim = proof.claim().unwrap().value().unwrap();
+        let image_id = claim.pre_state_digest();
+        let journal_digest = claim.output_journal_digest().unwrap();
+        let ctx = v1::V1::v1_2();
+
+        let verified = ctx.verify_full(image_id, &proof, journal_digest).unwrap();
+
+        assert_eq!(claim, verified);
+        assert!(matches!(
+            ctx.verify_full(Digest::from([1_u32; 8]), &proof, journal_digest),
+            Err(VerificationError::ClaimDigestMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn debug_shows_version_circuit_parameters_and_suites() {
+        let debug = std::format!("{:?}", crate::v1_2());
+
+        assert!(debug.starts_with("V1 { version: \"risc0/v1.2\""));
+        assert!(debug.contains("RV32IM:rev1v1___"));
+        assert!(debug.contains(
+            "segment_params: present, succinct_params: present, groth16_params: present"
+        ));
+        assert!(debug.contains("\"poseidon2\", \"sha-256\""));
+    }
+
+    #[test]
+    fn debug_shows_missing_parameters() {
+        let ctx = v2::V2::empty(&circuit::v2_1::CIRCUIT, &circuit::v2_1::recursive::CIRCUIT);
+
+        assert_eq!(
+            "V2 { version: \"risc0/v2\", circuit: RV32IM:v2_______, segment_params: missing, \
+             succinct_params: missing, groth16_params: missing, suites: [] }",
+            std::format!("{ctx:?}")
+        );
+    }
+
+    #[rstest]
+    #[case::v1_0(crate::Verifier::boxed(crate::v1_0()), "risc0/v1.0")]
+    #[case::v2_1(crate::Verifier::boxed(crate::v2_1()), "risc0/v2.1")]
+    #[case::v2_3(crate::Verifier::boxed(crate::v2_3()), "risc0/v2.3")]
+    #[case::v3_0(crate::Verifier::boxed(crate::v3_0()), "risc0/v3.0")]
+    fn describe_contains_the_version(
+        #[case] verifier: Box<dyn crate::Verifier>,
+        #[case] version: &str,
+    ) {
+        assert_eq!(version, verifier.version());
+        assert!(verifier.describe().starts_with(version));
+        assert_eq!(verifier.describe(), std::format!("{verifier:?}"));
+    }
+
+    #[test]
+    fn describe_shows_missing_parameters() {
+        let ctx = v2::V2::empty(&circuit::v2_1::CIRCUIT, &circuit::v2_1::recursive::CIRCUIT);
+
+        assert_eq!(
+            "risc0/v2 { circuit: RV32IM:v2_______, segment_params: missing, \
+             succinct_params: missing, groth16_params: missing, suites: [] }",
+            crate::Verifier::describe(&ctx)
+        );
+    }
+
+    #[test]
+    fn display_v2_1() {
+        assert_eq!("risc0/v2.1", std::format!("{}", crate::v2_1()));
+    }
+
+    #[test]
+    fn control_ids_are_exposed_through_a_boxed_verifier() {
+        let verifier = crate::Verifier::boxed(crate::v1_2());
+
+        assert!(!verifier.control_ids().unwrap().is_empty());
+        assert_eq!(
+            verifier.control_ids().unwrap().len(),
+            verifier.control_id_count()
+        );
+        assert_eq!(
+            crate::SegmentReceiptVerifierParameters::v1_2().control_ids,
+            *verifier.control_ids().unwrap()
+        );
+    }
+
+    #[test]
+    fn control_id_count_without_segment_parameters() {
+        let ctx = v2::V2::empty(&circuit::v2_1::CIRCUIT, &circuit::v2_1::recursive::CIRCUIT);
+
+        assert_eq!(None, crate::Verifier::control_ids(&ctx));
+        assert_eq!(0, crate::Verifier::control_id_count(&ctx));
+    }
+
+    #[rstest]
+    #[case::v1_0(
+        crate::Verifier::boxed(crate::v1_0()),
+        circuit::v1_0::recursive::control_id::ALLOWED_CONTROL_ROOT
+    )]
+    #[case::v1_1(
+        crate::Verifier::boxed(crate::v1_1()),
+        circuit::v1_1::recursive::control_id::ALLOWED_CONTROL_ROOT
+    )]
+    #[case::v1_2(
+        crate::Verifier::boxed(crate::v1_2()),
+        circuit::v1_2::recursive::control_id::ALLOWED_CONTROL_ROOT
+    )]
+    #[case::v2_0(
+        crate::Verifier::boxed(crate::v2_0()),
+        circuit::v2_0::recursive::control_id::ALLOWED_CONTROL_ROOT.translate()
+    )]
+    #[case::v2_1(
+        crate::Verifier::boxed(crate::v2_1()),
+        circuit::v2_1::recursive::control_id::ALLOWED_CONTROL_ROOT.translate()
+    )]
+    #[case::v2_2(
+        crate::Verifier::boxed(crate::v2_2()),
+        circuit::v2_2::recursive::control_id::ALLOWED_CONTROL_ROOT.translate()
+    )]
+    #[case::v2_3(
+        crate::Verifier::boxed(crate::v2_3()),
+        circuit::v2_3::recursive::control_id::ALLOWED_CONTROL_ROOT.translate()
+    )]
+    #[case::v3_0(
+        crate::Verifier::boxed(crate::v3_0()),
+        circuit::v3_0::recursive::control_id::ALLOWED_CONTROL_ROOT.translate()
+    )]
+    fn succinct_control_root_is_the_allowed_control_root(
+        #[case] verifier: Box<dyn crate::Verifier>,
+        #[case] expected: Digest,
+    ) {
+        assert_eq!(Some(expected), verifier.succinct_control_root());
+    }
+
+    #[rstest]
+    #[case::zero(0, 1)]
+    #[case::segment(20, 1 << 20)]
+    #[case::largest(63, 1 << 63)]
+    #[case::saturates(64, u64::MAX)]
+    #[case::saturates_any_larger(u32::MAX, u64::MAX)]
+    fn segment_info_cycles(#[case] po2: u32, #[case] expected: u64) {
+        assert_eq!(expected, SegmentInfo::dummy("poseidon2", po2).cycles());
+    }
 }
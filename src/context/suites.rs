@@ -0,0 +1,182 @@
+// Copyright 2024, Horizen Labs, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The registry of hash suites of a verifier. It's a `BTreeMap` by default; with the `wasm`
+//! feature it's a sorted `Vec` that exposes the same subset of the map API: a verifier holds
+//! at most three suites, so lookups stay cheap and the `BTreeMap` code is not pulled in the
+//! wasm binary.
+
+/// The registry of hash suites, indexed by the hash function name.
+#[cfg(not(feature = "wasm"))]
+pub type SuiteMap<H> = alloc::collections::BTreeMap<alloc::string::String, H>;
+
+/// The registry of hash suites, indexed by the hash function name.
+#[cfg(feature = "wasm")]
+pub type SuiteMap<H> = VecMap<H>;
+
+#[cfg(feature = "wasm")]
+pub use vec_map::VecMap;
+
+#[cfg(feature = "wasm")]
+mod vec_map {
+    use alloc::{string::String, vec::Vec};
+    use core::ops::Index;
+
+    /// A map from names to values backed by a `Vec` sorted by name.
+    #[derive(Debug, Clone)]
+    pub struct VecMap<H> {
+        entries: Vec<(String, H)>,
+    }
+
+    impl<H> Default for VecMap<H> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<H> VecMap<H> {
+        pub const fn new() -> Self {
+            Self {
+                entries: Vec::new(),
+            }
+        }
+
+        fn position(&self, name: &str) -> Result<usize, usize> {
+            self.entries
+                .binary_search_by(|(key, _)| key.as_str().cmp(name))
+        }
+
+        pub fn get(&self, name: &str) -> Option<&H> {
+            self.position(name).ok().map(|i| &self.entries[i].1)
+        }
+
+        pub fn get_mut(&mut self, name: &str) -> Option<&mut H> {
+            self.position(name).ok().map(|i| &mut self.entries[i].1)
+        }
+
+        pub fn contains_key(&self, name: &str) -> bool {
+            self.position(name).is_ok()
+        }
+
+        pub fn insert(&mut self, name: String, value: H) -> Option<H> {
+            match self.position(&name) {
+                Ok(i) => Some(core::mem::replace(&mut self.entries[i].1, value)),
+                Err(i) => {
+                    self.entries.insert(i, (name, value));
+                    None
+                }
+            }
+        }
+
+        pub fn remove(&mut self, name: &str) -> Option<H> {
+            self.position(name).ok().map(|i| self.entries.remove(i).1)
+        }
+
+        pub fn clear(&mut self) {
+            self.entries.clear()
+        }
+
+        pub fn len(&self) -> usize {
+            self.entries.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.entries.is_empty()
+        }
+
+        pub fn iter(&self) -> impl Iterator<Item = (&String, &H)> {
+            self.entries.iter().map(|(name, value)| (name, value))
+        }
+
+        pub fn keys(&self) -> impl Iterator<Item = &String> {
+            self.entries.iter().map(|(name, _)| name)
+        }
+    }
+
+    impl<H> FromIterator<(String, H)> for VecMap<H> {
+        fn from_iter<I: IntoIterator<Item = (String, H)>>(iter: I) -> Self {
+            let mut map = Self::new();
+            for (name, value) in iter {
+                map.insert(name, value);
+            }
+            map
+        }
+    }
+
+    impl<H, const N: usize> From<[(String, H); N]> for VecMap<H> {
+        fn from(entries: [(String, H); N]) -> Self {
+            entries.into_iter().collect()
+        }
+    }
+
+    impl<'a, H> IntoIterator for &'a VecMap<H> {
+        type Item = (&'a String, &'a H);
+        type IntoIter = core::iter::Map<
+            core::slice::Iter<'a, (String, H)>,
+            fn(&'a (String, H)) -> (&'a String, &'a H),
+        >;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.entries.iter().map(|(name, value)| (name, value))
+        }
+    }
+
+    impl<H, Q: AsRef<str> + ?Sized> Index<&Q> for VecMap<H> {
+        type Output = H;
+
+        fn index(&self, name: &Q) -> &H {
+            self.get(name.as_ref()).expect("no entry found for key")
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn keeps_entries_sorted_by_name() {
+            let mut map = VecMap::from([
+                ("sha-256".into(), 3),
+                ("blake2b".into(), 1),
+                ("poseidon2".into(), 2),
+            ]);
+
+            assert_eq!(
+                ["blake2b", "poseidon2", "sha-256"],
+                map.keys().map(String::as_str).collect::<Vec<_>>()[..]
+            );
+            assert_eq!(Some(&2), map.get("poseidon2"));
+            assert_eq!(3, map["sha-256"]);
+
+            assert_eq!(Some(2), map.insert("poseidon2".into(), 4));
+            assert_eq!(Some(4), map.remove("poseidon2"));
+            assert_eq!(None, map.remove("poseidon2"));
+            assert!(!map.contains_key("poseidon2"));
+            assert_eq!(2, map.len());
+
+            *map.get_mut("blake2b").unwrap() = 5;
+            assert_eq!(
+                [("blake2b", 5), ("sha-256", 3)],
+                map.iter()
+                    .map(|(k, v)| (k.as_str(), *v))
+                    .collect::<Vec<_>>()[..]
+            );
+
+            map.clear();
+            assert!(map.is_empty());
+        }
+    }
+}
@@ -15,14 +15,17 @@
 // limitations under the License.
 //
 
-use super::{CircuitInfo, VerifierContext, VerifierParameters};
+use super::{CircuitInfo, SuiteMap, VerifierContext, VerifierParameters};
 use crate::receipt_claim::{MaybePruned, ReceiptClaim};
 use crate::{
     circuit, circuit::CircuitCoreDefV1, poseidon2_injection::Poseidon2Mix,
-    receipt::merkle::MerkleProof, receipt::succinct::SuccinctReceiptVerifierParameters,
+    receipt::groth16::Groth16ReceiptVerifierParameters, receipt::merkle::MerkleProof,
+    receipt::succinct::SuccinctReceiptVerifierParameters,
     segment::SegmentReceiptVerifierParameters,
 };
-use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+use core::fmt;
+
+use alloc::{boxed::Box, vec::Vec};
 use risc0_binfmt_v1::{ExitCode, SystemState};
 //noinspection RsUnresolvedPath RustRover False positive SystemStateLayout
 use risc0_circuit_rv32im_v1::layout::{SystemStateLayout, OUT_LAYOUT};
@@ -32,9 +35,9 @@ use risc0_core_v1::{
 };
 use risc0_zkp_v1::adapter::ProtocolInfo;
 use risc0_zkp_v1::{
-    adapter::PROOF_SYSTEM_INFO, core::digest::Digest, core::hash::blake2b::Blake2bCpuHashSuite,
-    core::hash::poseidon2::Poseidon2HashSuite, core::hash::sha::Sha256HashSuite,
-    core::hash::HashSuite, layout::Tree, verify::VerificationError,
+    adapter::PROOF_SYSTEM_INFO, core::digest::Digest, core::hash::poseidon2::Poseidon2HashSuite,
+    core::hash::sha::Sha256HashSuite, core::hash::HashSuite, layout::Tree,
+    verify::VerificationError,
 };
 
 const OUTPUT_SIZE: usize = 138;
@@ -113,6 +116,20 @@ pub struct V1<SC: CircuitCoreDefV1, RC: CircuitCoreDefV1> {
     circuit: &'static SC,
 
     recursive_circuit: &'static RC,
+
+    version: &'static str,
+}
+
+impl<SC: CircuitCoreDefV1, RC: CircuitCoreDefV1> fmt::Debug for V1<SC, RC> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.verifier_parameters.debug_fmt("V1", self.version, f)
+    }
+}
+
+impl<SC: CircuitCoreDefV1, RC: CircuitCoreDefV1> fmt::Display for V1<SC, RC> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.version)
+    }
 }
 
 impl<SC: CircuitCoreDefV1, RC: CircuitCoreDefV1> VerifierContext for V1<SC, RC> {
@@ -131,6 +148,10 @@ impl<SC: CircuitCoreDefV1, RC: CircuitCoreDefV1> VerifierContext for V1<SC, RC>
         &mut self.verifier_parameters
     }
 
+    fn version(&self) -> &'static str {
+        self.version
+    }
+
     fn boxed_clone(
         &self,
     ) -> alloc::boxed::Box<
@@ -144,6 +165,7 @@ impl<SC: CircuitCoreDefV1, RC: CircuitCoreDefV1> VerifierContext for V1<SC, RC>
             verifier_parameters: self.verifier_parameters.clone(),
             circuit: self.circuit,
             recursive_circuit: self.recursive_circuit,
+            version: self.version,
         };
         alloc::boxed::Box::new(cloned)
     }
@@ -185,7 +207,7 @@ impl<SC: CircuitCoreDefV1, RC: CircuitCoreDefV1> VerifierContext for V1<SC, RC>
             .suite(hashfn)
             .ok_or(VerificationError::InvalidHashSuite)?;
 
-        log::debug!("SegmentReceipt::verify_integrity_with_context");
+        debug!("SegmentReceipt::verify_integrity_with_context");
         let check_code = |_, control_id: &Digest| -> Result<(), VerificationError> {
             params.control_ids.contains(control_id).then_some(()).ok_or(
                 VerificationError::ControlVerificationError {
@@ -213,7 +235,7 @@ impl<SC: CircuitCoreDefV1, RC: CircuitCoreDefV1> VerifierContext for V1<SC, RC>
             control_inclusion_proof
                 .verify(control_id, &params.control_root, suite.hashfn.as_ref())
                 .map_err(|_| {
-                    log::debug!(
+                    debug!(
                         "failed to verify control inclusion proof for {control_id} against root {} with {}",
                         params.control_root,
                         suite.name,
@@ -234,67 +256,116 @@ impl<SC: CircuitCoreDefV1, RC: CircuitCoreDefV1> VerifierContext for V1<SC, RC>
     }
 
     fn set_poseidon2_mix_impl(&mut self, poseidon2: Box<dyn Poseidon2Mix + Send + Sync + 'static>) {
-        self.mut_verifier_parameters()
-            .suites
-            .entry("poseidon2".into())
-            .and_modify(|s| {
-                s.hashfn =
-                    alloc::rc::Rc::new(crate::poseidon2_injection::Poseidon2Impl::new(poseidon2))
-            });
+        if let Some(s) = self.mut_verifier_parameters().suites.get_mut("poseidon2") {
+            s.hashfn = alloc::rc::Rc::new(crate::poseidon2_injection::Poseidon2Impl::new(poseidon2))
+        }
     }
 }
 
 impl V1<circuit::v1_0::CircuitImpl, circuit::v1_0::recursive::CircuitImpl> {
     /// Create an empty [V1] for any risc0 proof generate for any `1.0.x` vm version.
     pub fn v1_0() -> Self {
-        Self::empty(&circuit::v1_0::CIRCUIT, &circuit::v1_0::recursive::CIRCUIT)
-            .with_suites(Self::default_hash_suites())
-            .with_segment_verifier_parameters(SegmentReceiptVerifierParameters::v1_0())
-            .with_succinct_verifier_parameters(SuccinctReceiptVerifierParameters::v1_0())
+        Self::custom(
+            &circuit::v1_0::CIRCUIT,
+            &circuit::v1_0::recursive::CIRCUIT,
+            SegmentReceiptVerifierParameters::v1_0(),
+            SuccinctReceiptVerifierParameters::v1_0(),
+        )
+        .with_groth16_verifier_parameters(Groth16ReceiptVerifierParameters::v1_0())
+        .with_version("risc0/v1.0")
     }
 }
 
 impl V1<circuit::v1_1::CircuitImpl, circuit::v1_1::recursive::CircuitImpl> {
     /// Create an empty [V1] for any risc0 proof generate for any `1.1.x` vm version.
     pub fn v1_1() -> Self {
-        Self::empty(&circuit::v1_1::CIRCUIT, &circuit::v1_1::recursive::CIRCUIT)
-            .with_suites(Self::default_hash_suites())
-            .with_segment_verifier_parameters(SegmentReceiptVerifierParameters::v1_1())
-            .with_succinct_verifier_parameters(SuccinctReceiptVerifierParameters::v1_1())
+        Self::custom(
+            &circuit::v1_1::CIRCUIT,
+            &circuit::v1_1::recursive::CIRCUIT,
+            SegmentReceiptVerifierParameters::v1_1(),
+            SuccinctReceiptVerifierParameters::v1_1(),
+        )
+        .with_groth16_verifier_parameters(Groth16ReceiptVerifierParameters::v1_1())
+        .with_version("risc0/v1.1")
     }
 }
 
 impl V1<circuit::v1_2::CircuitImpl, circuit::v1_2::recursive::CircuitImpl> {
     /// Create an empty [V1] for any risc0 proof generate for any `1.2.x` vm version.
     pub fn v1_2() -> Self {
-        Self::empty(&circuit::v1_2::CIRCUIT, &circuit::v1_2::recursive::CIRCUIT)
-            .with_suites(Self::default_hash_suites())
-            .with_segment_verifier_parameters(SegmentReceiptVerifierParameters::v1_2())
-            .with_succinct_verifier_parameters(SuccinctReceiptVerifierParameters::v1_2())
+        Self::custom(
+            &circuit::v1_2::CIRCUIT,
+            &circuit::v1_2::recursive::CIRCUIT,
+            SegmentReceiptVerifierParameters::v1_2(),
+            SuccinctReceiptVerifierParameters::v1_2(),
+        )
+        .with_groth16_verifier_parameters(Groth16ReceiptVerifierParameters::v1_2())
+        .with_version("risc0/v1.2")
     }
 }
 
 impl<SC: CircuitCoreDefV1, RC: CircuitCoreDefV1> V1<SC, RC> {
-    /// Create an empty [V1].
+    /// Create an empty [V1], labelled with the generic `risc0/v1` version.
     pub fn empty(circuit: &'static SC, recursive_circuit: &'static RC) -> Self {
         Self {
             verifier_parameters: Default::default(),
             circuit,
             recursive_circuit,
+            version: "risc0/v1",
         }
     }
 
-    /// Return the mapping of hash suites used in the default [V1].
-    pub fn default_hash_suites() -> BTreeMap<String, HashSuite<BabyBear>> {
-        BTreeMap::from([
-            ("blake2b".into(), Blake2bCpuHashSuite::new_suite()),
-            ("poseidon2".into(), Poseidon2HashSuite::new_suite()),
-            ("sha-256".into(), Sha256HashSuite::new_suite()),
-        ])
+    /// Create a [V1] for the given circuits, with the default hash suites and the given
+    /// verifier parameters. Useful to verify the proofs of a forked circuit.
+    ///
+    /// Note that `segment_params.circuit_info` and `succinct_params.circuit_info` must match the
+    /// `CIRCUIT_INFO` of the circuits, otherwise the verification will always fail.
+    pub fn custom(
+        circuit: &'static SC,
+        recursive_circuit: &'static RC,
+        segment_params: SegmentReceiptVerifierParameters,
+        succinct_params: SuccinctReceiptVerifierParameters,
+    ) -> Self {
+        Self::empty(circuit, recursive_circuit)
+            .with_suites(Self::default_hash_suites())
+            .with_segment_verifier_parameters(segment_params)
+            .with_succinct_verifier_parameters(succinct_params)
+    }
+
+    /// Return the mapping of hash suites used in the default [V1]. With the `std` feature the
+    /// suites are built once per thread and then shared (cloning a suite only clones its `Rc`
+    /// pointers): see [V1::default_hash_suites_ref].
+    pub fn default_hash_suites() -> SuiteMap<HashSuite<BabyBear>> {
+        #[cfg(feature = "std")]
+        {
+            Self::default_hash_suites_ref().clone()
+        }
+        #[cfg(not(feature = "std"))]
+        new_default_hash_suites()
+    }
+
+    /// Borrow the mapping of hash suites used in the default [V1], without cloning it. The
+    /// suites hold `Rc` pointers, which are neither `Send` nor `Sync`, so they cannot be shared
+    /// by all the threads in a `OnceLock`: every thread builds its own mapping the first time
+    /// and leaks it, so the reference lives as long as the program but cannot leave the thread.
+    #[cfg(feature = "std")]
+    pub fn default_hash_suites_ref() -> &'static SuiteMap<HashSuite<BabyBear>> {
+        std::thread_local! {
+            static SUITES: &'static SuiteMap<HashSuite<BabyBear>> =
+                alloc::boxed::Box::leak(alloc::boxed::Box::new(new_default_hash_suites()));
+        }
+        SUITES.with(|suites| *suites)
+    }
+
+    /// Return [V1] with the given version label, the one shown by its [Display](fmt::Display)
+    /// and [Debug](fmt::Debug) implementations.
+    pub(crate) fn with_version(mut self, version: &'static str) -> Self {
+        self.version = version;
+        self
     }
 
     /// Return [V1] with the given map of hash suites.
-    pub fn with_suites(mut self, suites: BTreeMap<String, HashSuite<BabyBear>>) -> Self {
+    pub fn with_suites(mut self, suites: SuiteMap<HashSuite<BabyBear>>) -> Self {
         self.verifier_parameters.suites = suites;
         self
     }
@@ -308,6 +379,15 @@ impl<SC: CircuitCoreDefV1, RC: CircuitCoreDefV1> V1<SC, RC> {
         self
     }
 
+    /// Return [V1] with the given [Groth16ReceiptVerifierParameters] set.
+    pub fn with_groth16_verifier_parameters(
+        mut self,
+        params: Groth16ReceiptVerifierParameters,
+    ) -> Self {
+        self.verifier_parameters.groth16_verifier_parameters = Some(params);
+        self
+    }
+
     /// Return [V1] with the given [SuccinctReceiptVerifierParameters] set.
     pub fn with_succinct_verifier_parameters(
         mut self,
@@ -325,35 +405,12 @@ impl Default for VerifierParametersV1 {
     fn default() -> Self {
         Self {
             succinct_verifier_parameters: None,
-            suites: BTreeMap::new(),
+            suites: SuiteMap::new(),
             segment_verifier_parameters: None,
+            groth16_verifier_parameters: None,
             segment: SegmentV1,
             succinct: SuccinctV1,
-        }
-    }
-}
-
-impl Clone for VerifierParametersV1 {
-    fn clone(&self) -> Self {
-        Self {
-            suites: self
-                .suites
-                .iter()
-                .map(|(k, v)| {
-                    (
-                        k.clone(),
-                        HashSuiteV1 {
-                            name: v.name.clone(),
-                            hashfn: v.hashfn.clone(),
-                            rng: v.rng.clone(),
-                        },
-                    )
-                })
-                .collect(),
-            segment_verifier_parameters: self.segment_verifier_parameters.clone(),
-            succinct_verifier_parameters: self.succinct_verifier_parameters.clone(),
-            segment: self.segment,
-            succinct: self.succinct,
+            options: Default::default(),
         }
     }
 }
@@ -381,3 +438,20 @@ impl CircuitInfo for SuccinctV1 {
         <circuit::v1_2::recursive::CircuitImpl as risc0_zkp_v1::adapter::CircuitInfo>::OUTPUT_SIZE
     }
 }
+
+fn new_default_hash_suites() -> SuiteMap<HashSuite<BabyBear>> {
+    #[allow(unused_mut)]
+    let mut suites = SuiteMap::from([
+        ("poseidon2".into(), Poseidon2HashSuite::new_suite()),
+        ("sha-256".into(), Sha256HashSuite::new_suite()),
+    ]);
+    #[cfg(any(
+        feature = "blake2b",
+        not(all(feature = "wasm", target_arch = "wasm32"))
+    ))]
+    suites.insert(
+        "blake2b".into(),
+        risc0_zkp_v1::core::hash::blake2b::Blake2bCpuHashSuite::new_suite(),
+    );
+    suites
+}
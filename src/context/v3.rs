@@ -15,18 +15,20 @@
 // limitations under the License.
 //
 
-use super::{BoxedVC, VerifierContext, VerifierParameters};
+use super::{BoxedVC, SuiteMap, VerifierContext, VerifierParameters};
 use crate::receipt_claim::MaybePruned;
 use crate::{
-    circuit, circuit::CircuitCoreDefV3, poseidon2_injection::Poseidon2Mix,
+    circuit, circuit::CircuitCoreDefV3, hash_wrapper::CrossVersionHashFn,
+    poseidon2_injection::Poseidon2Mix, receipt::groth16::Groth16ReceiptVerifierParameters,
     receipt::merkle::MerkleProof, receipt::succinct::SuccinctReceiptVerifierParameters,
     receipt_claim::ReceiptClaim, segment::SegmentReceiptVerifierParameters, translate::Translate,
-    Proof,
+    HashFunction, Proof,
 };
-use alloc::{boxed::Box, collections::BTreeMap, string::String};
+use core::fmt;
+
+use alloc::boxed::Box;
 use risc0_binfmt_v1::{ExitCode, SystemState};
 use risc0_circuit_rv32im_v4::RV32IM_SEAL_VERSION;
-use risc0_core_v1::field::baby_bear::BabyBear;
 use risc0_zkp_v1::{
     adapter::{ProtocolInfo, PROOF_SYSTEM_INFO},
     core::digest::Digest,
@@ -48,11 +50,16 @@ impl<SC: CircuitCoreDefV3, RC: CircuitCoreDefV3> VerifierContext for V3<SC, RC>
         &mut self.verifier_parameters
     }
 
+    fn version(&self) -> &'static str {
+        self.version
+    }
+
     fn boxed_clone(&self) -> BoxedVC<Self> {
         let cloned = Self {
             verifier_parameters: self.verifier_parameters.clone(),
             circuit: self.circuit,
             recursive_circuit: self.recursive_circuit,
+            version: self.version,
         };
         Box::new(cloned)
     }
@@ -127,9 +134,9 @@ impl<SC: CircuitCoreDefV3, RC: CircuitCoreDefV3> VerifierContext for V3<SC, RC>
          -> Result<(), risc0_zkp_v3::verify::VerificationError> {
             let control_id_v1 = bytemuck::checked::cast_ref(control_id);
             control_inclusion_proof
-                .verify(control_id_v1, &params.control_root, &HashFnWrapper { inner: suite.hashfn.as_ref() })
+                .verify(control_id_v1, &params.control_root, &CrossVersionHashFn::new(suite.hashfn.as_ref()))
                 .map_err(|_| {
-                    log::debug!(
+                    debug!(
                         "failed to verify control inclusion proof for {control_id} against root {} with {}",
                         params.control_root,
                         suite.name,
@@ -151,7 +158,7 @@ impl<SC: CircuitCoreDefV3, RC: CircuitCoreDefV3> VerifierContext for V3<SC, RC>
             // V3 proof with `sha-256` segment are not admitted because misleading: they use
             // poseidon2 even if in the segment `hashfn` is "sha-256" as reported in
             // https://github.com/risc0/risc0/issues/3063
-            if c.segments.iter().any(|s| s.hashfn == "sha-256") {
+            if c.segments.iter().any(|s| s.hashfn == HashFunction::Sha256) {
                 return false;
             }
         }
@@ -163,20 +170,16 @@ impl<SC: CircuitCoreDefV3, RC: CircuitCoreDefV3> VerifierContext for V3<SC, RC>
     }
 
     fn set_poseidon2_mix_impl(&mut self, poseidon2: Box<dyn Poseidon2Mix + Send + Sync + 'static>) {
-        self.mut_verifier_parameters()
-            .suites
-            .entry("poseidon2".into())
-            .and_modify(|s| {
-                s.hashfn =
-                    alloc::rc::Rc::new(crate::poseidon2_injection::Poseidon2Impl::new(poseidon2))
-            });
+        if let Some(s) = self.mut_verifier_parameters().suites.get_mut("poseidon2") {
+            s.hashfn = alloc::rc::Rc::new(crate::poseidon2_injection::Poseidon2Impl::new(poseidon2))
+        }
     }
 }
 
 fn decode_from_seal(seal: &[u32], _po2: Option<u32>) -> Result<ReceiptClaim, VerificationError> {
     let claim = risc0_circuit_rv32im_v4::Rv32imV2Claim::decode(seal)
         .map_err(|_e| VerificationError::InvalidProof)?;
-    log::debug!("claim: {claim:#?}");
+    debug!("claim: {claim:#?}");
 
     let exit_code = exit_code_from_rv32im_v4_claim(&claim)?;
     let post_state = match exit_code {
@@ -258,103 +261,98 @@ pub struct V3<SC: CircuitCoreDefV3, RC: CircuitCoreDefV3> {
     circuit: &'static SC,
 
     recursive_circuit: &'static RC,
+
+    version: &'static str,
+}
+
+impl<SC: CircuitCoreDefV3, RC: CircuitCoreDefV3> fmt::Debug for V3<SC, RC> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.verifier_parameters.debug_fmt("V3", self.version, f)
+    }
+}
+
+impl<SC: CircuitCoreDefV3, RC: CircuitCoreDefV3> fmt::Display for V3<SC, RC> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.version)
+    }
 }
 
 impl Default for VerifierParametersV3 {
     fn default() -> Self {
         Self {
             succinct_verifier_parameters: None,
-            suites: BTreeMap::new(),
+            suites: SuiteMap::new(),
             segment_verifier_parameters: None,
+            groth16_verifier_parameters: None,
             segment: SegmentV3,
             succinct: SuccinctV3,
+            options: Default::default(),
         }
     }
 }
 
-impl Clone for VerifierParametersV3 {
-    fn clone(&self) -> Self {
+impl<SC: CircuitCoreDefV3, RC: CircuitCoreDefV3> V3<SC, RC> {
+    /// Create an empty [V3], labelled with the generic `risc0/v3` version.
+    pub fn empty(circuit: &'static SC, recursive_circuit: &'static RC) -> Self {
         Self {
-            suites: self
-                .suites
-                .iter()
-                .map(|(k, v)| {
-                    (
-                        k.clone(),
-                        HashSuiteV3 {
-                            name: v.name.clone(),
-                            hashfn: v.hashfn.clone(),
-                            rng: v.rng.clone(),
-                        },
-                    )
-                })
-                .collect(),
-            segment_verifier_parameters: self.segment_verifier_parameters.clone(),
-            succinct_verifier_parameters: self.succinct_verifier_parameters.clone(),
-            segment: self.segment,
-            succinct: self.succinct,
+            verifier_parameters: Default::default(),
+            circuit,
+            recursive_circuit,
+            version: "risc0/v3",
         }
     }
-}
-
-pub struct HashFnWrapper<'a> {
-    inner: &'a HashFnV3,
-}
-
-impl risc0_zkp_v1::core::hash::HashFn<BabyBear> for HashFnWrapper<'_> {
-    fn hash_pair(&self, a: &Digest, b: &Digest) -> Box<Digest> {
-        let a = bytemuck::checked::cast_ref(a);
-        let b = bytemuck::checked::cast_ref(b);
-        (*self.inner.hash_pair(a, b)).translate().into()
-    }
 
-    fn hash_elem_slice(
-        &self,
-        slice: &[<BabyBear as risc0_zkp_v1::field::Field>::Elem],
-    ) -> Box<Digest> {
-        let slice = bytemuck::checked::cast_slice(slice);
-        (*self.inner.hash_elem_slice(slice)).translate().into()
+    /// Create a [V3] for the given circuits, with the default hash suites and the given
+    /// verifier parameters. Useful to verify the proofs of a forked circuit.
+    ///
+    /// Note that `segment_params.circuit_info` and `succinct_params.circuit_info` must match the
+    /// `CIRCUIT_INFO` of the circuits, otherwise the verification will always fail.
+    pub fn custom(
+        circuit: &'static SC,
+        recursive_circuit: &'static RC,
+        segment_params: SegmentReceiptVerifierParameters,
+        succinct_params: SuccinctReceiptVerifierParameters,
+    ) -> Self {
+        Self::empty(circuit, recursive_circuit)
+            .with_suites(Self::default_hash_suites())
+            .with_segment_verifier_parameters(segment_params)
+            .with_succinct_verifier_parameters(succinct_params)
     }
 
-    fn hash_ext_elem_slice(
-        &self,
-        slice: &[<BabyBear as risc0_zkp_v1::field::Field>::ExtElem],
-    ) -> Box<Digest> {
-        let slice = bytemuck::checked::cast_slice(slice);
-        (*self.inner.hash_ext_elem_slice(slice)).translate().into()
+    /// Return the mapping of hash suites used in the default [V3]. With the `std` feature the
+    /// suites are built once per thread and then shared (cloning a suite only clones its `Rc`
+    /// pointers): see [V3::default_hash_suites_ref].
+    pub fn default_hash_suites() -> SuiteMap<HashSuiteV3> {
+        #[cfg(feature = "std")]
+        {
+            Self::default_hash_suites_ref().clone()
+        }
+        #[cfg(not(feature = "std"))]
+        new_default_hash_suites()
     }
-}
 
-impl<SC: CircuitCoreDefV3, RC: CircuitCoreDefV3> V3<SC, RC> {
-    /// Create an empty [V3].
-    pub fn empty(circuit: &'static SC, recursive_circuit: &'static RC) -> Self {
-        Self {
-            verifier_parameters: Default::default(),
-            circuit,
-            recursive_circuit,
+    /// Borrow the mapping of hash suites used in the default [V3], without cloning it. The
+    /// suites hold `Rc` pointers, which are neither `Send` nor `Sync`, so they cannot be shared
+    /// by all the threads in a `OnceLock`: every thread builds its own mapping the first time
+    /// and leaks it, so the reference lives as long as the program but cannot leave the thread.
+    #[cfg(feature = "std")]
+    pub fn default_hash_suites_ref() -> &'static SuiteMap<HashSuiteV3> {
+        std::thread_local! {
+            static SUITES: &'static SuiteMap<HashSuiteV3> =
+                alloc::boxed::Box::leak(alloc::boxed::Box::new(new_default_hash_suites()));
         }
+        SUITES.with(|suites| *suites)
     }
 
-    /// Return the mapping of hash suites used in the default [V3].
-    pub fn default_hash_suites() -> BTreeMap<String, HashSuiteV3> {
-        BTreeMap::from([
-            (
-                "blake2b".into(),
-                risc0_zkp_v3::core::hash::blake2b::Blake2bCpuHashSuite::new_suite(),
-            ),
-            (
-                "poseidon2".into(),
-                risc0_zkp_v3::core::hash::poseidon2::Poseidon2HashSuite::new_suite(),
-            ),
-            (
-                "sha-256".into(),
-                risc0_zkp_v3::core::hash::sha::Sha256HashSuite::new_suite(),
-            ),
-        ])
+    /// Return [V3] with the given version label, the one shown by its [Display](fmt::Display)
+    /// and [Debug](fmt::Debug) implementations.
+    pub(crate) fn with_version(mut self, version: &'static str) -> Self {
+        self.version = version;
+        self
     }
 
     /// Return [V3] with the given map of hash suites.
-    pub fn with_suites(mut self, suites: BTreeMap<String, HashSuiteV3>) -> Self {
+    pub fn with_suites(mut self, suites: SuiteMap<HashSuiteV3>) -> Self {
         self.verifier_parameters.suites = suites;
         self
     }
@@ -368,6 +366,15 @@ impl<SC: CircuitCoreDefV3, RC: CircuitCoreDefV3> V3<SC, RC> {
         self
     }
 
+    /// Return [V3] with the given [Groth16ReceiptVerifierParameters] set.
+    pub fn with_groth16_verifier_parameters(
+        mut self,
+        params: Groth16ReceiptVerifierParameters,
+    ) -> Self {
+        self.verifier_parameters.groth16_verifier_parameters = Some(params);
+        self
+    }
+
     /// Return [V3] with the given [SuccinctReceiptVerifierParameters] set.
     pub fn with_succinct_verifier_parameters(
         mut self,
@@ -381,9 +388,36 @@ impl<SC: CircuitCoreDefV3, RC: CircuitCoreDefV3> V3<SC, RC> {
 impl V3<circuit::v3_0::CircuitImpl, circuit::v3_0::recursive::CircuitImpl> {
     /// Create an empty [V3] for any risc0 proof generate for any `3.0.x` vm version.
     pub fn v3_0() -> Self {
-        Self::empty(&circuit::v3_0::CIRCUIT, &circuit::v3_0::recursive::CIRCUIT)
-            .with_suites(Self::default_hash_suites())
-            .with_segment_verifier_parameters(SegmentReceiptVerifierParameters::v3_0())
-            .with_succinct_verifier_parameters(SuccinctReceiptVerifierParameters::v3_0())
+        Self::custom(
+            &circuit::v3_0::CIRCUIT,
+            &circuit::v3_0::recursive::CIRCUIT,
+            SegmentReceiptVerifierParameters::v3_0(),
+            SuccinctReceiptVerifierParameters::v3_0(),
+        )
+        .with_groth16_verifier_parameters(Groth16ReceiptVerifierParameters::v3_0())
+        .with_version("risc0/v3.0")
     }
 }
+
+fn new_default_hash_suites() -> SuiteMap<HashSuiteV3> {
+    #[allow(unused_mut)]
+    let mut suites = SuiteMap::from([
+        (
+            "poseidon2".into(),
+            risc0_zkp_v3::core::hash::poseidon2::Poseidon2HashSuite::new_suite(),
+        ),
+        (
+            "sha-256".into(),
+            risc0_zkp_v3::core::hash::sha::Sha256HashSuite::new_suite(),
+        ),
+    ]);
+    #[cfg(any(
+        feature = "blake2b",
+        not(all(feature = "wasm", target_arch = "wasm32"))
+    ))]
+    suites.insert(
+        "blake2b".into(),
+        risc0_zkp_v3::core::hash::blake2b::Blake2bCpuHashSuite::new_suite(),
+    );
+    suites
+}
@@ -0,0 +1,176 @@
+// Copyright 2024, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The hash functions that a receipt can be generated with.
+
+use alloc::string::String;
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Verifier;
+
+/// Hash function used to generate a [SegmentReceipt](crate::SegmentReceipt) or a
+/// [SuccinctReceipt](crate::SuccinctReceipt). It's serialized as a string with the same names
+/// used by risc0 (e.g. `"sha-256"`), so the encoding of the receipts doesn't change even in
+/// non self-describing formats like `bincode`. Some other common spellings are accepted when
+/// deserializing.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "&'static str")]
+pub enum HashFunction {
+    Sha256,
+    Poseidon2,
+    Blake2b,
+}
+
+impl HashFunction {
+    /// All the supported hash functions.
+    pub const ALL: [Self; 3] = [Self::Poseidon2, Self::Sha256, Self::Blake2b];
+
+    /// The canonical name of the hash function, the one used to identify its hash suite.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sha256 => "sha-256",
+            Self::Poseidon2 => "poseidon2",
+            Self::Blake2b => "blake2b",
+        }
+    }
+
+    /// Return `true` if the given [Verifier] has a hash suite for this hash function.
+    pub fn is_supported_by(&self, verifier: &impl Verifier) -> bool {
+        verifier.has_hash_suite(self.as_str())
+    }
+}
+
+impl fmt::Display for HashFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned when a string is not the name of a known [HashFunction].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownHashFunction(pub String);
+
+impl fmt::Display for UnknownHashFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown hash function: {}", self.0)
+    }
+}
+
+impl TryFrom<&str> for HashFunction {
+    type Error = UnknownHashFunction;
+
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        match name {
+            "sha-256" | "sha256" | "SHA-256" => Ok(Self::Sha256),
+            "poseidon2" | "Poseidon2" => Ok(Self::Poseidon2),
+            "blake2b" | "BLAKE2b" => Ok(Self::Blake2b),
+            _ => Err(UnknownHashFunction(name.into())),
+        }
+    }
+}
+
+impl TryFrom<String> for HashFunction {
+    type Error = UnknownHashFunction;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        Self::try_from(name.as_str())
+    }
+}
+
+impl From<HashFunction> for &'static str {
+    fn from(hash: HashFunction) -> Self {
+        hash.as_str()
+    }
+}
+
+impl PartialEq<&str> for HashFunction {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::sha_256(HashFunction::Sha256, "sha-256")]
+    #[case::poseidon2(HashFunction::Poseidon2, "poseidon2")]
+    #[case::blake2b(HashFunction::Blake2b, "blake2b")]
+    fn canonical_names(#[case] hash: HashFunction, #[case] name: &str) {
+        assert_eq!(name, hash.as_str());
+        assert_eq!(name, std::format!("{hash}"));
+        assert_eq!(Ok(hash), HashFunction::try_from(name));
+        assert_eq!(
+            std::format!("\"{name}\""),
+            serde_json::to_string(&hash).unwrap()
+        );
+        assert_eq!(
+            hash,
+            serde_json::from_str::<HashFunction>(&std::format!("\"{name}\"")).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case::sha256(HashFunction::Sha256, "sha256")]
+    #[case::sha_256_upper(HashFunction::Sha256, "SHA-256")]
+    #[case::poseidon2(HashFunction::Poseidon2, "Poseidon2")]
+    #[case::blake2b(HashFunction::Blake2b, "BLAKE2b")]
+    fn aliases(#[case] hash: HashFunction, #[case] name: &str) {
+        assert_eq!(Ok(hash), HashFunction::try_from(name));
+        assert_eq!(
+            hash,
+            serde_json::from_str::<HashFunction>(&std::format!("\"{name}\"")).unwrap()
+        );
+    }
+
+    #[test]
+    fn unknown_name() {
+        assert_eq!(
+            Err(UnknownHashFunction("keccak".into())),
+            HashFunction::try_from("keccak")
+        );
+        assert!(serde_json::from_str::<HashFunction>("\"keccak\"").is_err());
+    }
+
+    #[test]
+    fn bincode_encoding_is_a_string() {
+        let encoded = bincode::serialize(&HashFunction::Sha256).unwrap();
+
+        assert_eq!(bincode::serialize("sha-256").unwrap(), encoded);
+        assert_eq!(
+            HashFunction::Sha256,
+            bincode::deserialize::<HashFunction>(&encoded).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case::v1_2(crate::v1_2())]
+    #[case::v2_1(crate::v2_1())]
+    #[case::v3_0(crate::v3_0())]
+    fn supported_by(#[case] verifier: impl Verifier) {
+        assert!(HashFunction::ALL
+            .into_iter()
+            .all(|h| h.is_supported_by(&verifier)));
+
+        let verifier = verifier.reject_sha256();
+
+        assert!(!HashFunction::Sha256.is_supported_by(&verifier));
+        assert!(HashFunction::Poseidon2.is_supported_by(&verifier));
+    }
+}
@@ -0,0 +1,150 @@
+// Copyright Copyright 2024, Horizen Labs, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Adapter to use the v2 and v3 hash functions where a v1 [HashFn] is required (e.g. to verify
+//! a [MerkleProof](crate::receipt::merkle::MerkleProof)).
+
+use alloc::boxed::Box;
+use risc0_core_v1::field::baby_bear::BabyBear;
+use risc0_zkp_v1::{
+    core::{digest::Digest, hash::HashFn},
+    field::Field,
+};
+
+use crate::{context::v2::HashFnV2, context::v3::HashFnV3, translate::Translate};
+
+/// Wrap a reference to a hash function of another risc0 version and expose it as a v1 [HashFn].
+pub struct CrossVersionHashFn<'a, F: ?Sized> {
+    inner: &'a F,
+}
+
+impl<'a, F: ?Sized> CrossVersionHashFn<'a, F> {
+    pub fn new(inner: &'a F) -> Self {
+        Self { inner }
+    }
+}
+
+macro_rules! impl_cross_version_hash_fn {
+    ($hashfn:ty) => {
+        impl HashFn<BabyBear> for CrossVersionHashFn<'_, $hashfn> {
+            fn hash_pair(&self, a: &Digest, b: &Digest) -> Box<Digest> {
+                let a = bytemuck::checked::cast_ref(a);
+                let b = bytemuck::checked::cast_ref(b);
+                (*self.inner.hash_pair(a, b)).translate().into()
+            }
+
+            fn hash_elem_slice(&self, slice: &[<BabyBear as Field>::Elem]) -> Box<Digest> {
+                let slice = bytemuck::checked::cast_slice(slice);
+                (*self.inner.hash_elem_slice(slice)).translate().into()
+            }
+
+            fn hash_ext_elem_slice(&self, slice: &[<BabyBear as Field>::ExtElem]) -> Box<Digest> {
+                let slice = bytemuck::checked::cast_slice(slice);
+                (*self.inner.hash_ext_elem_slice(slice)).translate().into()
+            }
+        }
+    };
+}
+
+impl_cross_version_hash_fn!(HashFnV2);
+impl_cross_version_hash_fn!(HashFnV3);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::receipt::merkle::MerkleProof;
+    use alloc::vec::Vec;
+    use rstest::rstest;
+
+    fn merkle_proof() -> (Digest, MerkleProof) {
+        let leaf = Digest::from([0x1234_u32; 8]);
+        let digests = (1..=4_u32).map(|i| Digest::from([i; 8])).collect();
+        (leaf, MerkleProof { index: 5, digests })
+    }
+
+    /// Compute the Merkle root as [MerkleProof::root] does but with the native `hash_pair`.
+    fn native_root<D: Copy>(
+        leaf: D,
+        proof: &MerkleProof,
+        siblings: Vec<D>,
+        hash_pair: impl Fn(&D, &D) -> D,
+    ) -> D {
+        let mut cur = leaf;
+        let mut cur_index = proof.index;
+        for sibling in &siblings {
+            cur = if cur_index & 1 == 0 {
+                hash_pair(&cur, sibling)
+            } else {
+                hash_pair(sibling, &cur)
+            };
+            cur_index >>= 1;
+        }
+        cur
+    }
+
+    #[rstest]
+    #[case::poseidon2("poseidon2")]
+    #[case::sha_256("sha-256")]
+    fn v2_wrapper_computes_the_same_merkle_root_of_native_hash_fn(#[case] name: &str) {
+        let suites = crate::context::v2::V2::<
+            crate::circuit::v2_1::CircuitImpl,
+            crate::circuit::v2_1::recursive::CircuitImpl,
+        >::default_hash_suites();
+        let native = suites[name].hashfn.as_ref();
+        let (leaf, proof) = merkle_proof();
+
+        let expected: risc0_zkp_v2::core::digest::Digest = native_root(
+            *bytemuck::checked::cast_ref(&leaf),
+            &proof,
+            proof
+                .digests
+                .iter()
+                .map(|d| *bytemuck::checked::cast_ref(d))
+                .collect(),
+            |a, b| *native.hash_pair(a, b),
+        );
+        let root = proof.root(&leaf, &CrossVersionHashFn::new(native));
+
+        assert_eq!(expected.as_words(), root.as_words());
+    }
+
+    #[rstest]
+    #[case::poseidon2("poseidon2")]
+    #[case::sha_256("sha-256")]
+    fn v3_wrapper_computes_the_same_merkle_root_of_native_hash_fn(#[case] name: &str) {
+        let suites = crate::context::v3::V3::<
+            crate::circuit::v3_0::CircuitImpl,
+            crate::circuit::v3_0::recursive::CircuitImpl,
+        >::default_hash_suites();
+        let native = suites[name].hashfn.as_ref();
+        let (leaf, proof) = merkle_proof();
+
+        let expected: risc0_zkp_v3::core::digest::Digest = native_root(
+            *bytemuck::checked::cast_ref(&leaf),
+            &proof,
+            proof
+                .digests
+                .iter()
+                .map(|d| *bytemuck::checked::cast_ref(d))
+                .collect(),
+            |a, b| *native.hash_pair(a, b),
+        );
+        let root = proof.root(&leaf, &CrossVersionHashFn::new(native));
+
+        assert_eq!(expected.as_words(), root.as_words());
+    }
+}
@@ -13,8 +13,53 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use core::fmt;
+
+use risc0_core_v1::field::baby_bear::P;
 use serde::{Deserialize, Serialize};
 
+/// Error returned when a [Vk] is not well formed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum VkError {
+    /// All the words of the key are zero.
+    ZeroKey,
+    /// All the bits of the key are set.
+    AllOnesKey,
+    /// The word at `index` is not a canonical BabyBear field element.
+    InvalidFieldElement { index: usize, value: u32 },
+}
+
+impl fmt::Display for VkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ZeroKey => write!(f, "verification key is all zeros"),
+            Self::AllOnesKey => write!(f, "verification key is all ones"),
+            Self::InvalidFieldElement { index, value } => write!(
+                f,
+                "verification key word {index} ({value:#010x}) is not a valid field element"
+            ),
+        }
+    }
+}
+
+/// Error returned when building a [Vk] from a byte slice.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum VkFromBytesError {
+    /// The slice is not 32 bytes long.
+    WrongLength { expected: usize, got: usize },
+}
+
+impl fmt::Display for VkFromBytesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::WrongLength { expected, got } => write!(
+                f,
+                "wrong verification key length: expected {expected} bytes, got {got}"
+            ),
+        }
+    }
+}
+
 /// The verification key (aka image id, the hash of the guest program)
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Vk(pub risc0_zkp_v1::core::digest::Digest);
@@ -27,6 +72,113 @@ impl Vk {
     pub fn as_bytes(&self) -> &[u8] {
         self.0.as_bytes()
     }
+
+    /// Cheap sanity check that rejects the degenerate all zeros and all ones keys, which cannot
+    /// be the image id of any program.
+    pub fn verify_well_formed(&self) -> Result<(), VkError> {
+        if self.as_words().iter().all(|&w| w == 0) {
+            return Err(VkError::ZeroKey);
+        }
+        if self.as_words().iter().all(|&w| w == u32::MAX) {
+            return Err(VkError::AllOnesKey);
+        }
+        Ok(())
+    }
+
+    /// Check that every word of the key is a canonical BabyBear field element. Image ids are
+    /// SHA-256 digests, so this holds only for keys that are expected to be field-encoded
+    /// (e.g. Poseidon2 digests), and it's not part of [Vk::verify_well_formed].
+    pub fn verify_field_elements(&self) -> Result<(), VkError> {
+        match self.as_words().iter().position(|&w| w >= P) {
+            Some(index) => Err(VkError::InvalidFieldElement {
+                index,
+                value: self.as_words()[index],
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Compare the two keys in constant time: all the words are always compared, without any
+    /// early exit. Use it where the comparison timing must not leak the position of the first
+    /// different word, e.g. when checking a pinned key.
+    pub fn timing_safe_eq(&self, other: &Vk) -> bool {
+        let diff = self
+            .as_words()
+            .iter()
+            .zip(other.as_words())
+            .fold(0_u32, |acc, (a, b)| acc | core::hint::black_box(a ^ b));
+        core::hint::black_box(diff) == 0
+    }
+
+    /// Build a [Vk] from its 8 words: the same as `Vk::from(words)`.
+    pub fn from_words(words: [u32; risc0_zkp_v1::core::digest::DIGEST_WORDS]) -> Self {
+        words.into()
+    }
+
+    /// Copy out the 8 words of the key.
+    pub fn words_as_array(&self) -> [u32; risc0_zkp_v1::core::digest::DIGEST_WORDS] {
+        let mut words = [0; risc0_zkp_v1::core::digest::DIGEST_WORDS];
+        words.copy_from_slice(self.as_words());
+        words
+    }
+
+    /// Build a [Vk] from its 32 bytes in the risc0 native encoding, i.e. each word little endian:
+    /// the same as `Vk::from([u8; 32])`.
+    pub fn from_bytes_le(bytes: &[u8]) -> Result<Vk, VkFromBytesError> {
+        let bytes: [u8; risc0_zkp_v1::core::digest::DIGEST_BYTES] =
+            bytes
+                .try_into()
+                .map_err(|_| VkFromBytesError::WrongLength {
+                    expected: risc0_zkp_v1::core::digest::DIGEST_BYTES,
+                    got: bytes.len(),
+                })?;
+        Ok(bytes.into())
+    }
+
+    /// Build a [Vk] from its 32 bytes with each word big endian, e.g. as read from a
+    /// `bytes32` of an EVM contract.
+    pub fn from_bytes_be(bytes: &[u8]) -> Result<Vk, VkFromBytesError> {
+        let mut vk = Self::from_bytes_le(bytes)?;
+        vk.0.as_mut_words()
+            .iter_mut()
+            .for_each(|w| *w = w.swap_bytes());
+        Ok(vk)
+    }
+
+    /// The 32 bytes of the key in the risc0 native encoding, i.e. each word little endian.
+    pub fn to_bytes_le(&self) -> [u8; risc0_zkp_v1::core::digest::DIGEST_BYTES] {
+        let mut bytes = [0; risc0_zkp_v1::core::digest::DIGEST_BYTES];
+        bytes.copy_from_slice(self.as_bytes());
+        bytes
+    }
+
+    /// The 32 bytes of the key with each word big endian.
+    pub fn to_bytes_be(&self) -> [u8; risc0_zkp_v1::core::digest::DIGEST_BYTES] {
+        let mut bytes = [0; risc0_zkp_v1::core::digest::DIGEST_BYTES];
+        for (chunk, word) in bytes.chunks_exact_mut(4).zip(self.iter_words()) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        bytes
+    }
+
+    /// Iterate over the 8 words of the key, in order.
+    pub fn iter_words(&self) -> impl Iterator<Item = u32> + '_ {
+        self.as_words().iter().copied()
+    }
+}
+
+impl core::ops::Index<usize> for Vk {
+    type Output = u32;
+
+    /// Return the word at `index`. Panics if `index >= 8`.
+    fn index(&self, index: usize) -> &u32 {
+        self.as_words().get(index).unwrap_or_else(|| {
+            panic!(
+                "Vk word index out of range: the index is {index} but a Vk has {} words",
+                risc0_zkp_v1::core::digest::DIGEST_WORDS
+            )
+        })
+    }
 }
 
 impl From<[u32; risc0_zkp_v1::core::digest::DIGEST_WORDS]> for Vk {
@@ -47,9 +199,94 @@ impl From<Vk> for risc0_zkp_v1::core::digest::Digest {
     }
 }
 
+/// Error returned when the [Vk] cannot be computed from an ELF binary.
+#[cfg(feature = "compute-vk")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VkFromElfError {
+    /// The binary is not a valid ELF executable for the zkVM.
+    MalformedElf(alloc::string::String),
+    /// The binary is an ELF but not for the 32 bits RISC-V ISA.
+    UnsupportedIsa,
+    /// The memory image of the binary cannot be hashed.
+    HashFailure,
+}
+
+#[cfg(feature = "compute-vk")]
+impl fmt::Display for VkFromElfError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MalformedElf(e) => write!(f, "malformed elf: {e}"),
+            Self::UnsupportedIsa => write!(f, "unsupported isa, expected 32 bits RISC-V"),
+            Self::HashFailure => write!(f, "cannot hash the memory image"),
+        }
+    }
+}
+
+#[cfg(feature = "compute-vk")]
+impl Vk {
+    /// Compute the image id of the given guest ELF binary. Available only with the `compute-vk`
+    /// feature.
+    ///
+    /// The image id is computed as the `1.x` risc0 zkVM does: programs built for the `2.x` and
+    /// `3.x` zkVMs are linked with a kernel and have a different image id.
+    ///
+    /// ```
+    /// # use risc0_verifier::Vk;
+    /// let elf = std::fs::read("./generate_proofs/host/method-1.2.0/method").unwrap();
+    ///
+    /// let vk = Vk::from_elf(&elf).unwrap();
+    ///
+    /// assert!(vk.verify_well_formed().is_ok());
+    /// ```
+    pub fn from_elf(elf: &[u8]) -> Result<Vk, VkFromElfError> {
+        use alloc::string::ToString;
+        use risc0_binfmt_v1::{MemoryImage, Program};
+        use risc0_zkvm_platform_v1::{memory::GUEST_MAX_MEM, PAGE_SIZE};
+
+        const EI_CLASS: usize = 4;
+        const ELF_CLASS_32: u8 = 1;
+        const E_MACHINE: usize = 18;
+        const EM_RISCV: u16 = 243;
+
+        if elf.starts_with(b"\x7fELF") && elf.len() >= E_MACHINE + 2 {
+            let machine = u16::from_le_bytes([elf[E_MACHINE], elf[E_MACHINE + 1]]);
+            if elf[EI_CLASS] != ELF_CLASS_32 || machine != EM_RISCV {
+                return Err(VkFromElfError::UnsupportedIsa);
+            }
+        }
+        let program = Program::load_elf(elf, GUEST_MAX_MEM as u32)
+            .map_err(|e| VkFromElfError::MalformedElf(e.to_string()))?;
+        let image = MemoryImage::new(&program, PAGE_SIZE as u32)
+            .map_err(|_| VkFromElfError::HashFailure)?;
+        Ok(Vk(image.compute_id()))
+    }
+}
+
+/// Serialize a [Vk] as a 64 characters lowercase hex string instead of an array of words: use
+/// it with `#[serde(with = "risc0_verifier::key::serde_hex")]`.
+pub mod serde_hex {
+    use super::Vk;
+    use crate::sha::{digest_from_hex, DigestExt};
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    /// Serialize `vk` as the lowercase hex string of its digest bytes.
+    pub fn serialize<S: Serializer>(vk: &Vk, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&vk.0.to_hex())
+    }
+
+    /// Deserialize a [Vk] from the hex string of its digest bytes: fail if it isn't a 64
+    /// characters hex string.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vk, D::Error> {
+        let hex = alloc::string::String::deserialize(deserializer)?;
+        digest_from_hex(&hex).map(Vk).map_err(D::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Vk;
+    use super::{Vk, VkError, VkFromBytesError};
+    use rstest::rstest;
+    use serde::{Deserialize, Serialize};
 
     #[test]
     fn should_have_same_from_result() {
@@ -68,4 +305,158 @@ mod tests {
 
         assert!(vu32.0.eq(&vu8.0));
     }
+
+    #[rstest]
+    #[case::zero([0; 8], Err(VkError::ZeroKey))]
+    #[case::all_ones([u32::MAX; 8], Err(VkError::AllOnesKey))]
+    #[case::image_id(
+        [2375596445, 2913778847, 4230594034, 2344181884, 1111696324, 3111015422, 3063136183, 1],
+        Ok(())
+    )]
+    #[case::single_word([0, 0, 0, 0, 0, 0, 0, 1], Ok(()))]
+    fn verify_well_formed(#[case] words: [u32; 8], #[case] expected: Result<(), VkError>) {
+        assert_eq!(expected, Vk::from(words).verify_well_formed());
+    }
+
+    #[rstest]
+    #[case::valid([0, 1, 2, 3, 4, 5, 6, 0x78000000], Ok(()))]
+    #[case::modulus(
+        [0, 1, 2, 0x78000001, 4, 5, 6, 7],
+        Err(VkError::InvalidFieldElement { index: 3, value: 0x78000001 })
+    )]
+    #[case::first_invalid(
+        [0, u32::MAX, 2, 3, 4, 5, 6, 0x78000001],
+        Err(VkError::InvalidFieldElement { index: 1, value: u32::MAX })
+    )]
+    fn verify_field_elements(#[case] words: [u32; 8], #[case] expected: Result<(), VkError>) {
+        assert_eq!(expected, Vk::from(words).verify_field_elements());
+    }
+
+    #[rstest]
+    #[case::same([1, 2, 3, 4, 5, 6, 7, 8], [1, 2, 3, 4, 5, 6, 7, 8])]
+    #[case::first_word([0, 2, 3, 4, 5, 6, 7, 8], [1, 2, 3, 4, 5, 6, 7, 8])]
+    #[case::last_word([1, 2, 3, 4, 5, 6, 7, 8], [1, 2, 3, 4, 5, 6, 7, 0])]
+    #[case::single_bit([1, 2, 3, 4, 5, 6, 7, 8], [1, 2, 3, 4, 5, 6, 7, 8 | 1 << 31])]
+    fn timing_safe_eq_agrees_with_eq(#[case] a: [u32; 8], #[case] b: [u32; 8]) {
+        let (a, b) = (Vk::from(a), Vk::from(b));
+
+        assert_eq!(a.0 == b.0, a.timing_safe_eq(&b));
+        assert_eq!(a.0 == b.0, b.timing_safe_eq(&a));
+        assert!(a.timing_safe_eq(&a));
+    }
+
+    #[test]
+    fn index_all_the_words() {
+        let words = [1, 2, 3, 4, 5, 6, 7, 8];
+        let vk = Vk::from_words(words);
+
+        for (i, word) in words.into_iter().enumerate() {
+            assert_eq!(word, vk[i]);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Vk word index out of range: the index is 8")]
+    fn index_out_of_range_panics() {
+        let _ = Vk::from_words([1; 8])[8];
+    }
+
+    #[test]
+    fn iter_words_in_order() {
+        let vk = vk();
+
+        assert!(vk.iter_words().eq(vk.as_words().iter().copied()));
+        assert_eq!(8, vk.iter_words().count());
+    }
+
+    #[test]
+    fn words_round_trip() {
+        let words = [
+            1067704626, 3452143673, 166143985, 2720203724, 4153258584, 3584210768, 3821389021,
+            2575106175,
+        ];
+
+        assert_eq!(words, Vk::from_words(words).words_as_array());
+        assert_eq!(vk().0, Vk::from_words(vk().words_as_array()).0);
+    }
+
+    fn swap_words(bytes: [u8; 32]) -> [u8; 32] {
+        let mut swapped = bytes;
+        swapped.chunks_exact_mut(4).for_each(<[u8]>::reverse);
+        swapped
+    }
+
+    #[test]
+    fn from_bytes_be_is_the_byte_swapped_from_bytes_le() {
+        let le = vk().to_bytes_le();
+        let be = swap_words(le);
+
+        assert_eq!(
+            Vk::from_bytes_le(&le).unwrap().0,
+            Vk::from_bytes_be(&be).unwrap().0
+        );
+        assert_eq!(vk().0, Vk::from_bytes_be(&be).unwrap().0);
+        assert_eq!(be, vk().to_bytes_be());
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let vk = vk();
+
+        assert_eq!(vk.0, Vk::from_bytes_le(&vk.to_bytes_le()).unwrap().0);
+        assert_eq!(vk.0, Vk::from_bytes_be(&vk.to_bytes_be()).unwrap().0);
+        assert_eq!(vk.as_bytes(), vk.to_bytes_le());
+    }
+
+    #[rstest]
+    #[case::empty(0)]
+    #[case::short(31)]
+    #[case::long(33)]
+    fn from_bytes_wrong_length(#[case] len: usize) {
+        let bytes = [1_u8; 40];
+        let expected = VkFromBytesError::WrongLength {
+            expected: 32,
+            got: len,
+        };
+
+        assert_eq!(expected, Vk::from_bytes_le(&bytes[..len]).unwrap_err());
+        assert_eq!(expected, Vk::from_bytes_be(&bytes[..len]).unwrap_err());
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct HexVk(#[serde(with = "super::serde_hex")] Vk);
+
+    const VK_HEX: &str = "32e1a33f3988c3cdf127e709cc0323a258b28df750b7a2d5ddc4c5e37f007d99";
+
+    fn vk() -> Vk {
+        Vk::from([
+            1067704626, 3452143673, 166143985, 2720203724, 4153258584, 3584210768, 3821389021,
+            2575106175,
+        ])
+    }
+
+    #[test]
+    fn serde_hex_serializes_a_string() {
+        assert_eq!(
+            std::format!("\"{VK_HEX}\""),
+            serde_json::to_string(&HexVk(vk())).unwrap()
+        );
+    }
+
+    #[test]
+    fn serde_hex_round_trip() {
+        let json = serde_json::to_string(&HexVk(vk())).unwrap();
+
+        let decoded: HexVk = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(vk().0, decoded.0 .0);
+    }
+
+    #[rstest]
+    #[case::not_hex("\"zz\"")]
+    #[case::too_short("\"32e1a33f\"")]
+    #[case::words("[1, 2, 3, 4, 5, 6, 7, 8]")]
+    fn serde_hex_rejects_invalid_input(#[case] json: &str) {
+        assert!(serde_json::from_str::<HexVk>(json).is_err());
+    }
 }
@@ -16,28 +16,87 @@
 #![no_std]
 #![doc = include_str!("../README.md")]
 
-#[cfg(test)]
+#[cfg(any(test, feature = "std"))]
 extern crate std;
 
 extern crate alloc;
 extern crate core;
 
-pub use context::SegmentInfo;
-pub use key::Vk;
+pub use context::{SegmentInfo, DEFAULT_MAX_PROOF_BYTES};
+pub use hash::{HashFunction, UnknownHashFunction};
+#[cfg(feature = "compute-vk")]
+pub use key::VkFromElfError;
+pub use key::{Vk, VkError, VkFromBytesError};
+pub use receipt::succinct::{SUCCINCT_SEAL_LEN_V1, SUCCINCT_SEAL_LEN_V2};
 pub use receipt::{
-    composite::CompositeReceipt, succinct::SuccinctReceipt, InnerReceipt, Journal, Proof,
+    composite::{CompositeReceipt, PrunedCompositeReceipt},
+    groth16::{Groth16Receipt, Groth16ReceiptVerifierParameters},
+    succinct::{
+        SuccinctReceipt, SuccinctReceiptVerifierParameters,
+        SuccinctReceiptVerifierParametersBuilder, SUCCINCT_SEAL_LEN,
+    },
+    InnerAssumptionReceipt, InnerReceipt, Journal, JournalDecodeError, JournalTooLargeError, Proof,
+    ReceiptKind, DEFAULT_MAX_JOURNAL_BYTES,
+};
+pub use segment::{
+    PrunedSegmentReceipt, SegmentReceipt, SegmentReceiptNewError, SegmentReceiptVerifierParameters,
+    SegmentReceiptsExt,
+};
+
+#[cfg(feature = "ethereum")]
+pub use abi::{decode_abi, AbiDecodeError, AbiEncodeError};
+#[cfg(feature = "migration")]
+pub use migration::{
+    migrate_journal_bincode_to_cbor, migrate_proof_bincode_to_cbor, MigrationError,
 };
+#[cfg(feature = "dev-mode")]
+pub use receipt::fake::FakeReceipt;
+#[cfg(feature = "risc0-zkvm-compat")]
+pub use receipt::zkvm_compat::ReceiptConversionError;
+#[cfg(feature = "std")]
+pub use receipt::ProofFileError;
 pub use sha::{Digest, Digestible};
 
 pub use risc0_zkp_v1::verify::VerificationError;
-pub use verifier::Verifier;
+pub use verifier::{
+    FilteredVerifier, ProofCost, SegmentVerificationError, UnknownProverVersion,
+    VerificationReport, Verifier,
+};
+
+/// Emit a debug event with `tracing` when the `tracing` feature is enabled, with `log` otherwise.
+macro_rules! debug {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)*);
+        #[cfg(not(feature = "tracing"))]
+        log::debug!($($arg)*);
+    }};
+}
 
+/// Emit a warning event with `tracing` when the `tracing` feature is enabled, with `log`
+/// otherwise.
+#[cfg(feature = "dev-mode")]
+macro_rules! warn {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "tracing")]
+        tracing::warn!($($arg)*);
+        #[cfg(not(feature = "tracing"))]
+        log::warn!($($arg)*);
+    }};
+}
+
+#[cfg(feature = "ethereum")]
+pub mod abi;
+pub mod key;
 pub mod poseidon2_injection;
 pub mod sha;
 
 mod circuit;
 mod context;
-mod key;
+mod hash;
+mod hash_wrapper;
+#[cfg(feature = "migration")]
+mod migration;
 mod receipt;
 pub mod receipt_claim;
 mod segment;
@@ -59,42 +118,177 @@ pub fn verify(
     verifier.verify(vk.0, proof, pubs)
 }
 
+/// Computes the digest of the [`ReceiptClaim`](receipt_claim::ReceiptClaim) that a valid proof
+/// for the given `image_id` and `journal_digest` must commit to: the execution halted with exit
+/// code 0 and without any pending assumption.
+pub fn compute_expected_claim_digest(image_id: Digest, journal_digest: Digest) -> Digest {
+    receipt_claim::ReceiptClaim::ok(image_id, receipt_claim::MaybePruned::Pruned(journal_digest))
+        .digest()
+}
+
+/// Computes the digest of the [`ReceiptClaim`](receipt_claim::ReceiptClaim) of an execution of
+/// `image_id` that paused with the user exit `code` after committing `journal_digest`.
+pub fn compute_paused_claim_digest(image_id: Digest, code: u32, journal_digest: Digest) -> Digest {
+    let mut claim = receipt_claim::ReceiptClaim::paused(
+        image_id,
+        receipt_claim::MaybePruned::Pruned(journal_digest),
+    );
+    claim.exit_code = risc0_binfmt_v1::ExitCode::Paused(code);
+    claim.digest()
+}
+
 /// Returns a `Verifier` for the specified RISC Zero prover 1.0 version.
-pub fn v1_0() -> impl Verifier {
+pub fn v1_0() -> impl Verifier + core::fmt::Debug + core::fmt::Display {
     context::v1::V1::v1_0()
 }
 
 /// Returns a `Verifier` for the specified RISC Zero prover 1.1 version.
-pub fn v1_1() -> impl Verifier {
+pub fn v1_1() -> impl Verifier + core::fmt::Debug + core::fmt::Display {
     context::v1::V1::v1_1()
 }
 
 /// Returns a `Verifier` for the specified RISC Zero prover 1.2 version.
-pub fn v1_2() -> impl Verifier {
+pub fn v1_2() -> impl Verifier + core::fmt::Debug + core::fmt::Display {
     context::v1::V1::v1_2()
 }
 
 /// Returns a `Verifier` for the specified RISC Zero prover 2.0 version.
-pub fn v2_0() -> impl Verifier {
+pub fn v2_0() -> impl Verifier + core::fmt::Debug + core::fmt::Display {
     context::v2::V2::v2_0()
 }
 
 /// Returns a `Verifier` for the specified RISC Zero prover 2.1 version.
-pub fn v2_1() -> impl Verifier {
+pub fn v2_1() -> impl Verifier + core::fmt::Debug + core::fmt::Display {
     context::v2::V2::v2_1()
 }
 
 /// Returns a `Verifier` for the specified RISC Zero prover 2.2 version.
-pub fn v2_2() -> impl Verifier {
+pub fn v2_2() -> impl Verifier + core::fmt::Debug + core::fmt::Display {
     context::v2::V2::v2_2()
 }
 
 /// Returns a `Verifier` for the specified RISC Zero prover 2.3 version.
-pub fn v2_3() -> impl Verifier {
+pub fn v2_3() -> impl Verifier + core::fmt::Debug + core::fmt::Display {
     context::v2::V2::v2_3()
 }
 
 /// Returns a `Verifier` for the specified RISC Zero prover 3.0 version.
-pub fn v3_0() -> impl Verifier {
+pub fn v3_0() -> impl Verifier + core::fmt::Debug + core::fmt::Display {
     context::v3::V3::v3_0()
 }
+
+type VerifierFactory = fn() -> alloc::boxed::Box<dyn Verifier>;
+
+/// The supported prover versions, newest first: the `verifier_parameters` digest of the composite
+/// receipts generated by the prover and the constructor of the verifier. The segment parameters
+/// cannot tell the versions apart, since the 1.1 and 1.2 provers share them as the 2.x ones do,
+/// so the digests of the risc0 composite parameters are pinned here. The 2.2 prover is left out:
+/// it generates receipts with the same parameters as the 2.3 one.
+const PROVER_VERSIONS: [(Digest, VerifierFactory); 7] = [
+    (
+        risc0_zkp_v1::core::digest::digest!(
+            "4bce006e0858edf3a3726987c0b1b6258224c000971e451bc9c05cfec086a84b"
+        ),
+        || v3_0().boxed(),
+    ),
+    (
+        risc0_zkp_v1::core::digest::digest!(
+            "12d12ff8404824a12d4242993527a6ae5b15037a454e9a8fa682e00b62fdc6f5"
+        ),
+        || v2_3().boxed(),
+    ),
+    (
+        risc0_zkp_v1::core::digest::digest!(
+            "50ffbc35d194e6c9f5fc8adb030d077f3bf5393d5e9cedc6f303a9f1eede3a32"
+        ),
+        || v2_1().boxed(),
+    ),
+    (
+        risc0_zkp_v1::core::digest::digest!(
+            "3daead8f1ec08eb96b60ce6cad42f82eba80f6cf89ba5007ca317e57256b6038"
+        ),
+        || v2_0().boxed(),
+    ),
+    (
+        risc0_zkp_v1::core::digest::digest!(
+            "d56767c98914dd6bdc45782fbe02eda0c3a3102ae28fdd70e7c55d701e5db42d"
+        ),
+        || v1_2().boxed(),
+    ),
+    (
+        risc0_zkp_v1::core::digest::digest!(
+            "e676b5e35f46df092b82e64c17d1b9d96341c70c03e68cb6ebd0968e5529c193"
+        ),
+        || v1_1().boxed(),
+    ),
+    (
+        risc0_zkp_v1::core::digest::digest!(
+            "0a12bed13d02e3b2864daeb2405ce14658388b804007aa3cac696762cfe35fdb"
+        ),
+        || v1_0().boxed(),
+    ),
+];
+
+/// Returns the `Verifier` of the RISC Zero prover version that generated the given proof, chosen
+/// by the proof `verifier_parameters` digest: for a composite receipt it's the digest of the
+/// composite parameters of the prover, for a succinct or a Groth16 one the digest of the verifier
+/// succinct or Groth16 parameters. Versions that generate receipts with the same parameters
+/// verify the same proofs: the newest one is returned, as for a fake receipt with the `dev-mode`
+/// feature.
+///
+/// The proof is not verified: use the returned verifier to do it.
+pub fn detect_verifier(
+    proof: &Proof,
+) -> Result<alloc::boxed::Box<dyn Verifier>, UnknownProverVersion> {
+    let received = proof.inner.verifier_parameters();
+    let found =
+        match &proof.inner {
+            InnerReceipt::Composite(_) => PROVER_VERSIONS
+                .iter()
+                .find(|(segment_params, _)| *segment_params == received)
+                .map(|(_, verifier)| verifier()),
+            InnerReceipt::Succinct(_) => PROVER_VERSIONS
+                .iter()
+                .map(|(_, verifier)| verifier())
+                .find(|verifier| {
+                    verifier.succinct_verifier_parameters().map(|p| p.digest()) == Some(received)
+                }),
+            InnerReceipt::Groth16(_) => PROVER_VERSIONS
+                .iter()
+                .map(|(_, verifier)| verifier())
+                .find(|verifier| {
+                    verifier.groth16_verifier_parameters().map(|p| p.digest()) == Some(received)
+                }),
+            // A fake receipt doesn't depend on the prover version: any verifier accepts it.
+            #[cfg(feature = "dev-mode")]
+            InnerReceipt::Fake(_) => PROVER_VERSIONS.first().map(|(_, verifier)| verifier()),
+        };
+    found.ok_or(UnknownProverVersion {
+        verifier_parameters: received,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prover_versions_have_distinct_digests() {
+        let digests = PROVER_VERSIONS
+            .iter()
+            .map(|(digest, _)| *digest)
+            .collect::<alloc::collections::BTreeSet<_>>();
+
+        assert_eq!(PROVER_VERSIONS.len(), digests.len());
+    }
+
+    #[test]
+    fn segment_parameters_are_shared_between_prover_versions() {
+        let digest = |verifier: alloc::boxed::Box<dyn Verifier>| {
+            verifier.segment_verifier_parameters().unwrap().digest()
+        };
+
+        assert_eq!(digest(v1_1().boxed()), digest(v1_2().boxed()));
+        assert_eq!(digest(v2_0().boxed()), digest(v2_3().boxed()));
+    }
+}
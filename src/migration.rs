@@ -0,0 +1,89 @@
+// Copyright 2024, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Migrate the [Proof]s and [Journal]s stored with the old `bincode` encoding to CBOR. Available
+//! only with the `migration` feature: the `convert_old` binary does the same from the command line.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{Journal, Proof};
+
+/// Error returned when a `bincode` encoded value cannot be migrated to CBOR.
+#[derive(Debug)]
+pub enum MigrationError {
+    /// The input is not a valid `bincode` encoding of the requested type.
+    BincodeRead(bincode::Error),
+    /// The value cannot be encoded in CBOR.
+    CborWrite(ciborium::ser::Error<std::io::Error>),
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::BincodeRead(e) => write!(f, "bincode read error: {e}"),
+            Self::CborWrite(e) => write!(f, "cbor write error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+/// Decode a `bincode` encoded [Proof] and encode it in CBOR.
+pub fn migrate_proof_bincode_to_cbor(bincode_bytes: &[u8]) -> Result<Vec<u8>, MigrationError> {
+    migrate::<Proof>(bincode_bytes)
+}
+
+/// Decode a `bincode` encoded [Journal] and encode it in CBOR.
+pub fn migrate_journal_bincode_to_cbor(bincode_bytes: &[u8]) -> Result<Vec<u8>, MigrationError> {
+    migrate::<Journal>(bincode_bytes)
+}
+
+fn migrate<T: Serialize + DeserializeOwned>(
+    bincode_bytes: &[u8],
+) -> Result<Vec<u8>, MigrationError> {
+    let value: T = bincode::deserialize(bincode_bytes).map_err(MigrationError::BincodeRead)?;
+    let mut buffer = Vec::new();
+    ciborium::into_writer(&value, &mut buffer).map_err(MigrationError::CborWrite)?;
+    Ok(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn journal_round_trip() {
+        let journal = Journal::new(alloc::vec![1, 2, 3, 4, 5]);
+        let encoded = bincode::serialize(&journal).unwrap();
+
+        let migrated = migrate_journal_bincode_to_cbor(&encoded).unwrap();
+
+        assert_eq!(
+            journal,
+            ciborium::from_reader::<Journal, _>(migrated.as_slice()).unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_bincode() {
+        assert!(matches!(
+            migrate_proof_bincode_to_cbor(&[1, 2, 3]),
+            Err(MigrationError::BincodeRead(_))
+        ));
+    }
+}
@@ -118,6 +118,27 @@ impl Poseidon2Mix for alloc::boxed::Box<dyn Poseidon2Mix + Send + Sync> {
     }
 }
 
+/// [Poseidon2Mix] implementation that use the reference [poseidon2_mix] function.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultPoseidon2;
+
+impl Poseidon2Mix for DefaultPoseidon2 {
+    #[inline]
+    fn poseidon2_mix(&self, cells: &mut [BabyBearElem; POSEIDON2_CELLS]) {
+        poseidon2_mix(cells);
+    }
+}
+
+/// [Poseidon2Mix] implementation that leave the cells untouched: no proof can be verified
+/// with it. Useful just to measure the overhead of the injection layer.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoOpPoseidon2;
+
+impl Poseidon2Mix for NoOpPoseidon2 {
+    #[inline]
+    fn poseidon2_mix(&self, _cells: &mut [BabyBearElem; POSEIDON2_CELLS]) {}
+}
+
 pub trait Boxed {
     fn boxed(self) -> Box<dyn Poseidon2Mix + Send + Sync>;
 }
@@ -143,6 +164,12 @@ fn to_digest(elems: [BabyBearElem; CELLS_OUT]) -> Box<Digest> {
     Box::new(Digest::from(state))
 }
 
+impl Default for Poseidon2Impl<DefaultPoseidon2> {
+    fn default() -> Self {
+        Self::new(DefaultPoseidon2)
+    }
+}
+
 impl<T: Poseidon2Mix> Poseidon2Impl<T> {
     /// Perform an unpadded hash of a vector of elements.  Because this is unpadded
     /// collision resistance is only true for vectors of the same size.  If the size
@@ -277,3 +304,105 @@ mod v3 {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use risc0_zkp_v1::{
+        core::hash::poseidon2::Poseidon2HashSuite, field::baby_bear::BabyBearExtElem,
+    };
+    use rstest::rstest;
+
+    fn elems(len: usize) -> Vec<BabyBearElem> {
+        (0..len as u32)
+            .map(|i| BabyBearElem::new(i * 7919 + 1))
+            .collect()
+    }
+
+    fn builtin() -> alloc::rc::Rc<dyn HashFn<BabyBear>> {
+        Poseidon2HashSuite::new_suite().hashfn
+    }
+
+    #[rstest]
+    #[case::empty(0)]
+    #[case::single(1)]
+    #[case::two(2)]
+    #[case::full_rate(CELLS_RATE)]
+    #[case::more_than_rate(CELLS_RATE + 3)]
+    #[case::two_rates(2 * CELLS_RATE)]
+    fn hash_elem_slice_match_the_builtin_hash_function(#[case] len: usize) {
+        let injected = Poseidon2Impl::<DefaultPoseidon2>::default();
+        let elems = elems(len);
+
+        assert_eq!(
+            builtin().hash_elem_slice(&elems),
+            injected.hash_elem_slice(&elems)
+        );
+    }
+
+    #[rstest]
+    #[case::empty(0)]
+    #[case::single(1)]
+    #[case::two(2)]
+    #[case::full_rate(CELLS_RATE / 4)]
+    #[case::more_than_rate(CELLS_RATE / 4 + 1)]
+    fn hash_ext_elem_slice_match_the_builtin_hash_function(#[case] len: usize) {
+        let injected = Poseidon2Impl::<DefaultPoseidon2>::default();
+        let elems = elems(4 * len)
+            .chunks_exact(4)
+            .map(|c| BabyBearExtElem::new(c[0], c[1], c[2], c[3]))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            builtin().hash_ext_elem_slice(&elems),
+            injected.hash_ext_elem_slice(&elems)
+        );
+    }
+
+    #[rstest]
+    #[case::zero(Digest::ZERO, Digest::ZERO)]
+    #[case::hashed(
+        *builtin().hash_elem_slice(&elems(3)),
+        *builtin().hash_elem_slice(&elems(CELLS_RATE))
+    )]
+    #[case::risc0_control_ids(
+        crate::circuit::v1_2::recursive::control_id::ALLOWED_CONTROL_IDS[0],
+        crate::circuit::v1_2::recursive::control_id::ALLOWED_CONTROL_IDS[1]
+    )]
+    fn hash_pair_match_the_builtin_hash_function(#[case] a: Digest, #[case] b: Digest) {
+        let injected = Poseidon2Impl::<DefaultPoseidon2>::default();
+
+        assert_eq!(builtin().hash_pair(&a, &b), injected.hash_pair(&a, &b));
+    }
+
+    #[test]
+    fn v1_and_v2_adapters_produce_the_same_digests() {
+        use risc0_core_v2::field::baby_bear::{
+            BabyBear as BabyBearV2, BabyBearElem as BabyBearElemV2,
+        };
+        use risc0_zkp_v2::core::{
+            digest::Digest as DigestV2,
+            hash::{poseidon2::Poseidon2HashSuite as Poseidon2HashSuiteV2, HashFn as HashFnV2},
+        };
+
+        let injected = Poseidon2Impl::<DefaultPoseidon2>::default();
+        let builtin_v2 = Poseidon2HashSuiteV2::new_suite().hashfn;
+        let elems = elems(CELLS_RATE + 3);
+        let elems_v2: &[BabyBearElemV2] = bytemuck::checked::cast_slice(&elems);
+
+        let v1 = *HashFn::<BabyBear>::hash_elem_slice(&injected, &elems);
+        let v2 = *HashFnV2::<BabyBearV2>::hash_elem_slice(&injected, elems_v2);
+
+        assert_eq!(v1.as_words(), v2.as_words());
+        assert_eq!(*builtin_v2.hash_elem_slice(elems_v2), v2);
+
+        let a: &DigestV2 = bytemuck::checked::cast_ref(&v1);
+        let b = DigestV2::ZERO;
+        let v1_pair = *HashFn::<BabyBear>::hash_pair(&injected, &v1, &Digest::ZERO);
+        let v2_pair = *HashFnV2::<BabyBearV2>::hash_pair(&injected, a, &b);
+
+        assert_eq!(v1_pair.as_words(), v2_pair.as_words());
+        assert_eq!(*builtin_v2.hash_pair(a, &b), v2_pair);
+    }
+}
@@ -16,20 +16,29 @@
 // limitations under the License.
 //
 
-use alloc::vec::Vec;
+use alloc::{borrow::Cow, string::String, vec::Vec};
 use composite::CompositeReceipt;
+use core::fmt;
 use risc0_zkp_v1::{core::digest::Digest, verify::VerificationError};
 use serde::{Deserialize, Serialize};
 
 use crate::{
     context::VerifierContext,
-    receipt_claim::{MaybePruned, ReceiptClaim, Unknown},
+    receipt::groth16::Groth16Receipt,
+    receipt_claim::{
+        Assumption, ExitCode, MaybePruned, Output, ReceiptClaim, ShortDigest, Unknown,
+    },
     sha::{Digestible, Sha256},
 };
 use succinct::SuccinctReceipt;
 
 pub mod composite;
+#[cfg(feature = "dev-mode")]
+pub mod fake;
+pub mod groth16;
 pub mod succinct;
+#[cfg(feature = "risc0-zkvm-compat")]
+pub mod zkvm_compat;
 
 pub mod merkle;
 /// Maximum segment size, as a power of two (po2) that the default verifier parameters will accept.
@@ -38,6 +47,13 @@ pub mod merkle;
 /// a po2 higher than 21 shows a degradation of 1 bit of security per po2, to 94 bits at po2 24.
 pub const DEFAULT_MAX_PO2: usize = 21;
 
+/// CBOR encodes every seal word as an integer of up to 5 bytes.
+const SEAL_WORD_CBOR_BYTES: usize = 5;
+
+/// Bytes allowed in a CBOR encoded [Proof] on top of its seals, for the claim, the journal and
+/// the other fields.
+const CBOR_ENVELOPE_BYTES: usize = 64 * 1024;
+
 /// A wrapper around [InnerReceipt]. It can be deserialized from a Risc0 receipt where it
 /// just ignore the journal and metadata fields.
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -52,6 +68,59 @@ impl Proof {
         Self { inner }
     }
 
+    /// Construct a composite [Proof].
+    pub fn from_composite(c: CompositeReceipt) -> Self {
+        Self::new(InnerReceipt::Composite(c))
+    }
+
+    /// Construct a succinct [Proof].
+    pub fn from_succinct(s: SuccinctReceipt<ReceiptClaim>) -> Self {
+        Self::new(InnerReceipt::Succinct(s))
+    }
+
+    /// Construct a Groth16 [Proof].
+    pub fn from_groth16(g: Groth16Receipt<ReceiptClaim>) -> Self {
+        Self::new(InnerReceipt::Groth16(g))
+    }
+
+    /// Return `true` if this is a composite proof.
+    pub fn is_composite(&self) -> bool {
+        matches!(self.inner, InnerReceipt::Composite(_))
+    }
+
+    /// Return `true` if this is a succinct proof.
+    pub fn is_succinct(&self) -> bool {
+        matches!(self.inner, InnerReceipt::Succinct(_))
+    }
+
+    /// Return `true` if this is a Groth16 proof.
+    pub fn is_groth16(&self) -> bool {
+        matches!(self.inner, InnerReceipt::Groth16(_))
+    }
+
+    /// Consume the proof and return its [CompositeReceipt]: fail with
+    /// [VerificationError::ReceiptFormatError] if it is not a composite proof.
+    pub fn try_into_composite(self) -> Result<CompositeReceipt, VerificationError> {
+        match self.inner {
+            InnerReceipt::Composite(c) => Ok(c),
+            _ => Err(VerificationError::ReceiptFormatError),
+        }
+    }
+
+    /// Consume the proof and return its [SuccinctReceipt]: fail with
+    /// [VerificationError::ReceiptFormatError] if it is not a succinct proof.
+    pub fn try_into_succinct(self) -> Result<SuccinctReceipt<ReceiptClaim>, VerificationError> {
+        match self.inner {
+            InnerReceipt::Succinct(s) => Ok(s),
+            _ => Err(VerificationError::ReceiptFormatError),
+        }
+    }
+
+    /// Construct a [Proof] from a single segment (see [CompositeReceipt::from_single_segment]).
+    pub fn from_single_segment(segment: crate::SegmentReceipt) -> Self {
+        Self::from_composite(CompositeReceipt::from_single_segment(segment))
+    }
+
     /// Verifies that this receipt proves a successful execution of the zkVM for the given `image_id`.
     ///
     /// This method uses a zero-knowledge proof system to verify the seal and decodes the proven
@@ -71,31 +140,281 @@ impl Proof {
         image_id: impl Into<Digest>,
         pubs: impl Into<Digest>,
     ) -> Result<(), VerificationError> {
-        log::debug!("Receipt::is_valid_receipt");
-        if !ctx.is_valid_receipt(self) {
-            log::debug!("Invalid receipt");
-            return Err(VerificationError::ReceiptFormatError);
+        self.verify_with_journal_digest(ctx, image_id, pubs.into())
+    }
+
+    /// Verify this receipt like [Proof::verify] but against the SHA digest of the journal:
+    /// useful when the caller holds only the digest (e.g. an on-chain commitment) and not the
+    /// whole [Journal].
+    pub fn verify_with_journal_digest(
+        &self,
+        ctx: &impl crate::context::VerifierContext,
+        image_id: impl Into<Digest>,
+        journal_digest: Digest,
+    ) -> Result<(), VerificationError> {
+        self.verify_with_exit_code(ctx, image_id, journal_digest, ExitCode::Halted(0))
+    }
+
+    /// Verify this receipt like [Proof::verify_with_journal_digest] but expecting the execution
+    /// to end with `exit_code` instead of `Halted(0)`: e.g. `Halted(n)` for a guest that exited
+    /// with a user code or `Paused(n)` for one that paused. A halted execution ends in the
+    /// zeroed post state, as in [ReceiptClaim::ok]; any other execution ends in the state to
+    /// resume from, which cannot be known in advance, so it's taken from the verified claim.
+    /// `SystemSplit` and `SessionLimit` never end a complete execution: they are rejected with
+    /// [VerificationError::UnexpectedExitCode].
+    pub fn verify_with_exit_code(
+        &self,
+        ctx: &impl crate::context::VerifierContext,
+        image_id: impl Into<Digest>,
+        journal_digest: Digest,
+        exit_code: ExitCode,
+    ) -> Result<(), VerificationError> {
+        self.verify_with(ctx, image_id, journal_digest, exit_code, |inner| {
+            inner.verify_integrity_with_context(ctx)
+        })
+    }
+
+    /// Verify this receipt like [Proof::verify_with_journal_digest] with a context built by
+    /// `new_ctx`. With the `parallel` feature the segments of a composite receipt are verified
+    /// in parallel, each thread with its own context built by `new_ctx`: the contexts hold `Rc`
+    /// hash suites, so they cannot be shared between threads. Without the feature, or for a
+    /// succinct receipt, the receipt is verified sequentially.
+    pub fn verify_parallel<C: crate::context::VerifierContext>(
+        &self,
+        new_ctx: impl Fn() -> C + Send + Sync,
+        image_id: impl Into<Digest>,
+        journal_digest: Digest,
+    ) -> Result<(), VerificationError> {
+        self.verify_parallel_with(&new_ctx(), new_ctx, image_id, journal_digest)
+    }
+
+    /// [Proof::verify_parallel] with an already built context `ctx`, used to verify everything
+    /// but the segment seals.
+    pub(crate) fn verify_parallel_with<C: crate::context::VerifierContext>(
+        &self,
+        ctx: &C,
+        new_ctx: impl Fn() -> C + Send + Sync,
+        image_id: impl Into<Digest>,
+        journal_digest: Digest,
+    ) -> Result<(), VerificationError> {
+        // Without the `parallel` feature the segments are verified with `ctx` too.
+        #[cfg(not(feature = "parallel"))]
+        let _ = new_ctx;
+        let halted = ExitCode::Halted(0);
+        self.verify_with(ctx, image_id, journal_digest, halted, |inner| match inner {
+            #[cfg(feature = "parallel")]
+            InnerReceipt::Composite(composite) => {
+                composite.verify_integrity_parallel(ctx, &new_ctx)
+            }
+            _ => inner.verify_integrity_with_context(ctx),
+        })
+    }
+
+    fn verify_with(
+        &self,
+        ctx: &impl crate::context::VerifierContext,
+        image_id: impl Into<Digest>,
+        journal_digest: Digest,
+        exit_code: ExitCode,
+        verify_integrity: impl FnOnce(&InnerReceipt) -> Result<(), VerificationError>,
+    ) -> Result<(), VerificationError> {
+        if matches!(exit_code, ExitCode::SystemSplit | ExitCode::SessionLimit) {
+            debug!("Not a final exit code: {exit_code:?}");
+            return Err(VerificationError::UnexpectedExitCode);
         }
+        self.precheck(ctx)?;
 
-        log::debug!("Receipt::verify_with_context");
-        self.inner.verify_integrity_with_context(ctx)?;
+        debug!("Receipt::verify_with_context");
+        verify_integrity(&self.inner)?;
 
         // Check that the claim on the verified receipt matches what was expected. Since we have
         // constrained all field in the ReceiptClaim, we can directly construct the expected digest
         // and do not need to open the claim digest on the inner receipt.
-        let expected_claim = ReceiptClaim::ok(image_id, MaybePruned::Pruned(pubs.into()));
-        if expected_claim.digest() != self.inner.claim()?.digest() {
-            log::debug!(
-                "receipt claim does not match expected claim:\nreceipt: {:#?}\nexpected: {:#?}",
-                self.inner.claim()?,
-                expected_claim
+        let mut expected_claim = ReceiptClaim::ok(image_id, MaybePruned::Pruned(journal_digest));
+        if !matches!(exit_code, ExitCode::Halted(_)) {
+            let claim = self.inner.claim()?;
+            let post = claim
+                .as_value()
+                .map_err(|_| VerificationError::ReceiptFormatError)?
+                .post_state_digest();
+            expected_claim.post = MaybePruned::Pruned(post);
+        }
+        expected_claim.exit_code = exit_code;
+        check_claim(&expected_claim, &self.inner.claim()?)
+    }
+
+    /// Verify this receipt like [Proof::verify], but accept a conditional receipt: a composite
+    /// receipt whose assumption receipts resolve only the first assumptions, or a succinct one
+    /// whose claim has assumptions. Return the claim digests of the assumptions left
+    /// unresolved, in order: the proof holds only once they are resolved externally. The
+    /// assumption receipts that are provided are verified.
+    pub fn verify_conditional(
+        &self,
+        ctx: &impl crate::context::VerifierContext,
+        image_id: impl Into<Digest>,
+        pubs: impl Into<Digest>,
+    ) -> Result<Vec<Digest>, VerificationError> {
+        self.precheck(ctx)?;
+
+        debug!("Receipt::verify_conditional");
+        let (received, unresolved) = match &self.inner {
+            InnerReceipt::Composite(inner) => {
+                let unresolved = inner.verify_conditional_integrity_with_context(ctx)?;
+                let claim = inner.conditional_claim(unresolved.clone())?;
+                (MaybePruned::Value(claim), unresolved)
+            }
+            inner => {
+                inner.verify_integrity_with_context(ctx)?;
+                let claim = inner.claim()?;
+                let unresolved = claim
+                    .as_value()
+                    .ok()
+                    .and_then(|claim| claim.output.as_value().ok()?.as_ref())
+                    .map(Output::assumptions_list)
+                    .transpose()
+                    .map_err(|_| VerificationError::ReceiptFormatError)?
+                    .unwrap_or_default();
+                (claim, unresolved)
+            }
+        };
+
+        let journal = MaybePruned::Pruned(pubs.into());
+        let mut expected_claim = ReceiptClaim::ok(image_id, journal.clone());
+        if !unresolved.is_empty() {
+            expected_claim.output = Some(Output {
+                journal,
+                assumptions: unresolved.clone().into(),
+            })
+            .into();
+        }
+        check_claim(&expected_claim, &received)?;
+        Ok(unresolved.into_iter().map(|a| a.claim).collect())
+    }
+
+    /// The checks of the proof before verifying its seals.
+    fn precheck(&self, ctx: &impl VerifierContext) -> Result<(), VerificationError> {
+        self.check_format(ctx.verifier_parameters().options.max_proof_bytes)?;
+
+        debug!("Receipt::is_valid_receipt");
+        if !ctx.is_valid_receipt(self) {
+            debug!("Invalid receipt");
+            return Err(VerificationError::ReceiptFormatError);
+        }
+
+        // Cheap check of the verifier parameters digests before verifying the seals.
+        self.check_verifier_parameters(ctx)
+    }
+
+    /// Decode a CBOR encoded [Proof] and [check its format](Proof::check_format) against
+    /// `max_proof_bytes` (e.g. [Verifier::max_proof_bytes](crate::Verifier::max_proof_bytes)).
+    /// Return [VerificationError::ReceiptFormatError] if the data cannot be decoded or the
+    /// proof is too big. Data longer than the CBOR encoding of `max_proof_bytes` of seals plus a
+    /// small envelope is rejected before decoding it.
+    pub fn from_cbor(data: &[u8], max_proof_bytes: usize) -> Result<Proof, VerificationError> {
+        let max_data_len = (max_proof_bytes / WORD_SIZE)
+            .saturating_mul(SEAL_WORD_CBOR_BYTES)
+            .saturating_add(CBOR_ENVELOPE_BYTES);
+        if data.len() > max_data_len {
+            debug!(
+                "CBOR proof of {} bytes exceeds the limit of {max_data_len} bytes",
+                data.len()
             );
-            return Err(VerificationError::ClaimDigestMismatch {
-                expected: expected_claim.digest(),
-                received: self.claim()?.digest(),
-            });
+            return Err(VerificationError::ReceiptFormatError);
+        }
+        let proof: Proof =
+            ciborium::from_reader(data).map_err(|_| VerificationError::ReceiptFormatError)?;
+        proof.check_format(max_proof_bytes)?;
+        Ok(proof)
+    }
+
+    /// Check that the seals of this proof don't exceed `max_proof_bytes`, otherwise return
+    /// [VerificationError::ReceiptFormatError].
+    pub fn check_format(&self, max_proof_bytes: usize) -> Result<(), VerificationError> {
+        if self.total_seal_bytes() > max_proof_bytes {
+            debug!(
+                "proof seals size {} exceeds the limit of {max_proof_bytes} bytes",
+                self.total_seal_bytes()
+            );
+            return Err(VerificationError::ReceiptFormatError);
+        }
+        Ok(())
+    }
+
+    fn check_verifier_parameters(
+        &self,
+        ctx: &impl crate::context::VerifierContext,
+    ) -> Result<(), VerificationError> {
+        let params = ctx.verifier_parameters();
+        let expected = match &self.inner {
+            // A fake receipt has no verifier parameters to check.
+            #[cfg(feature = "dev-mode")]
+            InnerReceipt::Fake(_) => return Ok(()),
+            InnerReceipt::Composite(_) => params.segment_verifier_parameters().map(|p| p.digest()),
+            InnerReceipt::Succinct(_) => params.succinct_verifier_parameters().map(|p| p.digest()),
+            InnerReceipt::Groth16(_) => params.groth16_verifier_parameters().map(|p| p.digest()),
+        }
+        .ok_or(VerificationError::VerifierParametersMissing)?;
+        let mismatch = match &self.inner {
+            InnerReceipt::Composite(inner) => inner
+                .segments
+                .iter()
+                .map(|s| s.verifier_parameters)
+                .find(|received| *received != expected),
+            InnerReceipt::Succinct(inner) => {
+                Some(inner.verifier_parameters).filter(|received| *received != expected)
+            }
+            InnerReceipt::Groth16(inner) => {
+                Some(inner.verifier_parameters).filter(|received| *received != expected)
+            }
+            #[cfg(feature = "dev-mode")]
+            InnerReceipt::Fake(_) => None,
+        };
+        match mismatch {
+            Some(received) => {
+                debug!("verifier parameters mismatch: expected {expected}, received {received}");
+                Err(VerificationError::VerifierParametersMismatch { expected, received })
+            }
+            None => Ok(()),
         }
+    }
 
+    /// Check that the claims of this proof match the claims committed in the seals, without
+    /// verifying the seals: every segment claim must be the one decoded from its seal, and the
+    /// claim of a succinct receipt must have the digest committed in its seal output. It's far
+    /// cheaper than the full verification and reports a tampered claim as
+    /// [VerificationError::ClaimDigestMismatch] instead of a cryptographic failure; a proof that
+    /// passes it still has to be verified. A Groth16 seal doesn't expose the claim digest, so a
+    /// Groth16 proof always passes this check, as a fake one without any seal.
+    pub fn check_claim_seal_consistency(
+        &self,
+        ctx: &impl VerifierContext,
+    ) -> Result<(), VerificationError> {
+        match &self.inner {
+            InnerReceipt::Composite(inner) => {
+                for segment in &inner.segments {
+                    if !segment.claim_is_consistent(ctx)? {
+                        debug!("segment {} claim doesn't match its seal", segment.index);
+                        return Err(VerificationError::ClaimDigestMismatch {
+                            expected: ctx.decode_from_seal(&segment.seal)?.digest(),
+                            received: segment.claim.digest(),
+                        });
+                    }
+                }
+            }
+            InnerReceipt::Succinct(inner) => {
+                let expected = inner.decode_output_hash_from_seal()?;
+                if expected != inner.claim.digest() {
+                    debug!("succinct receipt claim doesn't match its seal");
+                    return Err(VerificationError::ClaimDigestMismatch {
+                        expected,
+                        received: inner.claim.digest(),
+                    });
+                }
+            }
+            InnerReceipt::Groth16(_) => {}
+            #[cfg(feature = "dev-mode")]
+            InnerReceipt::Fake(_) => {}
+        }
         Ok(())
     }
 
@@ -103,6 +422,236 @@ impl Proof {
     pub fn claim(&self) -> Result<MaybePruned<ReceiptClaim>, VerificationError> {
         self.inner.claim()
     }
+
+    /// Extract the [ReceiptClaim] from this receipt like [Proof::claim]: no verification is
+    /// performed, so the claim cannot be trusted until the proof is verified.
+    pub fn inspect_claim(&self) -> Result<MaybePruned<ReceiptClaim>, VerificationError> {
+        self.claim()
+    }
+
+    /// Digest of the journal committed in the (not verified) claim, or `None` if the claim or
+    /// its output is pruned or absent.
+    pub fn inspect_journal_digest(&self) -> Result<Option<Digest>, VerificationError> {
+        Ok(self
+            .inspect_claim()?
+            .as_value()
+            .ok()
+            .and_then(ReceiptClaim::output_journal_digest))
+    }
+
+    /// Digest of the pre state of the (not verified) claim, i.e. the image id for a complete
+    /// execution.
+    pub fn inspect_pre_state(&self) -> Result<Digest, VerificationError> {
+        self.inspect_claim()?
+            .as_value()
+            .map(ReceiptClaim::pre_state_digest)
+            .map_err(|_| VerificationError::ReceiptFormatError)
+    }
+
+    /// Total number of bytes used by the seals of this proof.
+    pub fn total_seal_bytes(&self) -> usize {
+        self.inner.seal_size()
+    }
+
+    /// Total number of `u32` words used by the seals of this proof: the length of
+    /// [Proof::seal_u32_words]. The bytes of a Groth16 seal are counted as packed in words.
+    pub fn seal_word_count(&self) -> usize {
+        self.total_seal_bytes().div_ceil(WORD_SIZE)
+    }
+
+    /// Collect the seals of this proof in a single flat vector of words: the seals of all the
+    /// segments in order for a composite proof or the seal of a succinct one. Useful to build
+    /// the calldata for an on-chain verifier. The bytes of a Groth16 seal are packed in
+    /// little-endian words.
+    pub fn seal_u32_words(&self) -> Vec<u32> {
+        self.inner.seal_word_slices().concat()
+    }
+
+    /// Write a short summary of this proof, at most 30 lines: the receipt kind, the segments
+    /// (index, hash function and seal words) of a composite receipt or the seal of a succinct
+    /// one, and the shortened `verifier_parameters` digest. Unlike `{:#?}` it doesn't dump the
+    /// seals, so it fits in a log message.
+    pub fn pretty_print(&self, w: &mut impl fmt::Write) -> fmt::Result {
+        const MAX_SEGMENT_ROWS: usize = 25;
+        match &self.inner {
+            InnerReceipt::Composite(inner) => {
+                writeln!(w, "Composite receipt: {} segments", inner.segments.len())?;
+                writeln!(w, "index  hashfn     seal_words")?;
+                let shown = if inner.segments.len() > MAX_SEGMENT_ROWS {
+                    MAX_SEGMENT_ROWS - 1
+                } else {
+                    inner.segments.len()
+                };
+                for s in &inner.segments[..shown] {
+                    writeln!(
+                        w,
+                        "{:>5}  {:<9}  {}",
+                        s.index,
+                        s.hashfn.as_str(),
+                        s.seal.len()
+                    )?;
+                }
+                if shown < inner.segments.len() {
+                    writeln!(w, "... {} more segments", inner.segments.len() - shown)?;
+                }
+            }
+            InnerReceipt::Succinct(inner) => {
+                writeln!(w, "Succinct receipt: {} seal words", inner.seal.len())?;
+                writeln!(w, "hashfn: {}", inner.hashfn)?;
+            }
+            InnerReceipt::Groth16(inner) => {
+                writeln!(w, "Groth16 receipt: {} seal bytes", inner.seal.len())?;
+            }
+            #[cfg(feature = "dev-mode")]
+            InnerReceipt::Fake(_) => {
+                writeln!(w, "Fake receipt: no seal")?;
+            }
+        }
+        writeln!(
+            w,
+            "verifier_parameters: {}",
+            ShortDigest(self.inner.verifier_parameters())
+        )
+    }
+
+    /// The [Proof::pretty_print] summary as a string.
+    pub fn to_debug_string(&self) -> String {
+        let mut out = String::new();
+        // Writing to a `String` cannot fail.
+        let _ = self.pretty_print(&mut out);
+        out
+    }
+
+    /// SHA-256 of the concatenated seal bytes of this proof. Unlike the claim digest, it
+    /// identifies the proof by its cryptographic content: two proofs of the same execution
+    /// generated with different randomness have different fingerprints. A fake receipt has no
+    /// seal, so its claim digest is hashed instead: fake receipts of different claims have
+    /// different fingerprints.
+    pub fn content_fingerprint(&self) -> Digest {
+        #[cfg(feature = "dev-mode")]
+        if let InnerReceipt::Fake(fake) = &self.inner {
+            return *crate::sha::Impl::hash_bytes(fake.claim.digest().as_bytes());
+        }
+        *crate::sha::Impl::hash_bytes(&self.inner.concatenated_seal_bytes())
+    }
+
+    /// Conservative upper bound of the size of this proof CBOR encoded, e.g. to preallocate the
+    /// buffer to serialize it. CBOR encodes every seal word as an integer of up to 5 bytes, the
+    /// journal bytes as integers of up to 2 bytes, and every segment and the proof frame add
+    /// some overhead for the other fields.
+    pub fn estimate_cbor_size(&self) -> usize {
+        const JOURNAL_BYTE_BYTES: usize = 2;
+        const SEGMENT_OVERHEAD: usize = 128;
+        const FRAME_OVERHEAD: usize = 256;
+        // A succinct receipt also carries the control id and its Merkle inclusion proof.
+        const SUCCINCT_OVERHEAD: usize = 8 * SEGMENT_OVERHEAD;
+
+        let receipts_overhead = match &self.inner {
+            InnerReceipt::Composite(c) => c.segments.len() * SEGMENT_OVERHEAD,
+            InnerReceipt::Succinct(_) => SUCCINCT_OVERHEAD,
+            // A Groth16 seal is a byte array: every byte is an integer of up to 2 bytes.
+            InnerReceipt::Groth16(g) => g.seal.len() * JOURNAL_BYTE_BYTES + SEGMENT_OVERHEAD,
+            #[cfg(feature = "dev-mode")]
+            InnerReceipt::Fake(_) => 0,
+        };
+        let journal_len = self
+            .claim()
+            .ok()
+            .and_then(|claim| {
+                claim
+                    .as_value()
+                    .ok()?
+                    .output
+                    .as_value()
+                    .ok()?
+                    .as_ref()?
+                    .journal_bytes()
+                    .map(<[u8]>::len)
+            })
+            .unwrap_or_default();
+        self.seal_word_count() * SEAL_WORD_CBOR_BYTES
+            + journal_len * JOURNAL_BYTE_BYTES
+            + receipts_overhead
+            + FRAME_OVERHEAD
+    }
+
+    /// Both the [ReceiptClaim] and the [content fingerprint](Proof::content_fingerprint) of this
+    /// proof: useful to index a proof database.
+    pub fn claim_and_fingerprint(
+        &self,
+    ) -> (Result<MaybePruned<ReceiptClaim>, VerificationError>, Digest) {
+        (self.claim(), self.content_fingerprint())
+    }
+
+    /// Replace the `verifier_parameters` digests of the receipt and of all its segments with
+    /// [Digest::ZERO]. Proofs generated with different verifier versions embed different
+    /// digests: once normalized they can be compared byte by byte. The claim is not affected.
+    pub fn normalize(mut self) -> Proof {
+        match &mut self.inner {
+            InnerReceipt::Composite(inner) => {
+                inner.verifier_parameters = Digest::ZERO;
+                inner
+                    .segments
+                    .iter_mut()
+                    .for_each(|s| s.verifier_parameters = Digest::ZERO);
+            }
+            InnerReceipt::Succinct(inner) => inner.verifier_parameters = Digest::ZERO,
+            InnerReceipt::Groth16(inner) => inner.verifier_parameters = Digest::ZERO,
+            #[cfg(feature = "dev-mode")]
+            InnerReceipt::Fake(_) => {}
+        }
+        self
+    }
+
+    /// [Normalize](Proof::normalize) the proof and also sort its segments by index and renumber
+    /// them sequentially from 0. The claim is not affected.
+    pub fn canonicalize(self) -> Proof {
+        let mut proof = self.normalize();
+        if let InnerReceipt::Composite(inner) = &mut proof.inner {
+            inner.segments.sort_by_key(|s| s.index);
+            inner
+                .segments
+                .iter_mut()
+                .zip(0..)
+                .for_each(|(s, index)| s.index = index);
+        }
+        proof
+    }
+
+    /// Split a composite proof in two partial proofs: the first one contains the segments
+    /// `0..index` and the second one the segments `index..len`. The assumption receipts are
+    /// moved to the second proof, because the assumptions are committed by the last segment.
+    ///
+    /// Neither of the returned proofs verifies as a complete proof, but each segment can still
+    /// be checked: that is useful to bisect which segment of a long composite proof is broken.
+    /// Return [VerificationError::ReceiptFormatError] for succinct proofs or if `index` is out
+    /// of range.
+    pub fn split_composite_at(self, index: usize) -> Result<(Proof, Proof), VerificationError> {
+        let InnerReceipt::Composite(mut composite) = self.inner else {
+            return Err(VerificationError::ReceiptFormatError);
+        };
+        if index > composite.segments.len() {
+            return Err(VerificationError::ReceiptFormatError);
+        }
+        let tail = composite.segments.split_off(index);
+        let head = CompositeReceipt {
+            segments: composite.segments,
+            assumption_receipts: Vec::new(),
+            verifier_parameters: composite.verifier_parameters,
+        };
+        let tail = CompositeReceipt {
+            segments: tail,
+            ..composite
+        };
+        Ok((Proof::new(head.into()), Proof::new(tail.into())))
+    }
+}
+
+#[cfg(test)]
+impl PartialEq for Proof {
+    fn eq(&self, other: &Self) -> bool {
+        self.content_fingerprint() == other.content_fingerprint()
+    }
 }
 
 /// A record of the public commitments from a proven zkVM execution.
@@ -121,6 +670,137 @@ impl Journal {
     pub fn new(bytes: Vec<u8>) -> Self {
         Self { bytes }
     }
+
+    /// Construct an empty [Journal] with room for `cap` bytes.
+    pub fn with_capacity(cap: usize) -> Self {
+        Self::new(Vec::with_capacity(cap))
+    }
+
+    /// Construct a new [Journal], failing if `bytes` exceeds `max` bytes.
+    pub fn try_from_bytes(bytes: Vec<u8>, max: usize) -> Result<Journal, JournalTooLargeError> {
+        if bytes.len() > max {
+            return Err(JournalTooLargeError {
+                limit: max,
+                got: bytes.len(),
+            });
+        }
+        Ok(Self::new(bytes))
+    }
+
+    /// Compare two journals by their [digest](Digestible::digest) rather than by their raw bytes
+    /// as [PartialEq] does. A claim only commits to the journal digest, so this is the
+    /// comparison that matters when matching journals against claims. The two comparisons can
+    /// only disagree on a SHA-256 collision: a trailing padding byte changes the digest too.
+    pub fn digest_equal(&self, other: &Journal) -> bool {
+        self.digest() == other.digest()
+    }
+
+    /// Return `true` if the journal has no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Capacity of the underlying byte buffer.
+    pub fn capacity(&self) -> usize {
+        self.bytes.capacity()
+    }
+
+    /// Interpret the journal as a sequence of little-endian [u32] words, as they are written
+    /// by the guest. Return [JournalDecodeError::NotWordAligned] if the journal length is not a
+    /// multiple of 4.
+    pub fn as_risc0_words(&self) -> Result<Vec<u32>, JournalDecodeError> {
+        if !self.bytes.len().is_multiple_of(WORD_SIZE) {
+            return Err(JournalDecodeError::NotWordAligned {
+                len: self.bytes.len(),
+            });
+        }
+        Ok(self
+            .bytes
+            .chunks_exact(WORD_SIZE)
+            .map(|w| u32::from_le_bytes(w.try_into().unwrap()))
+            .collect())
+    }
+
+    /// Return the journal bytes with every 4-byte word swapped to big-endian order (e.g. to
+    /// be used in the Ethereum ABI). Trailing bytes that don't form a whole word are kept as is.
+    pub fn as_bytes_be(&self) -> Vec<u8> {
+        let words = self.bytes.chunks_exact(WORD_SIZE);
+        let remainder = words.remainder();
+        words
+            .flat_map(|w| w.iter().rev())
+            .chain(remainder)
+            .copied()
+            .collect()
+    }
+
+    /// Split the journal in two at the given byte `offset`. Return `None` if `offset` is
+    /// greater than the journal length.
+    pub fn split_at_offset(mut self, offset: usize) -> Option<(Journal, Journal)> {
+        if offset > self.bytes.len() {
+            return None;
+        }
+        let tail = self.bytes.split_off(offset);
+        Some((self, Journal::new(tail)))
+    }
+
+    /// Split the journal in journals of `chunk_size` bytes: the last one can be shorter. Return
+    /// an empty vector if `chunk_size` is 0.
+    pub fn chunks(&self, chunk_size: usize) -> Vec<Journal> {
+        if chunk_size == 0 {
+            return Vec::new();
+        }
+        self.bytes
+            .chunks(chunk_size)
+            .map(|c| Journal::new(c.to_vec()))
+            .collect()
+    }
+}
+
+impl Eq for Journal {}
+
+const WORD_SIZE: usize = core::mem::size_of::<u32>();
+
+/// Error returned when the [Journal] cannot be decoded in the requested format.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum JournalDecodeError {
+    /// The journal length is not a multiple of the word size.
+    NotWordAligned {
+        /// The journal length in bytes.
+        len: usize,
+    },
+}
+
+impl fmt::Display for JournalDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotWordAligned { len } => {
+                write!(f, "journal length {len} is not a multiple of the word size")
+            }
+        }
+    }
+}
+
+/// A reasonable limit for the journal size to use with
+/// [Verifier::set_max_journal_bytes](crate::Verifier::set_max_journal_bytes): 32 KB.
+pub const DEFAULT_MAX_JOURNAL_BYTES: usize = 32 * 1024;
+
+/// Error returned when a [Journal] exceeds the maximum allowed size.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct JournalTooLargeError {
+    /// The maximum number of bytes allowed.
+    pub limit: usize,
+    /// The journal size in bytes.
+    pub got: usize,
+}
+
+impl fmt::Display for JournalTooLargeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "journal size {} exceeds the limit of {} bytes",
+            self.got, self.limit
+        )
+    }
 }
 
 impl risc0_binfmt_v1::Digestible for Journal {
@@ -135,6 +815,135 @@ impl AsRef<[u8]> for Journal {
     }
 }
 
+/// Error returned when a [Proof], a [SuccinctReceipt] or a [Journal] cannot be read from or
+/// written to a CBOR file.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum ProofFileError {
+    /// The file cannot be opened, read or written.
+    Io(std::io::Error),
+    /// The file content is not a valid CBOR encoding of the requested type.
+    Cbor(alloc::string::String),
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ProofFileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "io error: {e}"),
+            Self::Cbor(e) => write!(f, "cbor error: {e}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ProofFileError {}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for ProofFileError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+#[cfg(feature = "std")]
+fn read_cbor_file<T: serde::de::DeserializeOwned>(
+    path: &std::path::Path,
+) -> Result<T, ProofFileError> {
+    use alloc::string::ToString;
+    let reader = std::io::BufReader::new(std::fs::File::open(path)?);
+    ciborium::from_reader(reader).map_err(|e| match e {
+        ciborium::de::Error::Io(e) => ProofFileError::Io(e),
+        e => ProofFileError::Cbor(e.to_string()),
+    })
+}
+
+#[cfg(feature = "std")]
+fn write_cbor_file<T: Serialize>(value: &T, path: &std::path::Path) -> Result<(), ProofFileError> {
+    use alloc::string::ToString;
+    use std::io::Write;
+    let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+    ciborium::into_writer(value, &mut writer).map_err(|e| match e {
+        ciborium::ser::Error::Io(e) => ProofFileError::Io(e),
+        e => ProofFileError::Cbor(e.to_string()),
+    })?;
+    writer.flush().map_err(Into::into)
+}
+
+#[cfg(feature = "std")]
+impl Proof {
+    /// Read a [Proof] from a CBOR encoded file, like the ones generated by the Risc0 prover.
+    pub fn from_cbor_file(path: &std::path::Path) -> Result<Proof, ProofFileError> {
+        read_cbor_file(path)
+    }
+
+    /// Write this [Proof] CBOR encoded in the given file.
+    pub fn to_cbor_file(&self, path: &std::path::Path) -> Result<(), ProofFileError> {
+        write_cbor_file(self, path)
+    }
+
+    /// Encode this [Proof] in CBOR, in a buffer preallocated with
+    /// [estimate_cbor_size](Proof::estimate_cbor_size) bytes.
+    pub fn to_cbor_preallocated(&self) -> Result<Vec<u8>, ProofFileError> {
+        use alloc::string::ToString;
+        let mut buffer = Vec::with_capacity(self.estimate_cbor_size());
+        ciborium::into_writer(self, &mut buffer).map_err(|e| match e {
+            ciborium::ser::Error::Io(e) => ProofFileError::Io(e),
+            e => ProofFileError::Cbor(e.to_string()),
+        })?;
+        Ok(buffer)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Claim> SuccinctReceipt<Claim>
+where
+    Claim:
+        risc0_binfmt_v1::Digestible + fmt::Debug + Clone + Serialize + serde::de::DeserializeOwned,
+{
+    /// Read a [SuccinctReceipt] from a CBOR encoded file.
+    pub fn from_cbor_file(path: &std::path::Path) -> Result<Self, ProofFileError> {
+        read_cbor_file(path)
+    }
+
+    /// Write this [SuccinctReceipt] CBOR encoded in the given file.
+    pub fn to_cbor_file(&self, path: &std::path::Path) -> Result<(), ProofFileError> {
+        write_cbor_file(self, path)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Journal {
+    /// Read a [Journal] from a CBOR encoded file.
+    pub fn from_cbor_file(path: &std::path::Path) -> Result<Self, ProofFileError> {
+        read_cbor_file(path)
+    }
+
+    /// Write this [Journal] CBOR encoded in the given file.
+    pub fn to_cbor_file(&self, path: &std::path::Path) -> Result<(), ProofFileError> {
+        write_cbor_file(self, path)
+    }
+}
+
+/// Check that the claim of a verified receipt is the expected one, otherwise return
+/// [VerificationError::ClaimDigestMismatch].
+fn check_claim(
+    expected: &ReceiptClaim,
+    received: &MaybePruned<ReceiptClaim>,
+) -> Result<(), VerificationError> {
+    if expected.digest() != received.digest() {
+        debug!(
+            "receipt claim does not match expected claim:\nreceipt: {}\nexpected: {}",
+            received, expected
+        );
+        return Err(VerificationError::ClaimDigestMismatch {
+            expected: expected.digest(),
+            received: received.digest(),
+        });
+    }
+    Ok(())
+}
+
 /// A lower level receipt, containing the cryptographic seal (i.e. zero-knowledge proof) and
 /// verification logic for a specific proof system and circuit. All inner receipt types are
 /// zero-knowledge proofs of execution for a RISC-V zkVM.
@@ -145,18 +954,104 @@ pub enum InnerReceipt {
     Composite(CompositeReceipt),
     /// A [SuccinctReceipt], proving arbitrarily long zkVM computations with a single STARK.
     Succinct(SuccinctReceipt<ReceiptClaim>),
+    /// A [Groth16Receipt], proving arbitrarily long zkVM computations with a single Groth16 SNARK.
+    Groth16(Groth16Receipt<ReceiptClaim>),
+    /// A [FakeReceipt](fake::FakeReceipt), with no cryptographic integrity, generated by the
+    /// provers in dev mode. Available only with the `dev-mode` feature.
+    #[cfg(feature = "dev-mode")]
+    Fake(fake::FakeReceipt<ReceiptClaim>),
+}
+
+/// The kind of an [InnerReceipt].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ReceiptKind {
+    /// A [CompositeReceipt].
+    Composite,
+    /// A [SuccinctReceipt].
+    Succinct,
+    /// A [Groth16Receipt].
+    Groth16,
+    /// A [FakeReceipt](fake::FakeReceipt).
+    #[cfg(feature = "dev-mode")]
+    Fake,
 }
 
 impl InnerReceipt {
+    /// The kind of this receipt.
+    pub fn kind(&self) -> ReceiptKind {
+        match self {
+            Self::Composite(_) => ReceiptKind::Composite,
+            Self::Succinct(_) => ReceiptKind::Succinct,
+            Self::Groth16(_) => ReceiptKind::Groth16,
+            #[cfg(feature = "dev-mode")]
+            Self::Fake(_) => ReceiptKind::Fake,
+        }
+    }
+
     /// Verify the integrity of this receipt, ensuring the claim is attested to by the seal.
     pub fn verify_integrity_with_context(
         &self,
         ctx: &impl VerifierContext,
     ) -> Result<(), VerificationError> {
-        log::debug!("InnerReceipt::verify_integrity_with_context");
+        debug!("InnerReceipt::verify_integrity_with_context");
         match self {
             Self::Composite(inner) => inner.verify_integrity_with_context(ctx),
             Self::Succinct(inner) => inner.verify_integrity_with_context(ctx),
+            Self::Groth16(inner) => inner.verify_integrity_with_context(ctx),
+            #[cfg(feature = "dev-mode")]
+            Self::Fake(inner) => inner.verify_integrity_with_context(ctx),
+        }
+    }
+
+    /// Return the seals of this receipt: one slice for every segment of a composite receipt or
+    /// a single slice for a succinct or Groth16 one. The seal of a Groth16 receipt is made of
+    /// bytes, not words: they are packed in little-endian words, padding the last one with
+    /// zeros. Use [InnerReceipt::concatenated_seal_bytes] to get the exact bytes.
+    pub fn seal_word_slices(&self) -> Vec<Cow<'_, [u32]>> {
+        match self {
+            Self::Composite(inner) => inner
+                .segments
+                .iter()
+                .map(|s| Cow::Borrowed(s.seal.as_slice()))
+                .collect(),
+            Self::Succinct(inner) => Vec::from([Cow::Borrowed(inner.seal.as_slice())]),
+            Self::Groth16(inner) => Vec::from([Cow::Owned(
+                inner
+                    .seal
+                    .chunks(WORD_SIZE)
+                    .map(|w| {
+                        let mut word = [0; WORD_SIZE];
+                        word[..w.len()].copy_from_slice(w);
+                        u32::from_le_bytes(word)
+                    })
+                    .collect(),
+            )]),
+            #[cfg(feature = "dev-mode")]
+            Self::Fake(_) => Vec::new(),
+        }
+    }
+
+    /// Return the bytes of all the seals of this receipt concatenated in order, e.g. to ABI
+    /// encode them.
+    pub fn concatenated_seal_bytes(&self) -> Vec<u8> {
+        if let Self::Groth16(inner) = self {
+            return inner.seal.clone();
+        }
+        self.seal_word_slices()
+            .iter()
+            .flat_map(|words| bytemuck::cast_slice::<u32, u8>(words))
+            .copied()
+            .collect()
+    }
+
+    /// Total number of bytes used by the seals of this receipt.
+    pub fn seal_size(&self) -> usize {
+        match self {
+            Self::Composite(inner) => inner.seal_size(),
+            Self::Succinct(inner) => inner.seal_size(),
+            Self::Groth16(inner) => inner.seal_size(),
+            #[cfg(feature = "dev-mode")]
+            Self::Fake(inner) => inner.seal_size(),
         }
     }
 
@@ -178,11 +1073,23 @@ impl InnerReceipt {
         }
     }
 
+    /// Returns the [`InnerReceipt::Groth16`] arm.
+    pub fn groth16(&self) -> Result<&Groth16Receipt<ReceiptClaim>, VerificationError> {
+        if let Self::Groth16(x) = self {
+            Ok(x)
+        } else {
+            Err(VerificationError::ReceiptFormatError)
+        }
+    }
+
     /// Extract the [`ReceiptClaim`] from this receipt.
     pub fn claim(&self) -> Result<MaybePruned<ReceiptClaim>, VerificationError> {
         match self {
             Self::Composite(ref inner) => Ok(inner.claim()?.into()),
             Self::Succinct(ref inner) => Ok(inner.claim.clone()),
+            Self::Groth16(ref inner) => Ok(inner.claim.clone()),
+            #[cfg(feature = "dev-mode")]
+            Self::Fake(ref inner) => Ok(inner.claim.clone()),
         }
     }
 
@@ -191,6 +1098,9 @@ impl InnerReceipt {
         match self {
             Self::Composite(ref inner) => inner.verifier_parameters,
             Self::Succinct(ref inner) => inner.verifier_parameters,
+            Self::Groth16(ref inner) => inner.verifier_parameters,
+            #[cfg(feature = "dev-mode")]
+            Self::Fake(_) => Digest::ZERO,
         }
     }
 }
@@ -205,19 +1115,64 @@ pub enum InnerAssumptionReceipt {
 
     /// A [SuccinctReceipt], proving arbitrarily the claim with a single STARK.
     Succinct(SuccinctReceipt<Unknown>),
+
+    /// A [Groth16Receipt], proving arbitrarily the claim with a single Groth16 SNARK.
+    Groth16(Groth16Receipt<Unknown>),
+
+    /// A [FakeReceipt](fake::FakeReceipt), with no cryptographic integrity, generated by the
+    /// provers in dev mode. Available only with the `dev-mode` feature, and never accepted as
+    /// an assumption receipt.
+    #[cfg(feature = "dev-mode")]
+    Fake(fake::FakeReceipt<Unknown>),
 }
 
 impl InnerAssumptionReceipt {
     /// Verify the integrity of this receipt, ensuring the claim is attested to by the seal.
     pub fn verify_integrity_with_context(
         &self,
-        ctx: &impl crate::context::VerifierContext,
+        ctx: &impl VerifierContext,
     ) -> Result<(), VerificationError> {
-        log::debug!("InnerAssumptionReceipt::verify_integrity_with_context");
+        debug!("InnerAssumptionReceipt::verify_integrity_with_context");
         match self {
             Self::Composite(inner) => inner.verify_integrity_with_context(ctx),
             Self::Succinct(inner) => inner.verify_integrity_with_context(ctx),
+            Self::Groth16(inner) => inner.verify_integrity_with_context(ctx),
+            // A fake receipt never resolves an assumption.
+            #[cfg(feature = "dev-mode")]
+            Self::Fake(_) => Err(VerificationError::ReceiptFormatError),
+        }
+    }
+
+    /// Verify this receipt as the one resolving the given assumption: the receipt is verified
+    /// with the context returned by [VerifierContext::assumption_context] (or `ctx` itself for
+    /// a self-composed assumption) and its claim must be the assumption claim.
+    pub fn verify_with_assumption(
+        &self,
+        ctx: &impl VerifierContext,
+        assumption: &Assumption,
+    ) -> Result<(), VerificationError> {
+        debug!("verifying assumption: {assumption:?}");
+        let assumption_ctx = ctx
+            .assumption_context(assumption)
+            .unwrap_or_else(|| ctx.boxed_clone());
+        self.verify_integrity_with_context(&assumption_ctx)?;
+        self.verify_claim_matches(assumption.claim)
+    }
+
+    /// Check that the claim digest of this receipt is the expected one, otherwise return
+    /// [VerificationError::ClaimDigestMismatch].
+    pub fn verify_claim_matches(&self, expected_claim: Digest) -> Result<(), VerificationError> {
+        let claim = self.claim_digest()?;
+        if claim != expected_claim {
+            debug!(
+                "receipt claim digest mismatch: expected: {expected_claim}, receipt claim digest: {claim}"
+            );
+            return Err(VerificationError::ClaimDigestMismatch {
+                expected: expected_claim,
+                received: claim,
+            });
         }
+        Ok(())
     }
 
     /// Returns the [InnerAssumptionReceipt::Composite] arm.
@@ -245,6 +1200,9 @@ impl InnerAssumptionReceipt {
         match self {
             Self::Composite(ref inner) => Ok(inner.claim()?.digest()),
             Self::Succinct(ref inner) => Ok(inner.claim.digest()),
+            Self::Groth16(ref inner) => Ok(inner.claim.digest()),
+            #[cfg(feature = "dev-mode")]
+            Self::Fake(ref inner) => Ok(inner.claim.digest()),
         }
     }
 
@@ -253,6 +1211,9 @@ impl InnerAssumptionReceipt {
         match self {
             Self::Composite(ref inner) => inner.verifier_parameters,
             Self::Succinct(ref inner) => inner.verifier_parameters,
+            Self::Groth16(ref inner) => inner.verifier_parameters,
+            #[cfg(feature = "dev-mode")]
+            Self::Fake(_) => Digest::ZERO,
         }
     }
 }
@@ -262,6 +1223,9 @@ impl From<InnerReceipt> for InnerAssumptionReceipt {
         match value {
             InnerReceipt::Composite(x) => InnerAssumptionReceipt::Composite(x),
             InnerReceipt::Succinct(x) => InnerAssumptionReceipt::Succinct(x.into_unknown()),
+            InnerReceipt::Groth16(x) => InnerAssumptionReceipt::Groth16(x.into_unknown()),
+            #[cfg(feature = "dev-mode")]
+            InnerReceipt::Fake(x) => InnerAssumptionReceipt::Fake(x.into_unknown()),
         }
     }
 }
@@ -271,3 +1235,608 @@ impl From<composite::CompositeReceipt> for InnerReceipt {
         Self::Composite(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn journal_as_risc0_words() {
+        let journal = Journal::new(vec![0x78, 0x56, 0x34, 0x12, 0x01, 0x00, 0x00, 0x00]);
+
+        assert_eq!(Ok(vec![0x12345678, 1]), journal.as_risc0_words());
+    }
+
+    #[test]
+    fn journal_as_risc0_words_not_aligned() {
+        let journal = Journal::new(vec![0x78, 0x56, 0x34, 0x12, 0x01]);
+
+        assert_eq!(
+            Err(JournalDecodeError::NotWordAligned { len: 5 }),
+            journal.as_risc0_words()
+        );
+    }
+
+    #[test]
+    fn journal_as_bytes_be() {
+        let journal = Journal::new(0x12345678_u32.to_le_bytes().to_vec());
+
+        assert_eq!(0x12345678_u32.to_be_bytes().to_vec(), journal.as_bytes_be());
+    }
+
+    #[test]
+    fn journal_as_bytes_be_keeps_trailing_bytes() {
+        let journal = Journal::new(vec![1, 2, 3, 4, 5, 6]);
+
+        assert_eq!(vec![4, 3, 2, 1, 5, 6], journal.as_bytes_be());
+    }
+
+    #[test]
+    fn journal_split_at_offset() {
+        let journal = Journal::new(vec![1, 2, 3, 4, 5]);
+
+        assert_eq!(
+            Some((Journal::new(vec![1, 2]), Journal::new(vec![3, 4, 5]))),
+            journal.clone().split_at_offset(2)
+        );
+        assert_eq!(
+            Some((journal.clone(), Journal::default())),
+            journal.clone().split_at_offset(5)
+        );
+        assert_eq!(None, journal.split_at_offset(6));
+    }
+
+    #[test]
+    fn journal_digest_equal() {
+        let journal = Journal::new(vec![1, 2, 3]);
+        let mut same_content = Journal::new(Vec::with_capacity(64));
+        same_content.bytes.extend([1, 2, 3]);
+        let padded = Journal::new(vec![1, 2, 3, 0]);
+
+        assert_ne!(journal.capacity(), same_content.capacity());
+        assert_eq!(journal, same_content);
+        assert!(journal.digest_equal(&same_content));
+        // A trailing zero byte is part of the journal: neither comparison ignores it.
+        assert_ne!(journal, padded);
+        assert!(!journal.digest_equal(&padded));
+    }
+
+    #[test]
+    fn journal_is_empty() {
+        assert!(Journal::default().is_empty());
+        assert!(!Journal::new(vec![0]).is_empty());
+    }
+
+    #[test]
+    fn journal_chunks_round_trip() {
+        let journal = Journal::new((0..10).collect());
+
+        let chunks = journal.chunks(4);
+
+        assert_eq!(3, chunks.len());
+        assert_eq!(2, chunks[2].bytes.len());
+        assert_eq!(
+            journal.bytes,
+            chunks.into_iter().flat_map(|c| c.bytes).collect::<Vec<_>>()
+        );
+        assert!(journal.chunks(0).is_empty());
+    }
+
+    #[derive(Deserialize)]
+    struct Case {
+        receipt_path: std::path::PathBuf,
+        journal: Journal,
+        vk: [u32; 8],
+    }
+
+    fn read_case(path: &str) -> (Proof, Journal, Digest) {
+        let case: Case = serde_json::from_reader(std::fs::File::open(path).unwrap()).unwrap();
+        let proof = ciborium::from_reader(std::fs::File::open(case.receipt_path).unwrap()).unwrap();
+        (proof, case.journal, case.vk.into())
+    }
+
+    #[rstest::rstest]
+    #[case::v1_2_composite(
+        crate::context::v1::V1::v1_2(),
+        "./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json"
+    )]
+    #[case::v1_2_succinct(
+        crate::context::v1::V1::v1_2(),
+        "./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json"
+    )]
+    #[case::v3_0_succinct(
+        crate::context::v3::V3::v3_0(),
+        "./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json"
+    )]
+    fn verify_with_journal_digest_matches_verify(
+        #[case] ctx: impl crate::context::VerifierContext,
+        #[case] path: &str,
+        #[values(false, true)] tampered: bool,
+    ) {
+        let (proof, mut journal, image_id) = read_case(path);
+        if tampered {
+            journal.bytes.push(0);
+        }
+
+        let expected = proof.verify(&ctx, image_id, journal.digest());
+
+        assert_eq!(tampered, expected.is_err());
+        assert_eq!(
+            expected,
+            proof.verify_with_journal_digest(&ctx, image_id, journal.digest())
+        );
+    }
+
+    #[rstest::rstest]
+    #[case::composite("./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json")]
+    #[case::succinct("./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json")]
+    fn content_fingerprint_is_deterministic(#[case] path: &str) {
+        let (proof, _, _) = read_case(path);
+        let (other, _, _) = read_case(path);
+
+        assert_eq!(proof.content_fingerprint(), other.content_fingerprint());
+        assert_eq!(proof, other);
+        let (claim, fingerprint) = proof.claim_and_fingerprint();
+        assert_eq!(proof.claim().unwrap().digest(), claim.unwrap().digest());
+        assert_eq!(proof.content_fingerprint(), fingerprint);
+    }
+
+    #[test]
+    fn content_fingerprint_changes_when_a_seal_byte_is_mutated() {
+        let (proof, _, _) = read_case("./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json");
+        let mut mutated = proof.clone();
+        let InnerReceipt::Composite(composite) = &mut mutated.inner else {
+            panic!("expected composite receipt");
+        };
+        composite.segments[2].seal[100] ^= 1;
+
+        assert_ne!(proof.content_fingerprint(), mutated.content_fingerprint());
+        assert_ne!(proof, mutated);
+    }
+
+    fn to_cbor(proof: &Proof) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        ciborium::into_writer(proof, &mut buffer).unwrap();
+        buffer
+    }
+
+    #[rstest::rstest]
+    fn estimate_cbor_size_is_a_close_upper_bound(
+        #[files("./resources/receipts/*/*.bin")] path: std::path::PathBuf,
+    ) {
+        let proof: Proof = ciborium::from_reader(std::fs::File::open(&path).unwrap()).unwrap();
+
+        let actual = to_cbor(&proof).len();
+        let estimate = proof.estimate_cbor_size();
+        assert!(estimate >= actual, "{estimate} < {actual}");
+        assert!(estimate <= 2 * actual, "{estimate} > 2 * {actual}");
+    }
+
+    fn verifier_parameters(proof: &Proof) -> Vec<Digest> {
+        match &proof.inner {
+            InnerReceipt::Composite(inner) => core::iter::once(inner.verifier_parameters)
+                .chain(inner.segments.iter().map(|s| s.verifier_parameters))
+                .collect(),
+            InnerReceipt::Succinct(inner) => [inner.verifier_parameters].into(),
+            InnerReceipt::Groth16(inner) => [inner.verifier_parameters].into(),
+            #[cfg(feature = "dev-mode")]
+            InnerReceipt::Fake(_) => Vec::new(),
+        }
+    }
+
+    #[rstest::rstest]
+    #[case::v1_0("./resources/cases/prover_1.0.3/vm_1.0.5/poseidon2_16.json")]
+    #[case::v1_1("./resources/cases/prover_1.1.3/vm_1.1.3/poseidon2_16.json")]
+    #[case::v1_2_succinct("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")]
+    fn normalize_only_clears_verifier_parameters(#[case] path: &str) {
+        let (proof, _, _) = read_case(path);
+
+        let normalized = proof.clone().normalize();
+
+        assert!(verifier_parameters(&normalized)
+            .iter()
+            .all(|d| *d == Digest::ZERO));
+        assert_eq!(
+            proof.claim().unwrap().digest(),
+            normalized.claim().unwrap().digest()
+        );
+        assert_eq!(
+            proof.inner.concatenated_seal_bytes(),
+            normalized.inner.concatenated_seal_bytes()
+        );
+    }
+
+    #[test]
+    fn normalized_proofs_from_different_verifier_versions_are_byte_equal() {
+        let (v1_1, _, _) = read_case("./resources/cases/prover_1.1.3/vm_1.1.3/poseidon2_16.json");
+        let (v1_0, _, _) = read_case("./resources/cases/prover_1.0.3/vm_1.0.5/poseidon2_16.json");
+        // The same proof as it would be labeled by a v1.0 prover.
+        let mut relabeled = v1_1.clone();
+        let InnerReceipt::Composite(inner) = &mut relabeled.inner else {
+            panic!("expected composite receipt");
+        };
+        inner.verifier_parameters = v1_0.inner.composite().unwrap().verifier_parameters;
+        for s in inner.segments.iter_mut() {
+            s.verifier_parameters = v1_0.inner.composite().unwrap().segments[0].verifier_parameters;
+        }
+        assert_ne!(to_cbor(&v1_1), to_cbor(&relabeled));
+
+        assert_eq!(to_cbor(&v1_1.normalize()), to_cbor(&relabeled.normalize()));
+    }
+
+    #[test]
+    fn canonicalize_renumbers_segments() {
+        let (proof, _, _) = read_case("./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json");
+        let mut shuffled = proof.clone();
+        let InnerReceipt::Composite(inner) = &mut shuffled.inner else {
+            panic!("expected composite receipt");
+        };
+        inner.segments.reverse();
+        inner.segments.iter_mut().for_each(|s| s.index += 10);
+
+        let canonical = shuffled.canonicalize();
+
+        assert_eq!(to_cbor(&proof.clone().normalize()), to_cbor(&canonical));
+        assert_eq!(
+            proof.claim().unwrap().digest(),
+            canonical.claim().unwrap().digest()
+        );
+    }
+
+    #[test]
+    fn composite_constructors() {
+        let (proof, _, _) = read_case("./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json");
+        let composite = proof.clone().try_into_composite().unwrap();
+        let segment = composite.segments[0].clone();
+
+        let from_composite = Proof::from_composite(composite);
+        let from_segment = segment.into_proof();
+
+        assert!(from_composite.is_composite() && !from_composite.is_succinct());
+        assert!(from_segment.is_composite() && !from_segment.is_succinct());
+        assert_eq!(proof, from_composite);
+        assert_eq!(
+            Err(VerificationError::ReceiptFormatError),
+            proof.try_into_succinct().map(|_| ())
+        );
+    }
+
+    #[test]
+    fn succinct_constructors() {
+        let (proof, _, _) = read_case("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json");
+        let succinct = proof.clone().try_into_succinct().unwrap();
+
+        let from_succinct = Proof::from_succinct(succinct);
+
+        assert!(from_succinct.is_succinct() && !from_succinct.is_composite());
+        assert_eq!(proof, from_succinct);
+        assert_eq!(
+            Err(VerificationError::ReceiptFormatError),
+            proof.try_into_composite().map(|_| ())
+        );
+    }
+
+    #[rstest::rstest]
+    #[case::composite("./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json")]
+    #[case::succinct("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")]
+    #[case::v3_succinct("./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json")]
+    fn inspect_claim(#[case] path: &str) {
+        let (proof, journal, image_id) = read_case(path);
+
+        assert_eq!(
+            proof.claim().unwrap().digest(),
+            proof.inspect_claim().unwrap().digest()
+        );
+        assert_eq!(Ok(Some(journal.digest())), proof.inspect_journal_digest());
+        assert_eq!(Ok(image_id), proof.inspect_pre_state());
+    }
+
+    #[test]
+    fn inspect_journal_digest_of_a_pruned_output() {
+        let (mut proof, _, _) =
+            read_case("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json");
+        let InnerReceipt::Succinct(inner) = &mut proof.inner else {
+            panic!("expected succinct receipt");
+        };
+        inner.claim = inner.claim.clone().value().unwrap().prune_output().into();
+
+        assert_eq!(Ok(None), proof.inspect_journal_digest());
+    }
+
+    fn read_proof(path: &str) -> Proof {
+        ciborium::from_reader(std::fs::File::open(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn pretty_print_composite() {
+        let proof = read_proof("./resources/receipts/1.2.0-1.2.0/sha_22.bin");
+        let params = std::format!("{}", ShortDigest(proof.inner.verifier_parameters()));
+
+        let out = proof.to_debug_string();
+
+        assert!(out.contains("Composite"));
+        assert_eq!(
+            3,
+            out.lines()
+                .filter(|l| l.trim_start().starts_with(char::is_numeric))
+                .count()
+        );
+        assert!(out.contains(params.trim_end_matches("...")));
+    }
+
+    #[test]
+    fn pretty_print_succinct() {
+        let proof = read_proof("./resources/receipts/1.2.0-1.2.0/succinct_16.bin");
+
+        let out = proof.to_debug_string();
+
+        assert!(out.starts_with("Succinct"));
+        assert!(out.contains(&std::format!("{} seal words", proof.seal_word_count())));
+    }
+
+    #[test]
+    fn pretty_print_is_at_most_30_lines() {
+        let mut proof = read_proof("./resources/receipts/1.2.0-1.2.0/sha_22.bin");
+        let InnerReceipt::Composite(inner) = &mut proof.inner else {
+            panic!("not a composite receipt")
+        };
+        let segment = inner.segments[0].clone();
+        inner.segments.resize(100, segment);
+
+        let out = proof.to_debug_string();
+
+        assert!(out.lines().count() <= 30);
+        assert!(out.contains("... 76 more segments"));
+    }
+
+    fn assumption_receipt() -> InnerAssumptionReceipt {
+        read_proof("./resources/receipts/1.2.0-1.2.0/succinct_16.bin")
+            .inner
+            .into()
+    }
+
+    #[test]
+    fn verify_with_self_composed_assumption() {
+        let receipt = assumption_receipt();
+        let assumption = Assumption::self_composed(receipt.claim_digest().unwrap());
+
+        assert_eq!(
+            Ok(()),
+            receipt.verify_with_assumption(&crate::context::v1::V1::v1_2(), &assumption)
+        );
+    }
+
+    #[test]
+    fn verify_with_assumption_routed_to_its_control_root() {
+        let receipt = assumption_receipt();
+        let assumption = Assumption {
+            claim: receipt.claim_digest().unwrap(),
+            control_root: succinct::SuccinctReceiptVerifierParameters::v1_2().control_root,
+        };
+        // The assumption is verified with its own control root, not with the context parameters.
+        let ctx = crate::context::v1::V1::v1_2().with_succinct_verifier_parameters(
+            succinct::SuccinctReceiptVerifierParameters {
+                control_root: Digest::from([1_u32; 8]),
+                ..succinct::SuccinctReceiptVerifierParameters::v1_2()
+            },
+        );
+
+        assert_eq!(Ok(()), receipt.verify_with_assumption(&ctx, &assumption));
+        assert!(matches!(
+            receipt.verify_with_assumption(
+                &crate::context::v1::V1::v1_2(),
+                &Assumption {
+                    control_root: Digest::from([1_u32; 8]),
+                    ..assumption
+                }
+            ),
+            Err(VerificationError::ControlVerificationError { .. })
+        ));
+    }
+
+    #[test]
+    fn verify_claim_matches() {
+        let receipt = assumption_receipt();
+        let claim = receipt.claim_digest().unwrap();
+
+        assert_eq!(Ok(()), receipt.verify_claim_matches(claim));
+        assert_eq!(
+            Err(VerificationError::ClaimDigestMismatch {
+                expected: Digest::ZERO,
+                received: claim,
+            }),
+            receipt.verify_claim_matches(Digest::ZERO)
+        );
+        assert!(matches!(
+            receipt.verify_with_assumption(
+                &crate::context::v1::V1::v1_2(),
+                &Assumption::self_composed(Digest::ZERO)
+            ),
+            Err(VerificationError::ClaimDigestMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn claim_seal_consistency_catches_a_tampered_segment_claim() {
+        let ctx = crate::context::v1::V1::v1_2();
+        let mut proof = read_proof("./resources/receipts/1.2.0-1.2.0/sha_22.bin");
+        assert_eq!(Ok(()), proof.check_claim_seal_consistency(&ctx));
+
+        let InnerReceipt::Composite(inner) = &mut proof.inner else {
+            panic!("not a composite receipt")
+        };
+        inner.segments[1].claim.exit_code = risc0_binfmt_v1::ExitCode::Paused(0);
+
+        assert!(matches!(
+            proof.check_claim_seal_consistency(&ctx),
+            Err(VerificationError::ClaimDigestMismatch { .. })
+        ));
+        assert!(proof.inner.verify_integrity_with_context(&ctx).is_err());
+    }
+
+    #[test]
+    fn claim_seal_consistency_catches_a_tampered_succinct_claim() {
+        let ctx = crate::context::v1::V1::v1_2();
+        let mut proof = read_proof("./resources/receipts/1.2.0-1.2.0/succinct_16.bin");
+        assert_eq!(Ok(()), proof.check_claim_seal_consistency(&ctx));
+
+        let InnerReceipt::Succinct(inner) = &mut proof.inner else {
+            panic!("not a succinct receipt")
+        };
+        let mut claim = inner.claim.as_value().unwrap().clone();
+        claim.exit_code = risc0_binfmt_v1::ExitCode::Paused(0);
+        inner.claim = MaybePruned::Value(claim);
+
+        assert!(matches!(
+            proof.check_claim_seal_consistency(&ctx),
+            Err(VerificationError::ClaimDigestMismatch { .. })
+        ));
+        assert!(proof.inner.verify_integrity_with_context(&ctx).is_err());
+    }
+
+    /// CBOR encoding of a [Proof] with the fake inner receipt of a risc0 prover in dev mode,
+    /// built without the `dev-mode` types.
+    fn fake_proof_cbor(claim: ReceiptClaim) -> Vec<u8> {
+        #[derive(Serialize)]
+        struct FakeProof {
+            inner: FakeInner,
+        }
+        #[derive(Serialize)]
+        enum FakeInner {
+            Fake { claim: MaybePruned<ReceiptClaim> },
+        }
+
+        let mut buffer = Vec::new();
+        ciborium::into_writer(
+            &FakeProof {
+                inner: FakeInner::Fake {
+                    claim: claim.into(),
+                },
+            },
+            &mut buffer,
+        )
+        .unwrap();
+        buffer
+    }
+
+    #[test]
+    #[cfg(not(feature = "dev-mode"))]
+    fn fake_receipt_is_rejected_without_dev_mode() {
+        let claim = ReceiptClaim::ok(Digest::ZERO, MaybePruned::Pruned(Digest::ZERO));
+
+        assert!(matches!(
+            Proof::from_cbor(&fake_proof_cbor(claim), usize::MAX),
+            Err(VerificationError::ReceiptFormatError)
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "dev-mode")]
+    fn fake_receipt_is_accepted_with_dev_mode() {
+        use crate::Verifier;
+
+        let ctx = crate::context::v3::V3::v3_0().with_fake_receipts_allowed();
+        let image_id = Digest::from([1; 8]);
+        let journal = Journal::new(vec![1, 2, 3, 4]);
+        let claim = ReceiptClaim::ok(image_id, MaybePruned::Pruned(journal.digest()));
+        let encoded = fake_proof_cbor(claim.clone());
+
+        let proof = Proof::from_cbor(&encoded, usize::MAX).unwrap();
+
+        assert_eq!(
+            to_cbor(&Proof::new(InnerReceipt::Fake(fake::FakeReceipt::new(
+                claim
+            )))),
+            encoded
+        );
+        assert_eq!(Ok(()), proof.verify(&ctx, image_id, journal.digest()));
+        assert_eq!(
+            Err(VerificationError::ReceiptFormatError),
+            proof.verify(&crate::context::v3::V3::v3_0(), image_id, journal.digest())
+        );
+        assert!(matches!(
+            proof.verify(&ctx, image_id, Journal::new(vec![5]).digest()),
+            Err(VerificationError::ClaimDigestMismatch { .. })
+        ));
+        assert!(matches!(
+            proof.verify(&ctx, Digest::ZERO, journal.digest()),
+            Err(VerificationError::ClaimDigestMismatch { .. })
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "dev-mode")]
+    fn fake_receipts_of_different_claims_have_different_fingerprints() {
+        let fake = |image_id: [u32; 8]| {
+            Proof::new(InnerReceipt::Fake(fake::FakeReceipt::new(
+                ReceiptClaim::ok(Digest::from(image_id), MaybePruned::Pruned(Digest::ZERO)),
+            )))
+        };
+
+        assert_eq!(
+            fake([1; 8]).content_fingerprint(),
+            fake([1; 8]).content_fingerprint()
+        );
+        assert_ne!(
+            fake([1; 8]).content_fingerprint(),
+            fake([2; 8]).content_fingerprint()
+        );
+    }
+
+    #[rstest::rstest]
+    #[case::halted_with_user_code(ExitCode::Halted(1), ExitCode::Halted(2))]
+    #[case::paused(ExitCode::Paused(0), ExitCode::Halted(0))]
+    #[case::paused_with_user_code(ExitCode::Paused(3), ExitCode::Paused(2))]
+    #[cfg(feature = "dev-mode")]
+    fn verify_with_exit_code(#[case] exit_code: ExitCode, #[case] other: ExitCode) {
+        use crate::Verifier;
+
+        let ctx = crate::context::v3::V3::v3_0().with_fake_receipts_allowed();
+        let image_id = Digest::from([1; 8]);
+        let journal = Journal::new(vec![1, 2, 3, 4]);
+        let mut claim = ReceiptClaim::ok(image_id, MaybePruned::Pruned(journal.digest()));
+        claim.exit_code = exit_code;
+        if let ExitCode::Paused(_) = exit_code {
+            // A paused execution ends in the state to resume from.
+            claim.post = MaybePruned::Value(risc0_binfmt_v1::SystemState {
+                pc: 0x1000,
+                merkle_root: Digest::from([2; 8]),
+            });
+        }
+        let proof = Proof::new(InnerReceipt::Fake(fake::FakeReceipt::new(claim)));
+
+        assert_eq!(
+            Ok(()),
+            proof.verify_with_exit_code(&ctx, image_id, journal.digest(), exit_code)
+        );
+        assert!(matches!(
+            proof.verify_with_exit_code(&ctx, image_id, journal.digest(), other),
+            Err(VerificationError::ClaimDigestMismatch { .. })
+        ));
+        assert!(matches!(
+            proof.verify(&ctx, image_id, journal.digest()),
+            Err(VerificationError::ClaimDigestMismatch { .. })
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "dev-mode")]
+    fn fake_assumption_receipt_is_rejected() {
+        use crate::Verifier;
+
+        let ctx = crate::context::v3::V3::v3_0().with_fake_receipts_allowed();
+        let claim = ReceiptClaim::ok(Digest::from([1; 8]), MaybePruned::Pruned(Digest::ZERO));
+        let assumption = Assumption {
+            claim: claim.digest(),
+            control_root: Digest::ZERO,
+        };
+        let receipt =
+            InnerAssumptionReceipt::from(InnerReceipt::Fake(fake::FakeReceipt::new(claim)));
+
+        assert_eq!(
+            Err(VerificationError::ReceiptFormatError),
+            receipt.verify_with_assumption(&ctx, &assumption)
+        );
+    }
+}
@@ -16,10 +16,11 @@
 // limitations under the License.
 //
 
-use alloc::{vec, vec::Vec};
-use risc0_binfmt_v1::{Digestible, ExitCode};
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+use core::fmt;
+use risc0_binfmt_v1::{tagged_iter, tagged_struct, Digestible, ExitCode};
 use risc0_zkp_v1::{
-    core::{digest::Digest, hash::sha},
+    core::{digest::Digest, hash::sha::Sha256},
     verify::VerificationError,
 };
 
@@ -28,8 +29,9 @@ use serde::{Deserialize, Serialize};
 use super::InnerAssumptionReceipt;
 use crate::{
     context::VerifierContext,
-    receipt_claim::{Assumption, Output, PrunedValueError, ReceiptClaim},
-    segment::SegmentReceipt,
+    receipt_claim::{Assumption, MaybePruned, Output, PrunedValueError, ReceiptClaim, ShortDigest},
+    segment::{PrunedSegmentReceipt, SegmentReceipt},
+    Verifier,
 };
 
 /// A receipt composed of one or more [SegmentReceipt] structs proving a single execution with
@@ -56,13 +58,163 @@ pub struct CompositeReceipt {
 }
 
 impl CompositeReceipt {
+    /// Wrap a single segment in a [CompositeReceipt] without assumption receipts and with the
+    /// `verifier_parameters` of the segment.
+    pub fn from_single_segment(segment: SegmentReceipt) -> Self {
+        Self {
+            verifier_parameters: segment.verifier_parameters,
+            segments: vec![segment],
+            assumption_receipts: Vec::new(),
+        }
+    }
+
+    /// Build an empty [CompositeReceipt] with room for `n` segments, to be filled with
+    /// [CompositeReceipt::push_segment] as the segments are produced.
+    pub fn with_capacity(n: usize) -> Self {
+        Self {
+            segments: Vec::with_capacity(n),
+            assumption_receipts: Vec::new(),
+            verifier_parameters: Digest::ZERO,
+        }
+    }
+
+    /// Append a segment receipt. The first segment pushed in an empty receipt also sets the
+    /// receipt `verifier_parameters`, as [CompositeReceipt::from_single_segment] does.
+    pub fn push_segment(&mut self, s: SegmentReceipt) {
+        if self.segments.is_empty() {
+            self.verifier_parameters = s.verifier_parameters;
+        }
+        self.segments.push(s);
+    }
+
+    /// Remove the last segment receipt and return it, or `None` if there are no segments.
+    pub fn pop_segment(&mut self) -> Option<SegmentReceipt> {
+        self.segments.pop()
+    }
+
+    /// Number of segment receipts.
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Return `true` if this receipt has no segment receipts.
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Number of segment receipts: the same as [CompositeReceipt::len].
+    pub fn segment_count(&self) -> usize {
+        self.len()
+    }
+
+    /// The first segment receipt, if any.
+    pub fn first_segment(&self) -> Option<&SegmentReceipt> {
+        self.segments.first()
+    }
+
+    /// The last segment receipt, if any.
+    pub fn last_segment(&self) -> Option<&SegmentReceipt> {
+        self.segments.last()
+    }
+
+    /// The exit code of the first segment, if any.
+    pub fn first_exit_code(&self) -> Option<ExitCode> {
+        self.first_segment().map(|s| s.claim.exit_code)
+    }
+
+    /// The exit code of the last segment, i.e. the one of the whole execution, if any.
+    pub fn final_exit_code(&self) -> Option<ExitCode> {
+        self.last_segment().map(|s| s.claim.exit_code)
+    }
+
+    /// Iterate over the segment receipts.
+    pub fn iter_segments(&self) -> core::slice::Iter<'_, SegmentReceipt> {
+        self.segments.iter()
+    }
+
     /// Verify the integrity of this receipt, ensuring the claim is attested
     /// to by the seal.
     pub fn verify_integrity_with_context(
         &self,
         ctx: &impl VerifierContext,
     ) -> Result<(), VerificationError> {
-        log::debug!("CompositeReceipt::verify_integrity_with_context");
+        debug!("CompositeReceipt::verify_integrity_with_context");
+        self.verify_integrity_with_segments(ctx, |receipt| {
+            receipt.verify_integrity_with_context(ctx)
+        })
+    }
+
+    /// Verify the integrity of this receipt like
+    /// [CompositeReceipt::verify_integrity_with_context], but verify the segment seals in
+    /// parallel, before checking their chaining. Every thread verifies its segments with its own
+    /// context, built by `new_ctx`.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn verify_integrity_parallel<C: VerifierContext>(
+        &self,
+        ctx: &C,
+        new_ctx: &(impl Fn() -> C + Send + Sync),
+    ) -> Result<(), VerificationError> {
+        use rayon::prelude::*;
+
+        debug!("CompositeReceipt::verify_integrity_parallel");
+        self.check_verifier_parameters_consistent()?;
+        self.segments
+            .par_iter()
+            .try_for_each_init(new_ctx, |ctx, receipt| {
+                receipt.verify_integrity_with_context(ctx)
+            })?;
+        // The seals and the verifier parameters are already verified.
+        self.verify_segments_chain(|_| Ok(()))?;
+        self.verify_assumption_receipts(ctx, false).map(|_| ())
+    }
+
+    /// Verify the integrity of this receipt like
+    /// [CompositeReceipt::verify_integrity_with_context], but accept a conditional receipt: the
+    /// assumption receipts resolve the assumptions in order and the assumptions left without a
+    /// receipt are returned, in order, instead of failing. There cannot be more assumption
+    /// receipts than assumptions.
+    pub fn verify_conditional_integrity_with_context(
+        &self,
+        ctx: &impl VerifierContext,
+    ) -> Result<Vec<Assumption>, VerificationError> {
+        debug!("CompositeReceipt::verify_conditional_integrity_with_context");
+        self.verify_conditional_integrity_with_segments(ctx, |receipt| {
+            receipt.verify_integrity_with_context(ctx)
+        })
+    }
+
+    fn verify_integrity_with_segments(
+        &self,
+        ctx: &impl VerifierContext,
+        verify_segment: impl Fn(&SegmentReceipt) -> Result<(), VerificationError>,
+    ) -> Result<(), VerificationError> {
+        self.check_verifier_parameters_consistent()?;
+        self.verify_segments_chain(verify_segment)?;
+        self.verify_assumption_receipts(ctx, false).map(|_| ())
+    }
+
+    fn verify_conditional_integrity_with_segments(
+        &self,
+        ctx: &impl VerifierContext,
+        verify_segment: impl Fn(&SegmentReceipt) -> Result<(), VerificationError>,
+    ) -> Result<Vec<Assumption>, VerificationError> {
+        self.check_verifier_parameters_consistent()?;
+        self.verify_segments_chain(verify_segment)?;
+        self.verify_assumption_receipts(ctx, true)
+    }
+
+    fn check_verifier_parameters_consistent(&self) -> Result<(), VerificationError> {
+        if !self.verifier_parameters_consistent() {
+            return Err(VerificationError::ReceiptFormatError);
+        }
+        Ok(())
+    }
+
+    fn verify_segments_chain(
+        &self,
+        verify_segment: impl Fn(&SegmentReceipt) -> Result<(), VerificationError>,
+    ) -> Result<(), VerificationError> {
+        debug!("segments:\n{}", DebugChain(self));
         // Verify the continuation, by verifying every segment receipt in order.
         let (final_receipt, receipts) = self
             .segments
@@ -71,77 +223,129 @@ impl CompositeReceipt {
             .ok_or(VerificationError::ReceiptFormatError)?;
 
         // Verify each segment and its chaining to the next.
-        let mut expected_pre_state_digest = None;
+        let mut prev_claim: Option<&ReceiptClaim> = None;
         for receipt in receipts {
-            receipt.verify_integrity_with_context(ctx)?;
+            verify_segment(receipt)?;
             let claim = &receipt.claim;
-            log::debug!("claim: {claim:#?}");
-            if let Some(id) = expected_pre_state_digest {
-                if id != claim.pre.digest::<sha::Impl>() {
+            if let Some(prev) = prev_claim {
+                if !claim.is_continuation_of(prev) {
                     return Err(VerificationError::ImageVerificationError);
                 }
             }
-            if claim.exit_code != ExitCode::SystemSplit {
+            if !claim.is_system_split() {
                 return Err(VerificationError::UnexpectedExitCode);
             }
             if !claim.output.is_none() {
                 return Err(VerificationError::ReceiptFormatError);
             }
-            expected_pre_state_digest = Some(
-                claim
-                    .post
-                    .as_value()
-                    .map_err(|_| VerificationError::ReceiptFormatError)?
-                    .digest::<sha::Impl>(),
-            );
+            claim
+                .post
+                .as_value()
+                .map_err(|_| VerificationError::ReceiptFormatError)?;
+            prev_claim = Some(claim);
         }
 
         // Verify the last receipt in the continuation.
-        final_receipt.verify_integrity_with_context(ctx)?;
-        log::debug!("final: {:#?}", final_receipt.claim);
-        if let Some(id) = expected_pre_state_digest {
-            if id != final_receipt.claim.pre.digest::<sha::Impl>() {
+        verify_segment(final_receipt)?;
+        if let Some(prev) = prev_claim {
+            if !final_receipt.claim.is_continuation_of(prev) {
                 return Err(VerificationError::ImageVerificationError);
             }
         }
+        Ok(())
+    }
 
+    /// Verify the assumption receipts against the assumptions they resolve, in order, and
+    /// return the assumptions without a receipt: with `allow_unresolved` set to `false` every
+    /// assumption must have its receipt.
+    fn verify_assumption_receipts(
+        &self,
+        ctx: &impl VerifierContext,
+        allow_unresolved: bool,
+    ) -> Result<Vec<Assumption>, VerificationError> {
         // Verify all assumptions on the receipt are resolved by attached receipts.
         // Ensure that there is one receipt for every assumption. An explicity check is required
         // because zip will terminate if either iterator terminates.
-        let assumptions = self.assumptions()?;
-        if assumptions.len() != self.assumption_receipts.len() {
-            log::debug!(
+        let mut assumptions = self.assumptions()?;
+        let count_mismatch = match allow_unresolved {
+            true => assumptions.len() < self.assumption_receipts.len(),
+            false => assumptions.len() != self.assumption_receipts.len(),
+        };
+        if count_mismatch {
+            debug!(
                 "only {} receipts provided for {} assumptions",
                 assumptions.len(),
                 self.assumption_receipts.len()
             );
             return Err(VerificationError::ReceiptFormatError);
         }
+        let unresolved = assumptions.split_off(self.assumption_receipts.len());
         for (assumption, receipt) in assumptions.into_iter().zip(self.assumption_receipts.iter()) {
-            let assumption_ctx = ctx.assumption_context(&assumption);
-            log::debug!("verifying assumption: {assumption:?}");
-            receipt.verify_integrity_with_context(
-                &assumption_ctx
-                    .map(|c| c.boxed_clone())
-                    .unwrap_or(ctx.boxed_clone()),
-            )?;
-            if receipt.claim_digest()? != assumption.claim {
-                log::debug!(
-                    "verifying assumption failed due to claim mismatch: assumption: {assumption:?}, receipt claim digest: {}",
-                    receipt.claim_digest()?
-                );
-                return Err(VerificationError::ClaimDigestMismatch {
-                    expected: assumption.claim,
-                    received: receipt.claim_digest()?,
-                });
-            }
+            receipt.verify_with_assumption(ctx, &assumption)?;
         }
 
+        Ok(unresolved)
+    }
+
+    /// Write a table of the continuation chain, one row per segment with its index, hash
+    /// function, shortened pre-state and post-state digests and exit code. Meant for debugging
+    /// a chain that fails to verify.
+    pub fn debug_chain(&self, w: &mut impl fmt::Write) -> fmt::Result {
+        writeln!(
+            w,
+            "index  hashfn     pre              post             exit"
+        )?;
+        for s in &self.segments {
+            writeln!(
+                w,
+                "{:>5}  {:<9}  {}  {}  {:?}",
+                s.index,
+                s.hashfn.as_str(),
+                ShortDigest(s.claim.pre_state_digest()),
+                ShortDigest(s.claim.post_state_digest()),
+                s.claim.exit_code
+            )?;
+        }
         Ok(())
     }
 
+    /// Check that all the segments share the same `verifier_parameters` digest: a composite
+    /// receipt mixing segments produced by different verifier versions is malformed. The
+    /// assumption receipts are not checked: they can be of different kinds (e.g. a composite and
+    /// a succinct one), each with the parameters of its own kind, and every one of them is
+    /// verified against its own parameters. Return `true` for empty or single-segment receipts.
+    pub fn verifier_parameters_consistent(&self) -> bool {
+        let mut digests = self.segments.iter().map(|s| s.verifier_parameters);
+        match digests.next() {
+            Some(first) => digests.all(|d| d == first),
+            None => true,
+        }
+    }
+
+    /// Lazily verify the integrity of the segments: every segment is verified only when the
+    /// iterator reaches it. Unlike [CompositeReceipt::verify_integrity_with_context], neither the
+    /// chaining of the segments nor the assumptions are checked.
+    pub fn verify_segments_iter<'a>(
+        &'a self,
+        ctx: &'a impl VerifierContext,
+    ) -> impl Iterator<Item = Result<&'a SegmentReceipt, VerificationError>> + 'a {
+        self.segments
+            .iter()
+            .map(move |segment| segment.verify_integrity_with_context(ctx).map(|_| segment))
+    }
+
     /// Returns the [ReceiptClaim] for this [CompositeReceipt].
     pub fn claim(&self) -> Result<ReceiptClaim, VerificationError> {
+        self.conditional_claim(Vec::new())
+    }
+
+    /// Returns the [ReceiptClaim] for this [CompositeReceipt] like [CompositeReceipt::claim],
+    /// but conditional on the given `unresolved` assumptions, e.g. the ones returned by
+    /// [CompositeReceipt::verify_conditional_integrity_with_context].
+    pub fn conditional_claim(
+        &self,
+        unresolved: Vec<Assumption>,
+    ) -> Result<ReceiptClaim, VerificationError> {
         let first_claim = &self
             .segments
             .first()
@@ -153,19 +357,23 @@ impl CompositeReceipt {
             .ok_or(VerificationError::ReceiptFormatError)?
             .claim;
 
-        // Remove the assumptions from the last receipt claim, as the verify routine requires every
-        // assumption to have an associated verifiable receipt.
-        // TODO(#982) Support unresolved assumptions here by only removing the proven assumptions.
-        let output = last_claim
-            .output
-            .as_value()
-            .map_err(|_| VerificationError::ReceiptFormatError)?
-            .as_ref()
-            .map(|output| Output {
-                journal: output.journal.clone(),
-                assumptions: vec![].into(),
-            })
-            .into();
+        // Remove the resolved assumptions from the last receipt claim: only the unresolved ones
+        // are left. Without assumption receipts there's nothing to remove, so a pruned output is
+        // kept as it is.
+        let output = match &last_claim.output {
+            MaybePruned::Pruned(_) if self.assumption_receipts.is_empty() => {
+                last_claim.output.clone()
+            }
+            output => output
+                .as_value()
+                .map_err(|_| VerificationError::ReceiptFormatError)?
+                .as_ref()
+                .map(|output| Output {
+                    journal: output.journal.clone(),
+                    assumptions: unresolved.into(),
+                })
+                .into(),
+        };
 
         Ok(ReceiptClaim {
             pre: first_claim.pre.clone(),
@@ -178,33 +386,539 @@ impl CompositeReceipt {
 
     fn assumptions(&self) -> Result<Vec<Assumption>, VerificationError> {
         // Collect the assumptions from the output of the last segment, handling any pruned values
-        // encountered and returning and empty list if the output is None.
-        Ok(self
+        // encountered and returning and empty list if the output is None. Without assumption
+        // receipts a pruned output is accepted as it is: the claim digest still commits to it.
+        let output = &self
             .segments
             .last()
             .ok_or(VerificationError::ReceiptFormatError)?
             .claim
-            .output
+            .output;
+        if matches!(output, MaybePruned::Pruned(_)) && self.assumption_receipts.is_empty() {
+            return Ok(Vec::new());
+        }
+        Ok(output
             .as_value()
             .map_err(|_| VerificationError::ReceiptFormatError)?
             .as_ref()
-            .map(|output| match output.assumptions.is_empty() {
-                true => Ok(Default::default()),
-                false => Ok(output
-                    .assumptions
-                    .as_value()?
-                    .iter()
-                    .map(|a| a.as_value().cloned())
-                    .collect::<Result<_, _>>()?),
-            })
+            .map(Output::assumptions_list)
             .transpose()
             .map_err(|_: PrunedValueError| VerificationError::ReceiptFormatError)?
             .unwrap_or_default())
     }
 
-    /// Total number of bytes used by the seals of this receipt.
+    /// Return this [CompositeReceipt] with the given [InnerAssumptionReceipt] appended to the
+    /// assumption receipts.
+    pub fn with_assumption_receipt(mut self, receipt: InnerAssumptionReceipt) -> Self {
+        self.assumption_receipts.push(receipt);
+        self
+    }
+
+    /// Return this [CompositeReceipt] without any assumption receipt.
+    pub fn clear_assumption_receipts(mut self) -> Self {
+        self.assumption_receipts.clear();
+        self
+    }
+
+    /// Number of assumption receipts attached to this receipt.
+    pub fn assumption_receipt_count(&self) -> usize {
+        self.assumption_receipts.len()
+    }
+
+    /// Iterate over the assumption receipts attached to this receipt.
+    pub fn iter_assumption_receipts(&self) -> impl Iterator<Item = &InnerAssumptionReceipt> {
+        self.assumption_receipts.iter()
+    }
+
+    /// Consume this receipt and return its segment receipts and its assumption receipts.
+    pub fn into_segments(self) -> (Vec<SegmentReceipt>, Vec<InnerAssumptionReceipt>) {
+        (self.segments, self.assumption_receipts)
+    }
+
+    /// Consume this receipt and return its first segment receipt, if any.
+    pub fn into_first_segment(self) -> Option<SegmentReceipt> {
+        self.segments.into_iter().next()
+    }
+
+    /// Consume this receipt and return its last segment receipt, if any.
+    pub fn into_last_segment(self) -> Option<SegmentReceipt> {
+        self.segments.into_iter().next_back()
+    }
+
+    /// Like [slice::split_first], return the first segment receipt and this receipt with the
+    /// remaining segments, or `None` if there are no segments. The assumption receipts are kept
+    /// by the remaining receipt.
+    pub fn split_first(mut self) -> Option<(SegmentReceipt, Self)> {
+        if self.segments.is_empty() {
+            return None;
+        }
+        let first = self.segments.remove(0);
+        Some((first, self))
+    }
+
+    /// Like [slice::split_last], return this receipt with all the segments but the last one and
+    /// the last segment receipt, or `None` if there are no segments. The assumption receipts are
+    /// kept by the remaining receipt.
+    pub fn split_last(mut self) -> Option<(Self, SegmentReceipt)> {
+        let last = self.segments.pop()?;
+        Some((self, last))
+    }
+
+    /// Count the segments of this receipt by po2 size, as extracted by the given [Verifier].
+    pub fn po2_histogram(
+        &self,
+        verifier: &impl Verifier,
+    ) -> Result<BTreeMap<u32, usize>, VerificationError> {
+        Ok(verifier
+            .extract_composite_segments_info(self)?
+            .into_iter()
+            .fold(BTreeMap::new(), |mut histogram, info| {
+                *histogram.entry(info.po2).or_default() += 1;
+                histogram
+            }))
+    }
+
+    /// The biggest po2 size of the segments of this receipt, or `None` if there are no segments.
+    pub fn max_segment_po2(
+        &self,
+        verifier: &impl Verifier,
+    ) -> Result<Option<u32>, VerificationError> {
+        Ok(self
+            .po2_histogram(verifier)?
+            .last_key_value()
+            .map(|(&po2, _)| po2))
+    }
+
+    /// The smallest po2 size of the segments of this receipt, or `None` if there are no segments.
+    pub fn min_segment_po2(
+        &self,
+        verifier: &impl Verifier,
+    ) -> Result<Option<u32>, VerificationError> {
+        Ok(self
+            .po2_histogram(verifier)?
+            .first_key_value()
+            .map(|(&po2, _)| po2))
+    }
+
+    /// Total number of bytes used by the seals of this receipt, saturated to `usize::MAX`.
     pub fn seal_size(&self) -> usize {
-        // NOTE: This sum cannot overflow because all seals are in memory.
-        self.segments.iter().map(|s| s.seal_size()).sum()
+        self.total_seal_bytes().unwrap_or(usize::MAX)
+    }
+
+    /// Total number of words of the seals of this receipt.
+    pub fn total_seal_words(&self) -> usize {
+        self.segments.iter().map(|s| s.seal.len()).sum()
+    }
+
+    /// Total number of bytes used by the seals of this receipt, or `None` if it overflows a
+    /// `usize` (on 32-bit targets).
+    pub fn total_seal_bytes(&self) -> Option<usize> {
+        self.segments
+            .iter()
+            .try_fold(0_usize, |total, s| total.checked_add(s.seal_size()))
+    }
+
+    /// Rough proxy of the cost of verifying this receipt: the sum of the `log2` of the number of
+    /// seal words of every segment, as the FRI verification cost grows with the log of the
+    /// trace size. Only useful to compare receipts.
+    pub fn verification_cost_heuristic(&self) -> f64 {
+        self.segments
+            .iter()
+            .filter(|s| !s.seal.is_empty())
+            .map(|s| libm::log2(s.seal.len() as f64))
+            .sum()
+    }
+
+    /// Convert this receipt into a [PrunedCompositeReceipt], replacing the seal of every
+    /// segment with its SHA-256 digest.
+    pub fn prune_seals(self) -> PrunedCompositeReceipt {
+        PrunedCompositeReceipt {
+            segments: self
+                .segments
+                .into_iter()
+                .map(SegmentReceipt::into_pruned)
+                .collect(),
+            assumption_receipts: self.assumption_receipts,
+            verifier_parameters: self.verifier_parameters,
+        }
+    }
+}
+
+impl Digestible for CompositeReceipt {
+    /// Canonical fingerprint of the receipt: it commits to the segment receipts, in order, and
+    /// the verifier parameters. The assumption receipts are not included.
+    fn digest<S: Sha256>(&self) -> Digest {
+        tagged_struct::<S>(
+            "risc0.CompositeReceipt",
+            &[
+                tagged_iter::<S>(
+                    "risc0.SegmentReceipts",
+                    self.segments.iter().map(|s| s.digest::<S>()),
+                ),
+                self.verifier_parameters,
+            ],
+            &[] as &[u32],
+        )
+    }
+}
+
+/// Display a [CompositeReceipt] with [CompositeReceipt::debug_chain].
+struct DebugChain<'a>(&'a CompositeReceipt);
+
+impl fmt::Display for DebugChain<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.debug_chain(f)
+    }
+}
+
+/// A [CompositeReceipt] where the segment seals are replaced by their SHA-256 digests: see
+/// [PrunedSegmentReceipt].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PrunedCompositeReceipt {
+    /// Pruned segment receipts forming the proof of an execution with continuations.
+    pub segments: Vec<PrunedSegmentReceipt>,
+
+    /// The assumption receipts of the original [CompositeReceipt].
+    pub assumption_receipts: Vec<InnerAssumptionReceipt>,
+
+    /// A digest of the verifier parameters that can be used to verify the original receipt.
+    pub verifier_parameters: Digest,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{context::v1::V1, Proof};
+
+    fn composite(path: &str) -> CompositeReceipt {
+        let proof: Proof = ciborium::from_reader(std::fs::File::open(path).unwrap()).unwrap();
+        proof.inner.composite().unwrap().clone()
+    }
+
+    #[test]
+    fn verifier_parameters_consistent() {
+        let composite = composite("./resources/receipts/1.2.0-1.2.0/poseidon2_16.bin");
+        let empty = CompositeReceipt {
+            segments: Vec::new(),
+            ..composite.clone()
+        };
+
+        assert!(composite.verifier_parameters_consistent());
+        assert!(empty.verifier_parameters_consistent());
+    }
+
+    fn corrupted(path: &str, indexes: &[usize]) -> CompositeReceipt {
+        let mut composite = composite(path);
+        for &i in indexes {
+            composite.segments[i].seal[0] ^= 1;
+        }
+        composite
+    }
+
+    #[test]
+    fn verify_segments_iter_stops_at_the_first_failure() {
+        let composite = corrupted("./resources/receipts/1.2.0-1.2.0/sha_22.bin", &[1]);
+        let ctx = V1::v1_2();
+
+        let verified = composite
+            .verify_segments_iter(&ctx)
+            .take_while(Result::is_ok)
+            .count();
+
+        assert_eq!(1, verified);
+    }
+
+    #[test]
+    fn verify_segments_iter_collects_all_the_errors() {
+        let composite = corrupted("./resources/receipts/1.2.0-1.2.0/sha_22.bin", &[0, 2]);
+        let ctx = V1::v1_2();
+
+        let results = composite.verify_segments_iter(&ctx).collect::<Vec<_>>();
+        let errors = results.iter().filter_map(|r| r.as_ref().err()).count();
+
+        assert_eq!(3, results.len());
+        assert_eq!(2, errors);
+        assert!(core::ptr::eq(
+            &composite.segments[1],
+            *results[1].as_ref().unwrap()
+        ));
+    }
+
+    #[test]
+    fn mixed_verifier_parameters_are_rejected_before_verifying_seals() {
+        let mut composite = composite("./resources/receipts/1.2.0-1.2.0/poseidon2_16.bin");
+        let mut segment = composite.segments[0].clone();
+        segment.index += 1;
+        segment.verifier_parameters = Digest::ZERO;
+        // An invalid seal would fail with `InvalidProof` if it was verified.
+        composite.segments[0].seal[0] ^= 1;
+        composite.segments.push(segment);
+
+        assert!(!composite.verifier_parameters_consistent());
+        assert_eq!(
+            Err(VerificationError::ReceiptFormatError),
+            composite.verify_integrity_with_context(&V1::v1_2())
+        );
+    }
+
+    /// A composite receipt whose last segment claim has the given assumptions, with the first
+    /// `resolved` of them resolved by an assumption receipt. The claim of every assumption
+    /// matches the one of the real succinct receipt used as assumption receipt, so only the
+    /// segment seals, which commit to the original claim, are not valid anymore.
+    fn conditional(assumptions: usize, resolved: usize) -> (CompositeReceipt, Vec<Assumption>) {
+        let mut composite = composite("./resources/receipts/1.2.0-1.2.0/poseidon2_16.bin");
+        let succinct: Proof = ciborium::from_reader(
+            std::fs::File::open("./resources/receipts/1.2.0-1.2.0/succinct_16.bin").unwrap(),
+        )
+        .unwrap();
+        let receipt = InnerAssumptionReceipt::from(succinct.inner);
+        let assumption = Assumption {
+            claim: receipt.claim_digest().unwrap(),
+            control_root: Digest::ZERO,
+        };
+        let assumptions = vec![assumption; assumptions];
+        let last = composite.segments.last_mut().unwrap();
+        let output = last.claim.output.as_value_mut().unwrap().as_mut().unwrap();
+        output.assumptions = assumptions.clone().into();
+        composite.assumption_receipts = vec![receipt; resolved];
+        (composite, assumptions)
+    }
+
+    #[rstest::rstest]
+    #[case::no_assumptions(0, 0)]
+    #[case::unresolved(2, 0)]
+    #[case::partially_resolved(2, 1)]
+    #[case::fully_resolved(2, 2)]
+    fn verify_conditional_returns_the_unresolved_assumptions(
+        #[case] assumptions: usize,
+        #[case] resolved: usize,
+    ) {
+        let (composite, assumptions) = conditional(assumptions, resolved);
+        let ctx = V1::v1_2();
+
+        let unresolved = composite
+            .verify_conditional_integrity_with_segments(&ctx, |_| Ok(()))
+            .unwrap();
+
+        assert_eq!(assumptions[resolved..], unresolved[..]);
+        let strict = composite.verify_integrity_with_segments(&ctx, |_| Ok(()));
+        match unresolved.is_empty() {
+            true => assert_eq!(Ok(()), strict),
+            false => assert_eq!(Err(VerificationError::ReceiptFormatError), strict),
+        }
+        // Without any resolved assumption the conditional claim is the one of the segments.
+        if resolved == 0 {
+            assert_eq!(
+                composite
+                    .segments
+                    .last()
+                    .unwrap()
+                    .claim
+                    .output
+                    .digest::<crate::sha::Impl>(),
+                composite
+                    .conditional_claim(unresolved)
+                    .unwrap()
+                    .output
+                    .digest::<crate::sha::Impl>()
+            );
+        }
+    }
+
+    #[test]
+    fn mixed_kind_assumption_receipts_are_accepted() {
+        let receipt = InnerAssumptionReceipt::Composite(composite(
+            "./resources/receipts/1.2.0-1.2.0/poseidon2_16.bin",
+        ));
+        let (mut conditional, mut assumptions) = conditional(1, 1);
+        assumptions.push(Assumption {
+            claim: receipt.claim_digest().unwrap(),
+            control_root: Digest::ZERO,
+        });
+        let last = conditional.segments.last_mut().unwrap();
+        let output = last.claim.output.as_value_mut().unwrap().as_mut().unwrap();
+        output.assumptions = assumptions.into();
+        conditional.assumption_receipts.push(receipt);
+        assert_ne!(
+            conditional.assumption_receipts[0].verifier_parameters(),
+            conditional.assumption_receipts[1].verifier_parameters()
+        );
+
+        assert!(conditional.verifier_parameters_consistent());
+        assert_eq!(
+            Ok(()),
+            conditional.verify_integrity_with_segments(&V1::v1_2(), |_| Ok(()))
+        );
+    }
+
+    #[test]
+    fn clearing_a_required_assumption_receipt_fails_verification() {
+        let (composite, _) = conditional(1, 1);
+        let ctx = V1::v1_2();
+        assert_eq!(
+            Ok(()),
+            composite.verify_integrity_with_segments(&ctx, |_| Ok(()))
+        );
+
+        let composite = composite.clear_assumption_receipts();
+
+        assert_eq!(
+            Err(VerificationError::ReceiptFormatError),
+            composite.verify_integrity_with_segments(&ctx, |_| Ok(()))
+        );
+    }
+
+    #[test]
+    fn verify_conditional_rejects_extra_assumption_receipts() {
+        let (mut composite, _) = conditional(1, 1);
+        composite
+            .assumption_receipts
+            .push(composite.assumption_receipts[0].clone());
+
+        assert_eq!(
+            Err(VerificationError::ReceiptFormatError),
+            composite.verify_conditional_integrity_with_segments(&V1::v1_2(), |_| Ok(()))
+        );
+    }
+
+    #[test]
+    fn verify_conditional_verifies_the_provided_assumption_receipts() {
+        let (mut composite, _) = conditional(2, 1);
+        let output = composite
+            .segments
+            .last_mut()
+            .unwrap()
+            .claim
+            .output
+            .as_value_mut()
+            .unwrap()
+            .as_mut()
+            .unwrap();
+        output.assumptions = vec![
+            Assumption {
+                claim: Digest::ZERO,
+                control_root: Digest::ZERO,
+            };
+            2
+        ]
+        .into();
+
+        assert!(matches!(
+            composite.verify_conditional_integrity_with_segments(&V1::v1_2(), |_| Ok(())),
+            Err(VerificationError::ClaimDigestMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn total_seal_size_and_cost() {
+        let single = composite("./resources/receipts/1.2.0-1.2.0/poseidon2_16.bin");
+        let mut four = composite("./resources/receipts/1.2.0-1.2.0/sha_22.bin");
+        four.push_segment(four.segments[0].clone());
+
+        assert_eq!(4, four.len());
+        assert_eq!(
+            Some(four.segments.iter().map(SegmentReceipt::seal_size).sum()),
+            four.total_seal_bytes()
+        );
+        assert_eq!(four.seal_size(), four.total_seal_words() * 4);
+        assert!(single.verification_cost_heuristic() > 0.0);
+        assert!(single.verification_cost_heuristic() < four.verification_cost_heuristic());
+    }
+
+    #[test]
+    fn composite_receipt_digest_is_stable() {
+        use risc0_zkp_v1::core::digest::digest;
+        let composite = composite("./resources/receipts/1.2.0-1.2.0/sha_22.bin");
+
+        assert_eq!(
+            digest!("9a469825905da8df744b04aaefa2c91ae4317dd4d6555147f1b82fe059d38bfc"),
+            composite.digest::<crate::sha::Impl>()
+        );
+    }
+
+    #[test]
+    fn debug_chain() {
+        let composite = composite("./resources/receipts/1.2.0-1.2.0/sha_22.bin");
+        let mut out = alloc::string::String::new();
+
+        composite.debug_chain(&mut out).unwrap();
+
+        let rows = out.lines().skip(1).collect::<Vec<_>>();
+        assert_eq!(3, rows.len());
+        let indexes = rows
+            .iter()
+            .map(|row| {
+                row.split_whitespace()
+                    .next()
+                    .unwrap()
+                    .parse::<u32>()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert!(indexes.windows(2).all(|w| w[0] < w[1]));
+        assert!(rows[..2].iter().all(|row| row.ends_with("SystemSplit")));
+        assert!(rows[0].contains(&std::format!(
+            "{}",
+            ShortDigest(composite.segments[0].claim.pre_state_digest())
+        )));
+    }
+
+    #[rstest::rstest]
+    #[case::empty(0)]
+    #[case::single(1)]
+    #[case::three(3)]
+    fn segment_bounds_accessors(#[case] n: usize) {
+        let mut composite = composite("./resources/receipts/1.2.0-1.2.0/sha_22.bin");
+        composite.segments.truncate(n);
+        let segments = composite.segments.clone();
+
+        assert_eq!(n, composite.segment_count());
+        assert_eq!(n == 0, composite.is_empty());
+        assert_eq!(
+            segments.first().map(|s| s.index),
+            composite.first_segment().map(|s| s.index)
+        );
+        assert_eq!(
+            segments.last().map(|s| s.index),
+            composite.last_segment().map(|s| s.index)
+        );
+        assert_eq!(
+            segments.first().map(|s| s.claim.exit_code),
+            composite.first_exit_code()
+        );
+        assert_eq!(
+            segments.last().map(|s| s.claim.exit_code),
+            composite.final_exit_code()
+        );
+        assert_eq!(
+            segments.first().map(|s| s.index),
+            composite.clone().into_first_segment().map(|s| s.index)
+        );
+        assert_eq!(
+            segments.last().map(|s| s.index),
+            composite.into_last_segment().map(|s| s.index)
+        );
+    }
+
+    #[test]
+    fn exit_codes_of_a_three_segments_receipt() {
+        let composite = composite("./resources/receipts/1.2.0-1.2.0/sha_22.bin");
+
+        assert_eq!(3, composite.segment_count());
+        assert_eq!(Some(ExitCode::SystemSplit), composite.first_exit_code());
+        assert_eq!(Some(ExitCode::Halted(0)), composite.final_exit_code());
+    }
+
+    #[test]
+    fn empty_receipt_has_no_bounds() {
+        let empty = CompositeReceipt {
+            segments: Vec::new(),
+            ..composite("./resources/receipts/1.2.0-1.2.0/sha_22.bin")
+        };
+
+        assert!(empty.first_segment().is_none() && empty.last_segment().is_none());
+        assert_eq!(None, empty.first_exit_code());
+        assert_eq!(None, empty.final_exit_code());
+        assert!(empty.into_first_segment().is_none());
     }
 }
@@ -0,0 +1,88 @@
+// Copyright 2024, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The fake receipts generated by the risc0 provers in dev mode (`RISC0_DEV_MODE=1`). They are
+//! available only with the `dev-mode` feature: never enable it in production.
+
+use core::fmt::Debug;
+
+use risc0_binfmt_v1::Digestible;
+use risc0_zkp_v1::verify::VerificationError;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    context::VerifierContext,
+    receipt_claim::{MaybePruned, Unknown},
+    sha,
+};
+
+/// A receipt with no cryptographic integrity that a risc0 prover in dev mode generates in place
+/// of the real one: it carries only the claim that it pretends to prove.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(test, derive(PartialEq))]
+#[non_exhaustive]
+pub struct FakeReceipt<Claim>
+where
+    Claim: Digestible + Debug + Clone + Serialize,
+{
+    /// Claim containing information about the computation that this receipt pretends to prove.
+    ///
+    /// The standard claim type is [ReceiptClaim][crate::receipt_claim::ReceiptClaim], which
+    /// represents a RISC-V zkVM execution.
+    pub claim: MaybePruned<Claim>,
+}
+
+impl<Claim> FakeReceipt<Claim>
+where
+    Claim: Digestible + Debug + Clone + Serialize,
+{
+    /// Create a [FakeReceipt] for the given claim.
+    pub fn new(claim: impl Into<MaybePruned<Claim>>) -> Self {
+        Self {
+            claim: claim.into(),
+        }
+    }
+
+    /// There's no seal to verify: succeed only if `ctx` allows fake receipts, and warn that the
+    /// receipt proves nothing. Otherwise return [VerificationError::ReceiptFormatError].
+    pub fn verify_integrity_with_context(
+        &self,
+        ctx: &impl VerifierContext,
+    ) -> Result<(), VerificationError> {
+        if !ctx.verifier_parameters().options.allow_fake_receipts {
+            debug!("fake receipts are not allowed by the verifier");
+            return Err(VerificationError::ReceiptFormatError);
+        }
+        warn!(
+            "ACCEPTING A FAKE RECEIPT WITHOUT ANY PROOF (claim {}): the `dev-mode` feature must \
+             never be enabled in production",
+            self.claim.digest::<sha::Impl>()
+        );
+        Ok(())
+    }
+
+    /// A fake receipt has no seal.
+    pub fn seal_size(&self) -> usize {
+        0
+    }
+
+    /// Prunes the claim, retaining its digest, and converts into a [FakeReceipt] with an unknown
+    /// claim type. Can be used to get receipts of a uniform type across heterogeneous claims.
+    pub fn into_unknown(self) -> FakeReceipt<Unknown> {
+        FakeReceipt {
+            claim: MaybePruned::Pruned(self.claim.digest::<sha::Impl>()),
+        }
+    }
+}
@@ -0,0 +1,483 @@
+// Copyright Copyright 2024, Horizen Labs, Inc.
+// Copyright Copyright 2024 RISC Zero, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use risc0_binfmt_v1::{tagged_struct, Digestible};
+use risc0_groth16::VerifyingKey;
+use risc0_zkp_v1::{
+    core::{digest::Digest, hash::sha::Sha256},
+    verify::VerificationError,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    context::VerifierContext,
+    receipt_claim::{MaybePruned, Unknown},
+    sha,
+    translate::Translate,
+};
+
+/// A receipt composed of a Groth16 proof over the BN254 curve: the compact receipt that risc0
+/// produces by wrapping a [SuccinctReceipt](crate::SuccinctReceipt) in a SNARK (see
+/// `ProverOpts::groth16()`).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(test, derive(PartialEq))]
+#[non_exhaustive]
+pub struct Groth16Receipt<Claim>
+where
+    Claim: Digestible + Debug + Clone + Serialize,
+{
+    /// A Groth16 proof of a zkVM execution with the associated claim.
+    pub seal: Vec<u8>,
+
+    /// Claim containing information about the computation that this receipt proves.
+    ///
+    /// The standard claim type is [ReceiptClaim][crate::ReceiptClaim], which represents a RISC-V
+    /// zkVM execution.
+    pub claim: MaybePruned<Claim>,
+
+    /// A digest of the verifier parameters that can be used to verify this receipt.
+    ///
+    /// Acts as a fingerprint to identify differing proof system or circuit versions between a
+    /// prover and a verifier. It is not intended to contain the full verifier parameters, which must
+    /// be provided by a trusted source (e.g. packaged with the verifier code).
+    pub verifier_parameters: Digest,
+}
+
+impl<Claim> Groth16Receipt<Claim>
+where
+    Claim: Digestible + Debug + Clone + Serialize,
+{
+    /// Create a [Groth16Receipt] from the given seal, claim, and verifier parameters digest.
+    pub fn new(seal: Vec<u8>, claim: MaybePruned<Claim>, verifier_parameters: Digest) -> Self {
+        Self {
+            seal,
+            claim,
+            verifier_parameters,
+        }
+    }
+
+    /// Verify the integrity of this receipt, ensuring the claim is attested
+    /// to by the seal.
+    pub fn verify_integrity_with_context(
+        &self,
+        ctx: &impl VerifierContext,
+    ) -> Result<(), VerificationError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("risc0::verify_groth16").entered();
+
+        let params = ctx
+            .verifier_parameters()
+            .groth16_verifier_parameters()
+            .ok_or(VerificationError::VerifierParametersMissing)?;
+
+        if params.digest::<sha::Impl>() != self.verifier_parameters {
+            return Err(VerificationError::VerifierParametersMismatch {
+                expected: params.digest::<sha::Impl>(),
+                received: self.verifier_parameters,
+            });
+        }
+
+        risc0_groth16::Verifier::new(
+            &self.seal,
+            params.control_root.translate(),
+            self.claim.digest::<sha::Impl>().translate(),
+            params.bn254_control_id.translate(),
+            &params.verifying_key,
+        )
+        .map_err(|_| VerificationError::ReceiptFormatError)?
+        .verify()
+        .map_err(|_| VerificationError::InvalidProof)
+    }
+
+    /// Number of bytes used by the seal for this receipt.
+    pub fn seal_size(&self) -> usize {
+        core::mem::size_of_val(self.seal.as_slice())
+    }
+
+    /// Prunes the claim, retaining its digest, and converts into a [Groth16Receipt] with an unknown
+    /// claim type. Can be used to get receipts of a uniform type across heterogeneous claims.
+    pub fn into_unknown(self) -> Groth16Receipt<Unknown> {
+        Groth16Receipt {
+            claim: MaybePruned::Pruned(self.claim.digest::<sha::Impl>()),
+            seal: self.seal,
+            verifier_parameters: self.verifier_parameters,
+        }
+    }
+}
+
+/// Verifier parameters used to verify a [Groth16Receipt].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Groth16ReceiptVerifierParameters {
+    /// Control root with which the receipt is expected to verify.
+    pub control_root: Digest,
+    /// Control ID, calculated with Poseidon over BN254 scalar field, with which the receipt is
+    /// expected to verify.
+    pub bn254_control_id: Digest,
+    /// Groth16 verifying key with which the receipt is expected to verify.
+    pub verifying_key: VerifyingKey,
+}
+
+impl Digestible for Groth16ReceiptVerifierParameters {
+    /// Hash the [Groth16ReceiptVerifierParameters] to get a digest of the struct.
+    fn digest<S: Sha256>(&self) -> Digest {
+        let verifying_key = risc0_binfmt_v3::Digestible::digest::<
+            risc0_zkp_v3::core::hash::sha::Impl,
+        >(&self.verifying_key);
+        tagged_struct::<S>(
+            "risc0.Groth16ReceiptVerifierParameters",
+            &[
+                self.control_root,
+                self.bn254_control_id,
+                verifying_key.translate(),
+            ],
+            &[],
+        )
+    }
+}
+
+// Every risc0 release verifies the Groth16 receipts with the key of the same trusted setup
+// ceremony: only the control root and the BN254 control ID of the recursion circuit change.
+impl Groth16ReceiptVerifierParameters {
+    /// v1_0 set of parameters used to verify a [Groth16Receipt].
+    pub fn v1_0() -> Self {
+        use crate::circuit::v1_0::recursive::control_id;
+        Self {
+            control_root: control_id::ALLOWED_CONTROL_ROOT,
+            bn254_control_id: control_id::BN254_IDENTITY_CONTROL_ID,
+            verifying_key: risc0_groth16::verifying_key(),
+        }
+    }
+
+    /// v1_1 set of parameters used to verify a [Groth16Receipt].
+    pub fn v1_1() -> Self {
+        use crate::circuit::v1_1::recursive::control_id;
+        Self {
+            control_root: control_id::ALLOWED_CONTROL_ROOT,
+            bn254_control_id: control_id::BN254_IDENTITY_CONTROL_ID,
+            verifying_key: risc0_groth16::verifying_key(),
+        }
+    }
+
+    /// v1_2 set of parameters used to verify a [Groth16Receipt].
+    pub fn v1_2() -> Self {
+        use crate::circuit::v1_2::recursive::control_id;
+        Self {
+            control_root: control_id::ALLOWED_CONTROL_ROOT,
+            bn254_control_id: control_id::BN254_IDENTITY_CONTROL_ID,
+            verifying_key: risc0_groth16::verifying_key(),
+        }
+    }
+
+    /// v2_0 set of parameters used to verify a [Groth16Receipt].
+    pub fn v2_0() -> Self {
+        use crate::circuit::v2_0::recursive::control_id;
+        Self {
+            control_root: control_id::ALLOWED_CONTROL_ROOT.translate(),
+            bn254_control_id: control_id::BN254_IDENTITY_CONTROL_ID.translate(),
+            verifying_key: risc0_groth16::verifying_key(),
+        }
+    }
+
+    /// v2_1 set of parameters used to verify a [Groth16Receipt].
+    pub fn v2_1() -> Self {
+        use crate::circuit::v2_1::recursive::control_id;
+        Self {
+            control_root: control_id::ALLOWED_CONTROL_ROOT.translate(),
+            bn254_control_id: control_id::BN254_IDENTITY_CONTROL_ID.translate(),
+            verifying_key: risc0_groth16::verifying_key(),
+        }
+    }
+
+    /// v2_2 set of parameters used to verify a [Groth16Receipt].
+    pub fn v2_2() -> Self {
+        use crate::circuit::v2_2::recursive::control_id;
+        Self {
+            control_root: control_id::ALLOWED_CONTROL_ROOT.translate(),
+            bn254_control_id: control_id::BN254_IDENTITY_CONTROL_ID.translate(),
+            verifying_key: risc0_groth16::verifying_key(),
+        }
+    }
+
+    /// v2_3 set of parameters used to verify a [Groth16Receipt].
+    pub fn v2_3() -> Self {
+        use crate::circuit::v2_3::recursive::control_id;
+        Self {
+            control_root: control_id::ALLOWED_CONTROL_ROOT.translate(),
+            bn254_control_id: control_id::BN254_IDENTITY_CONTROL_ID.translate(),
+            verifying_key: risc0_groth16::verifying_key(),
+        }
+    }
+
+    /// v3_0 set of parameters used to verify a [Groth16Receipt].
+    pub fn v3_0() -> Self {
+        use crate::circuit::v3_0::recursive::control_id;
+        Self {
+            control_root: control_id::ALLOWED_CONTROL_ROOT.translate(),
+            bn254_control_id: control_id::BN254_IDENTITY_CONTROL_ID.translate(),
+            verifying_key: risc0_groth16::verifying_key(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{context::v3::V3, receipt_claim::ReceiptClaim, Proof};
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::PrimeField;
+    use ark_groth16::Groth16;
+    use ark_relations::{
+        lc,
+        r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError, Variable},
+    };
+    use ark_serialize::CanonicalSerialize;
+    use ark_snark::{CircuitSpecificSetupSNARK, SNARK};
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use risc0_zkp_v1::core::digest::digest;
+    use rstest::rstest;
+
+    // Check that the verifier parameters has a stable digest (and therefore a stable value). This
+    // struct encodes parameters used in verification, and so this value should be updated if and
+    // only if a change to the verifier parameters is expected. Updating the verifier parameters
+    // will result in incompatibility with previous versions.
+    #[rstest]
+    #[case::v1_0(Groth16ReceiptVerifierParameters::v1_0().digest::<sha::Impl>(), digest!("310fe598e8e3e92fa805bc272d7f587898bb8b68c4d5d7938db884abaa76e15c"))]
+    #[case::v1_1(Groth16ReceiptVerifierParameters::v1_1().digest::<sha::Impl>(), digest!("50bd1769093e74abda3711c315d84d78e3e282173f6304a33272d92abb590ef5"))]
+    #[case::v1_2(Groth16ReceiptVerifierParameters::v1_2().digest::<sha::Impl>(), digest!("c101b42bcacd62e35222b1207223250814d05dd41d41f8cadc1f16f86707ae15"))]
+    #[case::v2_0(Groth16ReceiptVerifierParameters::v2_0().digest::<sha::Impl>(), digest!("9f39696cb3ae9d6038d6b7a55c09017f0cf35e226ad7582b82dbabb0dae53385"))]
+    #[case::v2_1(Groth16ReceiptVerifierParameters::v2_1().digest::<sha::Impl>(), digest!("f536085a791bdbc6cb46ab3074f88e9e94eabb192de8daca3caee1f4ed811b08"))]
+    #[case::v2_2(Groth16ReceiptVerifierParameters::v2_2().digest::<sha::Impl>(), digest!("bb001d444841d70e8bc0c7d034b349044bf3cf0117afb702b2f1e898b7dd13cc"))]
+    #[case::v2_3(Groth16ReceiptVerifierParameters::v2_3().digest::<sha::Impl>(), digest!("bb001d444841d70e8bc0c7d034b349044bf3cf0117afb702b2f1e898b7dd13cc"))]
+    #[case::v3_0(Groth16ReceiptVerifierParameters::v3_0().digest::<sha::Impl>(), digest!("73c457ba541936f0d907daf0c7253a39a9c5c427c225ba7709e44702d3c6eedc"))]
+    fn groth16_receipt_verifier_parameters_is_stable(
+        #[case] computed: Digest,
+        #[case] hardcoded: Digest,
+    ) {
+        assert_eq!(computed, hardcoded);
+    }
+
+    // The selector of the risc0-ethereum Groth16 verifier of every release is the first 4 bytes
+    // of the upstream `Groth16ReceiptVerifierParameters::default().digest()`: check them against
+    // the published ones (`contracts/deployment.toml` in risc0-ethereum). 2.3 didn't change the
+    // Groth16 parameters of 2.2.
+    #[rstest]
+    #[case::v1_0(Groth16ReceiptVerifierParameters::v1_0(), [0x31, 0x0f, 0xe5, 0x98])]
+    #[case::v1_1(Groth16ReceiptVerifierParameters::v1_1(), [0x50, 0xbd, 0x17, 0x69])]
+    #[case::v1_2(Groth16ReceiptVerifierParameters::v1_2(), [0xc1, 0x01, 0xb4, 0x2b])]
+    #[case::v2_0(Groth16ReceiptVerifierParameters::v2_0(), [0x9f, 0x39, 0x69, 0x6c])]
+    #[case::v2_1(Groth16ReceiptVerifierParameters::v2_1(), [0xf5, 0x36, 0x08, 0x5a])]
+    #[case::v2_2(Groth16ReceiptVerifierParameters::v2_2(), [0xbb, 0x00, 0x1d, 0x44])]
+    #[case::v2_3(Groth16ReceiptVerifierParameters::v2_3(), [0xbb, 0x00, 0x1d, 0x44])]
+    #[case::v3_0(Groth16ReceiptVerifierParameters::v3_0(), [0x73, 0xc4, 0x57, 0xba])]
+    fn groth16_receipt_verifier_parameters_match_the_published_selector(
+        #[case] params: Groth16ReceiptVerifierParameters,
+        #[case] selector: [u8; 4],
+    ) {
+        assert_eq!(selector, params.digest::<sha::Impl>().as_bytes()[..4]);
+    }
+
+    /// A circuit that only exposes the public inputs of the risc0 Groth16 verifier: the proofs
+    /// generated with its (test) setup verify with any claim.
+    struct PublicInputsCircuit([Fr; 5]);
+
+    impl ConstraintSynthesizer<Fr> for PublicInputsCircuit {
+        fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+            for input in self.0 {
+                let v = cs.new_input_variable(|| Ok(input))?;
+                cs.enforce_constraint(lc!() + v, lc!() + Variable::One, lc!() + v)?;
+            }
+            Ok(())
+        }
+    }
+
+    /// The public inputs as computed by the risc0 Groth16 verifier: the two halves of the
+    /// control root and of the claim digest and the byte reversed BN254 control ID.
+    fn public_inputs(control_root: Digest, claim: Digest, bn254_control_id: Digest) -> [Fr; 5] {
+        let halves = |d: Digest| {
+            let be: Vec<u8> = d.as_bytes().iter().rev().copied().collect();
+            (
+                Fr::from_be_bytes_mod_order(&be[16..]),
+                Fr::from_be_bytes_mod_order(&be[..16]),
+            )
+        };
+        let (a0, a1) = halves(control_root);
+        let (c0, c1) = halves(claim);
+        let id: Vec<u8> = bn254_control_id.as_bytes().iter().rev().copied().collect();
+        [a0, a1, c0, c1, Fr::from_be_bytes_mod_order(&id)]
+    }
+
+    /// Concatenate the big-endian 32 bytes coordinates of `point` in the given order.
+    fn be_coordinates(point: &impl CanonicalSerialize, order: &[usize]) -> Vec<u8> {
+        let mut le = Vec::new();
+        point.serialize_uncompressed(&mut le).unwrap();
+        let coordinates: Vec<_> = le.chunks(32).collect();
+        order
+            .iter()
+            .flat_map(|&i| coordinates[i].iter().rev().copied())
+            .collect()
+    }
+
+    /// Generate a test setup and a Groth16 receipt of `claim` that verifies with the returned
+    /// parameters (and with no risc0 release).
+    fn test_receipt(
+        claim: ReceiptClaim,
+    ) -> (
+        Groth16ReceiptVerifierParameters,
+        Groth16Receipt<ReceiptClaim>,
+    ) {
+        let base = Groth16ReceiptVerifierParameters::v3_0();
+        let circuit = || {
+            PublicInputsCircuit(public_inputs(
+                base.control_root,
+                claim.digest::<sha::Impl>(),
+                base.bn254_control_id,
+            ))
+        };
+        let mut rng = StdRng::seed_from_u64(0);
+        let (pk, vk) = Groth16::<Bn254>::setup(circuit(), &mut rng).unwrap();
+        let proof = Groth16::<Bn254>::prove(&pk, circuit(), &mut rng).unwrap();
+
+        let mut vk_bytes = Vec::new();
+        vk.serialize_uncompressed(&mut vk_bytes).unwrap();
+        let params = Groth16ReceiptVerifierParameters {
+            verifying_key: serde_json::from_value(serde_json::json!(vk_bytes)).unwrap(),
+            ..base
+        };
+        let seal = [
+            be_coordinates(&proof.a, &[0, 1]),
+            be_coordinates(&proof.b, &[1, 0, 3, 2]),
+            be_coordinates(&proof.c, &[0, 1]),
+        ]
+        .concat();
+        let receipt = Groth16Receipt::new(
+            seal,
+            MaybePruned::Value(claim),
+            params.digest::<sha::Impl>(),
+        );
+        (params, receipt)
+    }
+
+    const IMAGE_ID: Digest = Digest::new([1; 8]);
+    const JOURNAL_DIGEST: Digest = Digest::new([2; 8]);
+
+    fn claim() -> ReceiptClaim {
+        ReceiptClaim::ok(IMAGE_ID, MaybePruned::Pruned(JOURNAL_DIGEST))
+    }
+
+    #[test]
+    fn verify_proof() {
+        let (params, receipt) = test_receipt(claim());
+        let ctx = V3::v3_0().with_groth16_verifier_parameters(params);
+
+        Proof::from_groth16(receipt)
+            .verify(&ctx, IMAGE_ID, JOURNAL_DIGEST)
+            .unwrap();
+    }
+
+    #[test]
+    fn seal_words_match_the_word_count() {
+        let (_, receipt) = test_receipt(claim());
+        let proof = Proof::from_groth16(receipt);
+
+        let slices = proof.inner.seal_word_slices();
+
+        assert_eq!(1, slices.len());
+        assert_eq!(64, proof.seal_word_count());
+        assert_eq!(proof.seal_word_count(), slices[0].len());
+        assert_eq!(proof.seal_u32_words(), slices[0].as_ref());
+    }
+
+    #[test]
+    fn reject_wrong_journal() {
+        let (params, receipt) = test_receipt(claim());
+        let ctx = V3::v3_0().with_groth16_verifier_parameters(params);
+
+        assert!(matches!(
+            Proof::from_groth16(receipt).verify(&ctx, IMAGE_ID, Digest::new([3; 8])),
+            Err(VerificationError::ClaimDigestMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn reject_tampered_claim() {
+        let (params, mut receipt) = test_receipt(claim());
+        let ctx = V3::v3_0().with_groth16_verifier_parameters(params);
+        let other = Digest::new([3; 8]);
+        receipt.claim = MaybePruned::Value(ReceiptClaim::ok(IMAGE_ID, MaybePruned::Pruned(other)));
+
+        assert_eq!(
+            Err(VerificationError::InvalidProof),
+            Proof::from_groth16(receipt).verify(&ctx, IMAGE_ID, other)
+        );
+    }
+
+    #[test]
+    fn reject_proof_of_another_setup() {
+        let (_, mut receipt) = test_receipt(claim());
+        receipt.verifier_parameters =
+            Groth16ReceiptVerifierParameters::v3_0().digest::<sha::Impl>();
+
+        assert_eq!(
+            Err(VerificationError::InvalidProof),
+            Proof::from_groth16(receipt).verify(&V3::v3_0(), IMAGE_ID, JOURNAL_DIGEST)
+        );
+    }
+
+    #[test]
+    fn reject_malformed_seal() {
+        let (params, mut receipt) = test_receipt(claim());
+        let ctx = V3::v3_0().with_groth16_verifier_parameters(params);
+        receipt.seal.pop();
+
+        assert_eq!(
+            Err(VerificationError::ReceiptFormatError),
+            receipt.verify_integrity_with_context(&ctx)
+        );
+    }
+
+    #[test]
+    fn reject_mismatched_verifier_parameters() {
+        let (params, receipt) = test_receipt(claim());
+        let expected = Groth16ReceiptVerifierParameters::v3_0().digest::<sha::Impl>();
+
+        assert_eq!(
+            Err(VerificationError::VerifierParametersMismatch {
+                expected,
+                received: params.digest::<sha::Impl>(),
+            }),
+            receipt.verify_integrity_with_context(&V3::v3_0())
+        );
+    }
+
+    #[test]
+    fn reject_without_groth16_parameters() {
+        let (_, receipt) = test_receipt(claim());
+        let ctx = V3::empty(
+            &crate::circuit::v3_0::CIRCUIT,
+            &crate::circuit::v3_0::recursive::CIRCUIT,
+        );
+
+        assert_eq!(
+            Err(VerificationError::VerifierParametersMissing),
+            receipt.verify_integrity_with_context(&ctx)
+        );
+    }
+}
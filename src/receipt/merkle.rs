@@ -26,6 +26,10 @@ use risc0_core_v1::field::baby_bear::BabyBear;
 use risc0_zkp_v1::core::{digest::Digest, hash::HashFn};
 use serde::{Deserialize, Serialize};
 
+/// Depth of the Merkle tree of the control IDs allowed in the recursion system.
+#[cfg(feature = "ethereum")]
+const ALLOWED_CODE_MERKLE_DEPTH: u32 = 8;
+
 /// Used to verify inclusion of a given recursion program in the committed set.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct MerkleProof {
@@ -65,4 +69,31 @@ impl MerkleProof {
         }
         cur
     }
+
+    /// Compute the inclusion proof of the leaf at `index` in the tree of the control IDs
+    /// `leaves`, padded with zero digests as the recursion system does.
+    #[cfg(feature = "ethereum")]
+    pub(crate) fn inclusion(leaves: &[Digest], index: u32, hashfn: &dyn HashFn<BabyBear>) -> Self {
+        let digests = (0..ALLOWED_CODE_MERKLE_DEPTH)
+            .map(|level| {
+                let sibling_start = ((index >> level) ^ 1) << level;
+                range_root(leaves, sibling_start, sibling_start + (1 << level), hashfn)
+            })
+            .collect();
+        Self { index, digests }
+    }
+}
+
+/// Root of the subtree of `leaves` over the `start..end` range.
+#[cfg(feature = "ethereum")]
+fn range_root(leaves: &[Digest], start: u32, end: u32, hashfn: &dyn HashFn<BabyBear>) -> Digest {
+    if start + 1 == end {
+        leaves.get(start as usize).copied().unwrap_or(Digest::ZERO)
+    } else {
+        let mid = (start + end) / 2;
+        *hashfn.hash_pair(
+            &range_root(leaves, start, mid, hashfn),
+            &range_root(leaves, mid, end, hashfn),
+        )
+    }
 }
@@ -16,7 +16,7 @@
 // limitations under the License.
 //
 
-use alloc::{collections::VecDeque, string::String, vec::Vec};
+use alloc::{collections::VecDeque, vec::Vec};
 use core::fmt::Debug;
 
 use risc0_binfmt_v1::{read_sha_halfs, tagged_struct, Digestible};
@@ -25,6 +25,7 @@ use risc0_zkp_v1::{
     adapter::{CircuitInfo, ProtocolInfo, PROOF_SYSTEM_INFO},
     core::{digest::Digest, hash::sha::Sha256},
     verify::VerificationError,
+    FRI_FOLD, INV_RATE, QUERIES,
 };
 
 use serde::{Deserialize, Serialize};
@@ -35,8 +36,72 @@ use crate::{
     receipt_claim::{MaybePruned, Unknown},
     sha,
     translate::Translate,
+    verifier::extract_po2::extract_segment_po2,
+    HashFunction, SegmentInfo, Verifier,
 };
 
+/// The po2 at which the recursion programs run.
+const RECURSION_PO2: usize = 18;
+
+/// Number of outputs of the recursion circuit of every supported version.
+pub(crate) const RECURSION_OUTPUT_SIZE: usize = 32;
+
+/// Number of words of the seal of a STARK of the recursion circuit, following the reads of the
+/// risc0-zkp verifier. The recursion programs always run at [RECURSION_PO2], so the layout only
+/// depends on the circuit: its `output_size`, the total number of columns of its code, data and
+/// accum groups and its number of taps.
+const fn recursion_seal_len(output_size: usize, columns: usize, taps: usize) -> usize {
+    const EXT_SIZE: usize = 4;
+    const DIGEST_WORDS: usize = 8;
+    const FRI_FOLD_PO2: usize = FRI_FOLD.ilog2() as usize;
+    const FRI_MIN_DEGREE: usize = 256;
+    const CHECK_SIZE: usize = INV_RATE * EXT_SIZE;
+
+    // The top layer of a Merkle tree, read once, is the widest one with at most QUERIES nodes:
+    // every query reads only the branch below it.
+    const fn top_layer(layers: usize) -> usize {
+        let mut top = 0;
+        while top + 1 < layers && (1 << (top + 1)) <= QUERIES {
+            top += 1;
+        }
+        top
+    }
+
+    let layers = RECURSION_PO2 + INV_RATE.ilog2() as usize;
+    // The circuit outputs and the po2, the top layers of the code, data, accum and check trees
+    // and the coefficients of the taps and of the check polynomial.
+    let mut len = output_size
+        + 1
+        + 4 * (1 << top_layer(layers)) * DIGEST_WORDS
+        + (taps + CHECK_SIZE) * EXT_SIZE;
+    // Every query opens a row of the four trees.
+    let mut query = columns + CHECK_SIZE + 4 * (layers - top_layer(layers)) * DIGEST_WORDS;
+    // Every FRI round folds the degree by FRI_FOLD with a tree of its own, opened by every query.
+    let mut degree = 1 << RECURSION_PO2;
+    let mut round_layers = layers;
+    while degree > FRI_MIN_DEGREE {
+        round_layers -= FRI_FOLD_PO2;
+        len += (1 << top_layer(round_layers)) * DIGEST_WORDS;
+        query += FRI_FOLD * EXT_SIZE + (round_layers - top_layer(round_layers)) * DIGEST_WORDS;
+        degree /= FRI_FOLD;
+    }
+    // The coefficients of the final FRI polynomial and the queries.
+    len + degree * EXT_SIZE + QUERIES * query
+}
+
+/// Number of words of the seal of a succinct receipt: 55 667. The recursion circuit of every
+/// supported version has 32 outputs, 163 columns (23 code, 128 data and 12 accum) and 643 taps,
+/// so the seal layout is fixed and doesn't depend on the proven execution.
+pub const SUCCINCT_SEAL_LEN: usize = recursion_seal_len(RECURSION_OUTPUT_SIZE, 23 + 128 + 12, 643);
+
+/// Number of words of the seal of a succinct receipt produced by the v1 recursion circuit: the
+/// same as [SUCCINCT_SEAL_LEN].
+pub const SUCCINCT_SEAL_LEN_V1: usize = SUCCINCT_SEAL_LEN;
+
+/// Number of words of the seal of a succinct receipt produced by the v2 and v3 recursion
+/// circuits: the same as [SUCCINCT_SEAL_LEN].
+pub const SUCCINCT_SEAL_LEN_V2: usize = SUCCINCT_SEAL_LEN;
+
 /// A succinct receipt, produced via recursion, proving the execution of the zkVM with a [STARK].
 ///
 /// Using recursion, a [CompositeReceipt][crate::CompositeReceipt] can be compressed to form a
@@ -66,7 +131,7 @@ where
     pub claim: MaybePruned<Claim>,
 
     /// Name of the hash function used to create this receipt.
-    pub hashfn: String,
+    pub hashfn: HashFunction,
 
     /// A digest of the verifier parameters that can be used to verify this receipt.
     ///
@@ -89,6 +154,9 @@ where
         &self,
         ctx: &impl VerifierContext,
     ) -> Result<(), VerificationError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("risc0::verify_succinct", hashfn = %self.hashfn).entered();
+
         let params = ctx
             .verifier_parameters()
             .succinct_verifier_parameters()
@@ -110,6 +178,16 @@ where
             });
         }
 
+        // Cheap guard before verifying the seal: the recursion circuit seal has a fixed size.
+        if !self.is_seal_expected_size(ctx) {
+            debug!(
+                "succinct receipt seal has {} words, expected {}",
+                self.seal.len(),
+                ctx.succinct_seal_len()
+            );
+            return Err(VerificationError::ReceiptFormatError);
+        }
+
         // Verify the receipt itself is correct, and therefore the encoded globals are
         // reliable.
         ctx.verify_succinct(
@@ -119,27 +197,9 @@ where
             params,
         )?;
 
-        // Extract the globals from the seal
-        let output_elems: &[BabyBearElem] = bytemuck::checked::cast_slice(&self.seal);
-        let mut seal_claim = VecDeque::new();
-        for elem in output_elems.iter().take(ctx.succinct_output_size()) {
-            seal_claim.push_back(elem.as_u32())
-        }
-
-        // Read the Poseidon2 control root digest from the first 16 words of the output.
-        // NOTE: Implemented recursion programs have two output slots, each of size 16 elems.
-        // A SHA2 digest is encoded as 16 half words. Poseidon digests are encoded in 8 elems,
-        // but are interspersed with padding to fill out the whole 16 elems.
-        let control_root: Digest = seal_claim
-            .drain(0..16)
-            .enumerate()
-            .filter_map(|(i, word)| (i & 1 == 0).then_some(word))
-            .collect::<Vec<_>>()
-            .try_into()
-            .map_err(|_| VerificationError::ReceiptFormatError)?;
-
+        let control_root = self.decode_control_root_from_seal()?;
         if control_root != params.inner_control_root.unwrap_or(params.control_root) {
-            log::debug!(
+            debug!(
                 "succinct receipt does not match the expected control root: decoded: {:#?}, expected: {:?}",
                 control_root,
                 params.inner_control_root.unwrap_or(params.control_root),
@@ -150,10 +210,9 @@ where
         }
 
         // Verify the output hash matches that data
-        let output_hash =
-            read_sha_halfs(&mut seal_claim).map_err(|_| VerificationError::ReceiptFormatError)?;
+        let output_hash = self.decode_output_hash_from_seal()?;
         if output_hash != self.claim.digest::<sha::Impl>() {
-            log::debug!(
+            debug!(
                 "succinct receipt claim does not match the output digest: claim: {:#?}, digest expected: {output_hash:?}",
                 self.claim,
             );
@@ -163,16 +222,84 @@ where
         Ok(())
     }
 
+    /// Return `true` if the seal has the fixed size of the seals produced by the recursion
+    /// circuit of the given context.
+    pub fn is_seal_expected_size(&self, ctx: &impl VerifierContext) -> bool {
+        self.seal.len() == ctx.succinct_seal_len()
+    }
+
+    /// Decode the control root from the first output slot of the seal, without verifying the
+    /// seal: useful to identify the recursion programs (and so the zkVM version) that generated
+    /// this receipt.
+    pub fn decode_control_root_from_seal(&self) -> Result<Digest, VerificationError> {
+        // NOTE: Implemented recursion programs have two output slots, each of size 16 elems.
+        // A SHA2 digest is encoded as 16 half words. Poseidon digests are encoded in 8 elems,
+        // but are interspersed with padding to fill out the whole 16 elems.
+        self.decode_output_slot(0)?
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, word)| (i & 1 == 0).then_some(word))
+            .collect::<Vec<_>>()
+            .try_into()
+            .map_err(|_| VerificationError::ReceiptFormatError)
+    }
+
+    /// Decode the SHA2 digest of the claim from the second output slot of the seal, without
+    /// verifying the seal.
+    pub fn decode_output_hash_from_seal(&self) -> Result<Digest, VerificationError> {
+        read_sha_halfs(&mut self.decode_output_slot(1)?)
+            .map_err(|_| VerificationError::ReceiptFormatError)
+    }
+
+    fn decode_output_slot(&self, slot: usize) -> Result<VecDeque<u32>, VerificationError> {
+        const SLOT_ELEMS: usize = 16;
+        let words = self
+            .seal
+            .get(slot * SLOT_ELEMS..(slot + 1) * SLOT_ELEMS)
+            .ok_or(VerificationError::ReceiptFormatError)?;
+        let elems: &[BabyBearElem] = bytemuck::checked::try_cast_slice(words)
+            .map_err(|_| VerificationError::ReceiptFormatError)?;
+        Ok(elems.iter().map(|elem| elem.as_u32()).collect())
+    }
+
+    /// Check that the `verifier_parameters` digest of this receipt matches the succinct verifier
+    /// parameters of `verifier`: a cheap check to run before the seal verification.
+    pub fn verifier_parameters_matches(&self, verifier: &impl Verifier) -> bool {
+        verifier
+            .succinct_verifier_parameters()
+            .is_some_and(|params| params.digest::<sha::Impl>() == self.verifier_parameters)
+    }
+
     /// Return the seal for this receipt, as a vector of bytes.
     pub fn get_seal_bytes(&self) -> Vec<u8> {
         self.seal.iter().flat_map(|x| x.to_le_bytes()).collect()
     }
 
+    /// Return the seal for this receipt, without copying it.
+    pub fn seal_words(&self) -> &[u32] {
+        &self.seal
+    }
+
+    /// Return the seal for this receipt as bytes, without copying it.
+    pub fn seal_bytes(&self) -> &[u8] {
+        bytemuck::cast_slice(&self.seal)
+    }
+
     /// Number of bytes used by the seal for this receipt.
     pub fn seal_size(&self) -> usize {
         core::mem::size_of_val(self.seal.as_slice())
     }
 
+    pub(crate) fn succinct_info_at(
+        &self,
+        output_size: usize,
+    ) -> Result<SegmentInfo, VerificationError> {
+        extract_segment_po2(&self.seal, output_size).map(|po2| SegmentInfo {
+            hash: self.hashfn.as_str().into(),
+            po2,
+        })
+    }
+
     /// Prunes the claim, retaining its digest, and converts into a [SuccinctReceipt] with an unknown
     /// claim type. Can be used to get receipts of a uniform type across heterogeneous claims.
     pub fn into_unknown(self) -> SuccinctReceipt<Unknown> {
@@ -185,6 +312,35 @@ where
             control_inclusion_proof: self.control_inclusion_proof,
         }
     }
+
+    /// Like [SuccinctReceipt::into_unknown] but without consuming this receipt: all the fields
+    /// but the claim are cloned.
+    pub fn to_unknown_ref(&self) -> SuccinctReceipt<Unknown> {
+        SuccinctReceipt {
+            claim: MaybePruned::Pruned(self.claim.digest::<sha::Impl>()),
+            seal: self.seal.clone(),
+            control_id: self.control_id,
+            hashfn: self.hashfn,
+            verifier_parameters: self.verifier_parameters,
+            control_inclusion_proof: self.control_inclusion_proof.clone(),
+        }
+    }
+
+    /// Replace the claim of this receipt with `c`, possibly of a different type, keeping all the
+    /// other fields. The receipt verifies only if `c` has the same digest of the original claim.
+    pub fn with_claim<NewClaim>(self, c: MaybePruned<NewClaim>) -> SuccinctReceipt<NewClaim>
+    where
+        NewClaim: Digestible + Debug + Clone + Serialize,
+    {
+        SuccinctReceipt {
+            claim: c,
+            seal: self.seal,
+            control_id: self.control_id,
+            hashfn: self.hashfn,
+            verifier_parameters: self.verifier_parameters,
+            control_inclusion_proof: self.control_inclusion_proof,
+        }
+    }
 }
 
 /// Verifier parameters used to verify a [SuccinctReceipt].
@@ -221,6 +377,30 @@ impl Digestible for SuccinctReceiptVerifierParameters {
 }
 
 impl SuccinctReceiptVerifierParameters {
+    /// Multi-line diagnostic description of these parameters: the control roots and the
+    /// protocol infos.
+    pub fn dump(&self) -> alloc::string::String {
+        use core::fmt::Write;
+
+        let mut out = alloc::string::String::new();
+        let _ = writeln!(out, "control_root: {}", self.control_root);
+        let _ = match self.inner_control_root {
+            Some(root) => writeln!(out, "inner_control_root: {root}"),
+            None => writeln!(out, "inner_control_root: none"),
+        };
+        let _ = writeln!(out, "circuit_info: {}", self.circuit_info);
+        let _ = writeln!(out, "proof_system_info: {}", self.proof_system_info);
+        out
+    }
+
+    /// Build a custom set of parameters (e.g. for a custom recursion program). Fields that are not
+    /// set keep the [latest](SuccinctReceiptVerifierParameters::v3_0) values.
+    pub fn builder() -> SuccinctReceiptVerifierParametersBuilder {
+        SuccinctReceiptVerifierParametersBuilder {
+            params: Self::v3_0(),
+        }
+    }
+
     /// v1_0 set of parameters used to verify a [SuccinctReceipt].
     pub fn v1_0() -> Self {
         use crate::circuit::v1_0::recursive as circuit;
@@ -329,10 +509,51 @@ impl SuccinctReceiptVerifierParameters {
     }
 }
 
+/// Builder for [SuccinctReceiptVerifierParameters]: see
+/// [SuccinctReceiptVerifierParameters::builder].
+#[derive(Clone, Debug)]
+pub struct SuccinctReceiptVerifierParametersBuilder {
+    params: SuccinctReceiptVerifierParameters,
+}
+
+impl SuccinctReceiptVerifierParametersBuilder {
+    /// Set the control root used to verify the control ID of the recursion program.
+    pub fn control_root(mut self, r: Digest) -> Self {
+        self.params.control_root = r;
+        self
+    }
+
+    /// Set the control root used to verify the recursive control root in the receipt output.
+    pub fn inner_control_root(mut self, r: Digest) -> Self {
+        self.params.inner_control_root = Some(r);
+        self
+    }
+
+    /// Set the proof system protocol info.
+    pub fn proof_system_info(mut self, p: ProtocolInfo) -> Self {
+        self.params.proof_system_info = p;
+        self
+    }
+
+    /// Set the circuit protocol info.
+    pub fn circuit_info(mut self, c: ProtocolInfo) -> Self {
+        self.params.circuit_info = c;
+        self
+    }
+
+    /// Build the parameters.
+    pub fn build(self) -> SuccinctReceiptVerifierParameters {
+        self.params
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
-    use super::SuccinctReceiptVerifierParameters;
+    use super::{
+        SuccinctReceiptVerifierParameters, SUCCINCT_SEAL_LEN, SUCCINCT_SEAL_LEN_V1,
+        SUCCINCT_SEAL_LEN_V2,
+    };
     use crate::sha::Digestible;
     use risc0_zkp_v1::core::digest::{digest, Digest};
     use rstest::rstest;
@@ -345,10 +566,200 @@ mod tests {
     #[case::v1_0(SuccinctReceiptVerifierParameters::v1_0().digest(), digest!("f171d19df8f27878677080c5e4c38ed2655f5f54302468ce805594a4b3e38104"))]
     #[case::v1_1(SuccinctReceiptVerifierParameters::v1_1().digest(), digest!("71023badfee05b76de871c5cc5a95cbedf50395e3634ffb9f3192950b16a77ae"))]
     #[case::v1_2(SuccinctReceiptVerifierParameters::v1_2().digest(), digest!("21a829e931cda9f34723dc77d947efe264771fea83bc495b3903014d0fe50d57"))]
+    #[case::v2_0(SuccinctReceiptVerifierParameters::v2_0().digest(), digest!("68ecff4bad7b3348ca3ac642e852b8d66b7158307f7d2a001c13887698fe6019"))]
+    #[case::v2_1(SuccinctReceiptVerifierParameters::v2_1().digest(), digest!("bb81f1400f9a2b28b457f9d686c28ec3ffeece08b0b00f1b0d7643a1cc471115"))]
+    #[case::v2_2(SuccinctReceiptVerifierParameters::v2_2().digest(), digest!("6da21180b0fb9de482aed36931a29b10feeb64fb96de49f2f2e5e119e2bb8cd8"))]
+    #[case::v2_3(SuccinctReceiptVerifierParameters::v2_3().digest(), digest!("6da21180b0fb9de482aed36931a29b10feeb64fb96de49f2f2e5e119e2bb8cd8"))]
+    #[case::v3_0(SuccinctReceiptVerifierParameters::v3_0().digest(), digest!("ece5e9b8ae2cd6ea6b1827b464ff0348f9a7f4decd269c0087fdfd75098da013"))]
     fn succinct_receipt_verifier_parameters_is_stable(
         #[case] computed: Digest,
         #[case] hardcoded: Digest,
     ) {
         assert_eq!(computed, hardcoded);
     }
+
+    #[test]
+    fn builder_sets_all_fields() {
+        let v1_2 = SuccinctReceiptVerifierParameters::v1_2();
+        let root = Digest::from([1_u32; 8]);
+        let inner_root = Digest::from([2_u32; 8]);
+
+        let params = SuccinctReceiptVerifierParameters::builder()
+            .control_root(root)
+            .inner_control_root(inner_root)
+            .proof_system_info(v1_2.proof_system_info)
+            .circuit_info(v1_2.circuit_info)
+            .build();
+
+        assert_eq!(root, params.control_root);
+        assert_eq!(Some(inner_root), params.inner_control_root);
+        assert_eq!(v1_2.proof_system_info, params.proof_system_info);
+        assert_eq!(v1_2.circuit_info, params.circuit_info);
+    }
+
+    #[test]
+    fn builder_defaults_to_latest_parameters() {
+        assert_eq!(
+            SuccinctReceiptVerifierParameters::v3_0().digest(),
+            SuccinctReceiptVerifierParameters::builder()
+                .build()
+                .digest()
+        );
+    }
+
+    #[test]
+    fn borrowed_seal_accessors() {
+        let proof: crate::Proof = ciborium::from_reader(
+            std::fs::File::open("./resources/receipts/1.2.0-1.2.0/succinct_16.bin").unwrap(),
+        )
+        .unwrap();
+        let succinct = proof.inner.succinct().unwrap();
+
+        assert_eq!(succinct.get_seal_bytes(), succinct.seal_bytes());
+        assert_eq!(succinct.seal.as_slice(), succinct.seal_words());
+    }
+
+    fn succinct() -> super::SuccinctReceipt<crate::receipt_claim::ReceiptClaim> {
+        let proof: crate::Proof = ciborium::from_reader(
+            std::fs::File::open("./resources/receipts/1.2.0-1.2.0/succinct_16.bin").unwrap(),
+        )
+        .unwrap();
+        proof.inner.succinct().unwrap().clone()
+    }
+
+    #[test]
+    fn to_unknown_ref_matches_into_unknown() {
+        let succinct = succinct();
+
+        let borrowed = succinct.to_unknown_ref();
+        let owned = succinct.clone().into_unknown();
+
+        assert_eq!(owned.claim.digest(), borrowed.claim.digest());
+        assert_eq!(succinct.claim.digest(), borrowed.claim.digest());
+        assert_eq!(owned.seal, borrowed.seal);
+        assert_eq!(owned.control_id, borrowed.control_id);
+        assert_eq!(owned.hashfn, borrowed.hashfn);
+        assert_eq!(owned.verifier_parameters, borrowed.verifier_parameters);
+        assert_eq!(
+            owned.control_inclusion_proof,
+            borrowed.control_inclusion_proof
+        );
+    }
+
+    #[test]
+    fn with_claim_keeps_the_other_fields() {
+        let succinct = succinct();
+        let digest = succinct.claim.digest();
+
+        let unknown = succinct
+            .clone()
+            .with_claim::<crate::receipt_claim::Unknown>(
+                crate::receipt_claim::MaybePruned::Pruned(digest),
+            );
+
+        assert_eq!(digest, unknown.claim.digest());
+        assert_eq!(succinct.seal, unknown.seal);
+        assert_eq!(succinct.control_id, unknown.control_id);
+        assert_eq!(succinct.hashfn, unknown.hashfn);
+        assert_eq!(succinct.verifier_parameters, unknown.verifier_parameters);
+        assert_eq!(
+            succinct.control_inclusion_proof,
+            unknown.control_inclusion_proof
+        );
+        assert_eq!(
+            Ok(()),
+            unknown.verify_integrity_with_context(&crate::context::v1::V1::v1_2())
+        );
+    }
+
+    #[test]
+    fn v1_2_dump() {
+        let params = SuccinctReceiptVerifierParameters::v1_2();
+
+        let dump = params.dump();
+
+        assert!(dump.contains(&std::format!("control_root: {}", params.control_root)));
+        assert!(dump.contains("inner_control_root: none"));
+        assert!(dump.contains("circuit_info: RECURSION:rev1v1"));
+    }
+
+    fn succinct_at(dir: &str) -> super::SuccinctReceipt<crate::receipt_claim::ReceiptClaim> {
+        let path = std::format!("./resources/receipts/{dir}/succinct_16.bin");
+        let proof: crate::Proof =
+            ciborium::from_reader(std::fs::File::open(path).unwrap()).unwrap();
+        proof.inner.succinct().unwrap().clone()
+    }
+
+    #[rstest]
+    #[case::v1_0(crate::context::v1::V1::v1_0(), "1.0.3-1.0.5", SUCCINCT_SEAL_LEN_V1)]
+    #[case::v1_1(crate::context::v1::V1::v1_1(), "1.1.3-1.1.1", SUCCINCT_SEAL_LEN_V1)]
+    #[case::v1_2(crate::context::v1::V1::v1_2(), "1.2.0-1.2.0", SUCCINCT_SEAL_LEN_V1)]
+    #[case::v2_0(crate::context::v2::V2::v2_0(), "2.0.0-2.0.0", SUCCINCT_SEAL_LEN_V2)]
+    #[case::v2_1(crate::context::v2::V2::v2_1(), "2.1.0-2.1.0", SUCCINCT_SEAL_LEN_V2)]
+    #[case::v2_2(crate::context::v2::V2::v2_2(), "2.2.0-2.2.0", SUCCINCT_SEAL_LEN_V2)]
+    #[case::v2_3(crate::context::v2::V2::v2_3(), "2.3.0-2.3.0", SUCCINCT_SEAL_LEN_V2)]
+    #[case::v3_0(crate::context::v3::V3::v3_0(), "3.0.0-3.0.0", SUCCINCT_SEAL_LEN_V2)]
+    fn seal_of_unexpected_size_is_rejected(
+        #[case] ctx: impl crate::context::VerifierContext,
+        #[case] dir: &str,
+        #[case] expected_len: usize,
+        #[values(-1, 1)] delta: isize,
+    ) {
+        let mut succinct = succinct_at(dir);
+        assert_eq!(expected_len, succinct.seal.len());
+        assert!(succinct.is_seal_expected_size(&ctx));
+
+        succinct
+            .seal
+            .resize(expected_len.checked_add_signed(delta).unwrap(), 0);
+
+        assert!(!succinct.is_seal_expected_size(&ctx));
+        assert_eq!(
+            Err(risc0_zkp_v1::verify::VerificationError::ReceiptFormatError),
+            succinct.verify_integrity_with_context(&ctx)
+        );
+    }
+
+    macro_rules! recursion_layout {
+        ($zkp:ident, $version:ident) => {{
+            use crate::circuit::$version::recursive::{CircuitImpl, CIRCUIT};
+            use $zkp::adapter::{
+                CircuitInfo, TapsProvider, REGISTER_GROUP_ACCUM, REGISTER_GROUP_CODE,
+                REGISTER_GROUP_DATA,
+            };
+            let taps = CIRCUIT.get_taps();
+            (
+                <CircuitImpl as CircuitInfo>::OUTPUT_SIZE,
+                [
+                    REGISTER_GROUP_CODE,
+                    REGISTER_GROUP_DATA,
+                    REGISTER_GROUP_ACCUM,
+                ]
+                .map(|group| taps.group_size(group)),
+                taps.tap_size(),
+            )
+        }};
+    }
+
+    #[rstest]
+    #[case::v1_0(recursion_layout!(risc0_zkp_v1, v1_0))]
+    #[case::v1_1(recursion_layout!(risc0_zkp_v1, v1_1))]
+    #[case::v1_2(recursion_layout!(risc0_zkp_v1, v1_2))]
+    #[case::v2_0(recursion_layout!(risc0_zkp_v2, v2_0))]
+    #[case::v2_1(recursion_layout!(risc0_zkp_v2, v2_1))]
+    #[case::v2_2(recursion_layout!(risc0_zkp_v2, v2_2))]
+    #[case::v2_3(recursion_layout!(risc0_zkp_v2, v2_3))]
+    #[case::v3_0(recursion_layout!(risc0_zkp_v3, v3_0))]
+    fn succinct_seal_len_matches_the_recursion_circuit(
+        #[case] (output_size, groups, taps): (usize, [usize; 3], usize),
+    ) {
+        assert_eq!(
+            (super::RECURSION_OUTPUT_SIZE, [23, 128, 12], 643),
+            (output_size, groups, taps)
+        );
+        assert_eq!(
+            SUCCINCT_SEAL_LEN,
+            super::recursion_seal_len(output_size, groups.iter().sum(), taps)
+        );
+    }
 }
@@ -0,0 +1,106 @@
+// Copyright Copyright 2024, Horizen Labs, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Conversions from and to the [`risc0_zkvm`] types. Available only with the
+//! `risc0-zkvm-compat` feature.
+//!
+//! ```
+//! # use risc0_verifier::{v1_2, Journal, Proof, Verifier};
+//! # #[derive(serde::Deserialize)]
+//! # struct Case { receipt_path: std::path::PathBuf, vk: [u32; 8] }
+//! # let case: Case = serde_json::from_reader(std::fs::File::open(
+//! #     "./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json").unwrap()).unwrap();
+//! # let image_id = case.vk;
+//! // The receipt generated by the prover.
+//! let receipt: risc0_zkvm::Receipt =
+//!     ciborium::from_reader(std::fs::File::open(case.receipt_path).unwrap()).unwrap();
+//!
+//! let journal = Journal::from(receipt.journal.clone());
+//! let proof = Proof::try_from(receipt).unwrap();
+//!
+//! v1_2().verify(image_id.into(), proof, journal).unwrap();
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use super::{Journal, Proof};
+
+/// Error returned when a conversion between [`risc0_zkvm`] receipts and [Proof] fails.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ReceiptConversionError {
+    /// The source receipt cannot be serialized.
+    Serialization,
+    /// The serialized receipt cannot be deserialized in the target type.
+    Deserialization,
+    /// The inner receipt type (e.g. fake receipts without the `dev-mode` feature) is not supported.
+    UnsupportedInnerReceipt,
+}
+
+impl fmt::Display for ReceiptConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Serialization => write!(f, "failed to serialize receipt"),
+            Self::Deserialization => write!(f, "failed to deserialize receipt"),
+            Self::UnsupportedInnerReceipt => write!(f, "unsupported inner receipt type"),
+        }
+    }
+}
+
+fn convert<S: serde::Serialize, D: serde::de::DeserializeOwned>(
+    source: &S,
+) -> Result<D, ReceiptConversionError> {
+    let mut buffer = Vec::new();
+    ciborium::into_writer(source, &mut buffer)
+        .map_err(|_| ReceiptConversionError::Serialization)?;
+    ciborium::from_reader(buffer.as_slice()).map_err(|_| ReceiptConversionError::Deserialization)
+}
+
+impl TryFrom<risc0_zkvm::Receipt> for Proof {
+    type Error = ReceiptConversionError;
+
+    fn try_from(receipt: risc0_zkvm::Receipt) -> Result<Self, Self::Error> {
+        match receipt.inner {
+            risc0_zkvm::InnerReceipt::Composite(_)
+            | risc0_zkvm::InnerReceipt::Succinct(_)
+            | risc0_zkvm::InnerReceipt::Groth16(_) => convert(&receipt),
+            #[cfg(feature = "dev-mode")]
+            risc0_zkvm::InnerReceipt::Fake(_) => convert(&receipt),
+            _ => Err(ReceiptConversionError::UnsupportedInnerReceipt),
+        }
+    }
+}
+
+impl TryFrom<Proof> for risc0_zkvm::InnerReceipt {
+    type Error = ReceiptConversionError;
+
+    fn try_from(proof: Proof) -> Result<Self, Self::Error> {
+        convert(&proof.inner)
+    }
+}
+
+impl From<risc0_zkvm::Journal> for Journal {
+    fn from(journal: risc0_zkvm::Journal) -> Self {
+        Self::new(journal.bytes)
+    }
+}
+
+impl From<Journal> for risc0_zkvm::Journal {
+    fn from(journal: Journal) -> Self {
+        Self::new(journal.bytes)
+    }
+}
@@ -29,13 +29,17 @@ use anyhow::{anyhow, ensure};
 use core::{fmt, ops::Deref};
 
 // use anyhow::{anyhow, ensure};
+pub use risc0_binfmt_v1::ExitCode;
 use risc0_binfmt_v1::{
     read_sha_halfs, tagged_list, tagged_list_cons, tagged_struct, write_sha_halfs,
-    DecodeError as SysDecodeError, Digestible, ExitCode, InvalidExitCodeError, SystemState,
+    DecodeError as SysDecodeError, Digestible, InvalidExitCodeError, SystemState,
 };
-use risc0_zkp_v1::core::{
-    digest::Digest,
-    hash::{sha, sha::Sha256},
+use risc0_zkp_v1::{
+    core::{
+        digest::Digest,
+        hash::{sha, sha::Sha256},
+    },
+    verify::VerificationError,
 };
 use serde::{Deserialize, Serialize};
 
@@ -113,6 +117,20 @@ impl ReceiptClaim {
         }
     }
 
+    /// Like [ReceiptClaim::ok], but with the journal pruned to its SHA-256 digest: the claim that
+    /// a proof of a successful execution committing `journal` should have.
+    pub fn ok_with_journal_bytes(image_id: impl Into<Digest>, journal: &[u8]) -> ReceiptClaim {
+        Self::ok(
+            image_id,
+            MaybePruned::Pruned(*sha::Impl::hash_bytes(journal)),
+        )
+    }
+
+    /// Like [ReceiptClaim::ok], but keeping the whole journal in the claim output.
+    pub fn ok_with_journal_vec(image_id: impl Into<Digest>, journal: Vec<u8>) -> ReceiptClaim {
+        Self::ok(image_id, MaybePruned::Value(journal))
+    }
+
     /// Decode a [ReceiptClaim] from a list of [u32]'s
     pub fn decode(flat: &mut VecDeque<u32>) -> Result<Self, DecodeError> {
         let input = read_sha_halfs(flat)?;
@@ -143,6 +161,209 @@ impl ReceiptClaim {
         write_sha_halfs(flat, &self.output.digest::<sha::Impl>());
         Ok(())
     }
+
+    /// Digest of the [SystemState] just before execution has begun.
+    pub fn pre_state_digest(&self) -> Digest {
+        self.pre.digest::<sha::Impl>()
+    }
+
+    /// Digest of the [SystemState] just after execution has completed.
+    pub fn post_state_digest(&self) -> Digest {
+        self.post.digest::<sha::Impl>()
+    }
+
+    /// Digest of the input of the execution: available even if the input is pruned.
+    pub fn input_digest(&self) -> Digest {
+        self.input.digest::<sha::Impl>()
+    }
+
+    /// Return this claim with the input replaced by the given digest.
+    pub fn with_input_digest(mut self, d: Digest) -> Self {
+        self.input = MaybePruned::Pruned(d);
+        self
+    }
+
+    /// Return `true` if the execution started from the given image id, i.e. it's the digest of
+    /// the pre-state.
+    pub fn pre_matches(&self, image_id: Digest) -> bool {
+        self.pre_state_digest() == image_id
+    }
+
+    /// Return `true` if the execution halted, whatever the user exit code.
+    pub fn post_is_halted(&self) -> bool {
+        matches!(self.exit_code, ExitCode::Halted(_))
+    }
+
+    /// Digest of the journal committed in the output, or `None` if the output is pruned or
+    /// absent. A pruned journal still has a known digest.
+    pub fn output_journal_digest(&self) -> Option<Digest> {
+        let output = self.output.as_value().ok()?.as_ref()?;
+        Some(output.journal_digest())
+    }
+
+    /// Digest of the assumptions committed in the output, or `None` if the output is pruned or
+    /// absent.
+    pub fn output_assumptions_digest(&self) -> Option<Digest> {
+        let output = self.output.as_value().ok()?.as_ref()?;
+        Some(output.assumptions.digest::<sha::Impl>())
+    }
+
+    /// Replace the output with its digest. The claim digest doesn't change; an absent output is
+    /// left as is.
+    pub fn prune_output(mut self) -> Self {
+        if let MaybePruned::Value(Some(_)) = self.output {
+            self.output = MaybePruned::Pruned(self.output.digest::<sha::Impl>());
+        }
+        self
+    }
+
+    /// Replace the input with its digest. The claim digest doesn't change; an absent input is
+    /// left as is.
+    pub fn prune_input(mut self) -> Self {
+        if let MaybePruned::Value(Some(_)) = self.input {
+            self.input = MaybePruned::Pruned(self.input.digest::<sha::Impl>());
+        }
+        self
+    }
+
+    /// The digests of the claim fields, in the order they are hashed in the claim digest:
+    /// `[input, pre, post, output]`.
+    pub fn to_digest_tuple(&self) -> [Digest; 4] {
+        [
+            self.input.digest::<sha::Impl>(),
+            self.pre.digest::<sha::Impl>(),
+            self.post.digest::<sha::Impl>(),
+            self.output.digest::<sha::Impl>(),
+        ]
+    }
+
+    /// Build a fully pruned [ReceiptClaim] from the digests returned by
+    /// [ReceiptClaim::to_digest_tuple] and the exit code: it has the same digest of the original
+    /// claim.
+    pub fn from_digest_tuple(digests: [Digest; 4], exit_code: ExitCode) -> ReceiptClaim {
+        let [input, pre, post, output] = digests;
+        Self {
+            pre: MaybePruned::Pruned(pre),
+            post: MaybePruned::Pruned(post),
+            exit_code,
+            input: MaybePruned::Pruned(input),
+            output: MaybePruned::Pruned(output),
+        }
+    }
+
+    /// Return `true` if this claim starts from the state where `prev` ended, i.e. the two
+    /// claims are consecutive segments of the same continuation.
+    pub fn is_continuation_of(&self, prev: &ReceiptClaim) -> bool {
+        prev.post_state_digest() == self.pre_state_digest()
+    }
+
+    /// Return `true` if the execution was split by the system to continue in the next segment.
+    pub fn is_system_split(&self) -> bool {
+        self.exit_code == ExitCode::SystemSplit
+    }
+
+    /// Return `true` if the two claims have the same digest: unlike a structural comparison,
+    /// claims with the same content but pruned in different ways match.
+    pub fn claims_match(&self, other: &ReceiptClaim) -> bool {
+        self.digest::<sha::Impl>() == other.digest::<sha::Impl>()
+    }
+
+    /// Check that the execution ended normally, i.e. with `Halted(0)`, otherwise return
+    /// [VerificationError::UnexpectedExitCode].
+    pub fn check_halted_ok(&self) -> Result<(), VerificationError> {
+        match self.exit_code {
+            ExitCode::Halted(0) => Ok(()),
+            _ => Err(VerificationError::UnexpectedExitCode),
+        }
+    }
+
+    /// Check that the execution started from the given image id, otherwise return
+    /// [VerificationError::ImageVerificationError].
+    pub fn check_pre_matches(&self, image_id: Digest) -> Result<(), VerificationError> {
+        if self.pre_state_digest() != image_id {
+            return Err(VerificationError::ImageVerificationError);
+        }
+        Ok(())
+    }
+}
+
+/// Builder of fully pruned [ReceiptClaim]s, e.g. to compute the claim digest that a proof should
+/// commit to. By default it builds the claim of a segment split by the system, with all the
+/// other digests zeroed but the post state, which is the digest of the zeroed [SystemState] that
+/// a terminated execution ends in (as in [ReceiptClaim::ok]).
+#[derive(Clone, Debug)]
+pub struct ReceiptClaimBuilder {
+    pre: Digest,
+    post: Digest,
+    exit_code: ExitCode,
+    input_digest: Digest,
+    output_digest: Digest,
+}
+
+impl Default for ReceiptClaimBuilder {
+    fn default() -> Self {
+        Self {
+            pre: Digest::ZERO,
+            post: SystemState {
+                pc: 0,
+                merkle_root: Digest::ZERO,
+            }
+            .digest::<sha::Impl>(),
+            exit_code: ExitCode::SystemSplit,
+            input_digest: Digest::ZERO,
+            output_digest: Digest::ZERO,
+        }
+    }
+}
+
+impl ReceiptClaimBuilder {
+    /// Set the digest of the pre state, i.e. the image id.
+    pub fn pre(mut self, d: Digest) -> Self {
+        self.pre = d;
+        self
+    }
+
+    /// Set the digest of the post state.
+    pub fn post(mut self, d: Digest) -> Self {
+        self.post = d;
+        self
+    }
+
+    /// Set the exit code to `Halted(code)`.
+    pub fn halted(mut self, code: u32) -> Self {
+        self.exit_code = ExitCode::Halted(code);
+        self
+    }
+
+    /// Set the exit code to `Paused(code)`.
+    pub fn paused(mut self, code: u32) -> Self {
+        self.exit_code = ExitCode::Paused(code);
+        self
+    }
+
+    /// Set the digest of the input.
+    pub fn input_digest(mut self, d: Digest) -> Self {
+        self.input_digest = d;
+        self
+    }
+
+    /// Set the output to the given journal digest with no assumptions.
+    pub fn journal_digest(mut self, d: Digest) -> Self {
+        self.output_digest = MaybePruned::Value(Some(Output {
+            journal: MaybePruned::Pruned(d),
+            assumptions: MaybePruned::Pruned(Digest::ZERO),
+        }))
+        .digest::<sha::Impl>();
+        self
+    }
+
+    /// Build the claim: all its fields are pruned.
+    pub fn build(self) -> ReceiptClaim {
+        ReceiptClaim::from_digest_tuple(
+            [self.input_digest, self.pre, self.post, self.output_digest],
+            self.exit_code,
+        )
+    }
 }
 
 impl Digestible for ReceiptClaim {
@@ -162,6 +383,40 @@ impl Digestible for ReceiptClaim {
     }
 }
 
+impl fmt::Display for ReceiptClaim {
+    /// Single line summary of the [ReceiptClaim], with shortened digests.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ReceiptClaim {{ pre: {}, post: {}, exit: {:?}, output: ",
+            ShortDigest(self.pre_state_digest()),
+            ShortDigest(self.post_state_digest()),
+            self.exit_code,
+        )?;
+        match &self.output {
+            MaybePruned::Value(Some(output)) => match output.journal_bytes() {
+                Some(journal) => write!(f, "Some({}B)", journal.len())?,
+                None => write!(f, "Some(Pruned)")?,
+            },
+            MaybePruned::Value(None) => write!(f, "None")?,
+            MaybePruned::Pruned(_) => write!(f, "Pruned")?,
+        }
+        write!(f, " }}")
+    }
+}
+
+/// Display just the first 6 bytes of a [Digest], in hex.
+pub(crate) struct ShortDigest(pub(crate) Digest);
+
+impl fmt::Display for ShortDigest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for b in &self.0.as_bytes()[..6] {
+            write!(f, "{b:02x}")?;
+        }
+        write!(f, "...")
+    }
+}
+
 /// Error returned when decoding [ReceiptClaim] fails.
 #[derive(Debug, Copy, Clone)]
 pub enum DecodeError {
@@ -250,6 +505,36 @@ pub struct Output {
     pub assumptions: MaybePruned<Assumptions>,
 }
 
+impl Output {
+    /// The journal bytes, or `None` if the journal is pruned.
+    pub fn journal_bytes(&self) -> Option<&[u8]> {
+        self.journal.as_value().ok().map(|v| v.as_slice())
+    }
+
+    /// The journal digest: available even if the journal is pruned.
+    pub fn journal_digest(&self) -> Digest {
+        self.journal.digest::<sha::Impl>()
+    }
+
+    /// Return `true` if there are no assumptions, also when they are pruned.
+    pub fn is_assumptions_empty(&self) -> bool {
+        self.assumptions.is_empty()
+    }
+
+    /// The assumptions of this output, in order: fail if the list, or one of its assumptions, is
+    /// pruned. An empty list is returned even if it's pruned.
+    pub fn assumptions_list(&self) -> Result<Vec<Assumption>, PrunedValueError> {
+        if self.is_assumptions_empty() {
+            return Ok(Vec::new());
+        }
+        self.assumptions
+            .as_value()?
+            .iter()
+            .map(|a| a.as_value().cloned())
+            .collect()
+    }
+}
+
 impl Digestible for Output {
     /// Hash the [Output] to get a digest of the struct.
     fn digest<S: Sha256>(&self) -> Digest {
@@ -261,6 +546,24 @@ impl Digestible for Output {
     }
 }
 
+impl fmt::Display for Output {
+    /// Single line summary of the [Output]: the journal size and the number of assumptions, or
+    /// their shortened digests if they are pruned.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Output {{ journal: ")?;
+        match self.journal_bytes() {
+            Some(journal) => write!(f, "{}B", journal.len())?,
+            None => write!(f, "Pruned({})", ShortDigest(self.journal_digest()))?,
+        }
+        write!(f, ", assumptions: ")?;
+        match &self.assumptions {
+            MaybePruned::Value(assumptions) => write!(f, "{}", assumptions.0.len())?,
+            MaybePruned::Pruned(digest) => write!(f, "Pruned({})", ShortDigest(*digest))?,
+        }
+        write!(f, " }}")
+    }
+}
+
 /// An [assumption] made in the course of proving program execution.
 ///
 /// Assumptions are generated when the guest makes a recursive verification call. Each assumption
@@ -289,6 +592,21 @@ pub struct Assumption {
     pub control_root: Digest,
 }
 
+impl Assumption {
+    /// A self-composed assumption on `claim`, i.e. with the all zeroes control root.
+    pub fn self_composed(claim: Digest) -> Assumption {
+        Assumption {
+            claim,
+            control_root: Digest::ZERO,
+        }
+    }
+
+    /// Return `true` if this assumption is self-composed (see [Assumption::control_root]).
+    pub fn is_self_composed(&self) -> bool {
+        self.control_root == Digest::ZERO
+    }
+}
+
 impl Digestible for Assumption {
     /// Hash the [Assumption] to get a digest of the struct.
     fn digest<S: Sha256>(&self) -> Digest {
@@ -296,12 +614,34 @@ impl Digestible for Assumption {
     }
 }
 
+impl fmt::Display for Assumption {
+    /// Single line summary of the [Assumption], with shortened digests.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Assumption {{ claim: {}, control_root: {} }}",
+            ShortDigest(self.claim),
+            ShortDigest(self.control_root),
+        )
+    }
+}
+
 /// A list of assumptions, each a [Digest] or populated value of an [Assumption].
 #[derive(Clone, Default, Debug, Serialize, Deserialize)]
 #[cfg_attr(test, derive(PartialEq))]
 pub struct Assumptions(pub Vec<MaybePruned<Assumption>>);
 
 impl Assumptions {
+    /// Build the list from the given assumption values.
+    pub fn from_values(values: impl IntoIterator<Item = Assumption>) -> Assumptions {
+        Assumptions(values.into_iter().map(MaybePruned::Value).collect())
+    }
+
+    /// Build the list from the digests of the assumptions, i.e. with all of them pruned.
+    pub fn from_digests(digests: impl IntoIterator<Item = Digest>) -> Assumptions {
+        Assumptions(digests.into_iter().map(MaybePruned::Pruned).collect())
+    }
+
     /// Add an assumption to the head of the assumptions list.
     pub fn add(&mut self, assumption: MaybePruned<Assumption>) {
         self.0.insert(0, assumption);
@@ -531,6 +871,19 @@ where
     }
 }
 
+impl<T> fmt::Display for MaybePruned<T>
+where
+    T: Clone + Serialize + Digestible + fmt::Display,
+{
+    /// Display the value if available, the shortened digest otherwise.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MaybePruned::Value(value) => value.fmt(f),
+            MaybePruned::Pruned(digest) => write!(f, "Pruned({})", ShortDigest(*digest)),
+        }
+    }
+}
+
 impl<T> fmt::Debug for MaybePruned<T>
 where
     T: Clone + Serialize + Digestible + fmt::Debug,
@@ -558,3 +911,391 @@ impl fmt::Display for PrunedValueError {
         write!(f, "value is pruned: {}", &self.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(pc: u32) -> SystemState {
+        SystemState {
+            pc,
+            merkle_root: Digest::from([pc; 8]),
+        }
+    }
+
+    fn claim(pre: SystemState, post: SystemState, exit_code: ExitCode) -> ReceiptClaim {
+        ReceiptClaim {
+            pre: pre.into(),
+            post: post.into(),
+            exit_code,
+            input: None.into(),
+            output: None.into(),
+        }
+    }
+
+    #[test]
+    fn chained_claims_are_continuation() {
+        let first = claim(state(1), state(2), ExitCode::SystemSplit);
+        let second = claim(state(2), state(3), ExitCode::Halted(0));
+
+        assert!(second.is_continuation_of(&first));
+    }
+
+    #[test]
+    fn chained_claims_with_pruned_states_are_continuation() {
+        let first = claim(state(1), state(2), ExitCode::SystemSplit);
+        let mut second = claim(state(2), state(3), ExitCode::Halted(0));
+        second.pre = MaybePruned::Pruned(second.pre_state_digest());
+
+        assert!(second.is_continuation_of(&first));
+    }
+
+    #[test]
+    fn not_chained_claims_are_not_continuation() {
+        let first = claim(state(1), state(2), ExitCode::SystemSplit);
+        let second = claim(state(4), state(3), ExitCode::Halted(0));
+
+        assert!(!second.is_continuation_of(&first));
+        assert!(!first.is_continuation_of(&second));
+    }
+
+    #[test]
+    fn input_digest_round_trip() {
+        let ok = ReceiptClaim::ok([1_u32; 8], alloc::vec![1_u8; 1024]);
+        let digest = Digest::from([7_u32; 8]);
+
+        assert_eq!(Digest::ZERO, ok.input_digest());
+        let with_input = ok.clone().with_input_digest(digest);
+        assert_eq!(digest, with_input.input_digest());
+        assert_ne!(ok.digest::<sha::Impl>(), with_input.digest::<sha::Impl>());
+        assert_eq!(
+            ok.digest::<sha::Impl>(),
+            with_input
+                .with_input_digest(Digest::ZERO)
+                .digest::<sha::Impl>()
+        );
+    }
+
+    #[test]
+    fn pre_matches_the_image_id() {
+        let image_id = Digest::from([1_u32; 8]);
+        let ok = ReceiptClaim::ok(image_id, alloc::vec![]);
+
+        assert!(ok.pre_matches(image_id));
+        assert!(!ok.pre_matches(Digest::from([2_u32; 8])));
+        assert!(claim(state(1), state(2), ExitCode::SystemSplit)
+            .pre_matches(state(1).digest::<sha::Impl>()));
+    }
+
+    #[rstest::rstest]
+    #[case::halted_ok(ExitCode::Halted(0), true)]
+    #[case::halted_error(ExitCode::Halted(1), true)]
+    #[case::paused(ExitCode::Paused(0), false)]
+    #[case::system_split(ExitCode::SystemSplit, false)]
+    #[case::session_limit(ExitCode::SessionLimit, false)]
+    fn post_is_halted(#[case] exit_code: ExitCode, #[case] expected: bool) {
+        assert_eq!(
+            expected,
+            claim(state(1), state(2), exit_code).post_is_halted()
+        );
+    }
+
+    #[test]
+    fn display_is_a_short_single_line() {
+        let ok = ReceiptClaim::ok([1_u32; 8], alloc::vec![1_u8; 1024]);
+
+        let formatted = std::format!("{ok}");
+
+        assert!(formatted.len() < 120, "{formatted}");
+        assert!(!formatted.contains('\n'));
+        assert!(formatted.contains("exit: Halted(0)"));
+        assert!(formatted.ends_with("output: Some(1024B) }"));
+
+        let split = std::format!("{}", claim(state(1), state(2), ExitCode::SystemSplit));
+        assert!(split.len() < 120, "{split}");
+        assert!(split.contains("exit: SystemSplit"));
+        assert!(split.ends_with("output: None }"));
+    }
+
+    #[test]
+    fn display_pruned_claim() {
+        let ok = ReceiptClaim::ok([1_u32; 8], alloc::vec![1_u8; 1024]);
+        let mut pruned_journal = ok.clone();
+        if let MaybePruned::Value(Some(output)) = &mut pruned_journal.output {
+            output.journal = MaybePruned::Pruned(output.journal.digest::<sha::Impl>());
+        }
+
+        let formatted = std::format!("{}", ok.prune_output());
+
+        assert!(formatted.len() < 120, "{formatted}");
+        assert!(formatted.ends_with("output: Pruned }"));
+        assert!(std::format!("{pruned_journal}").ends_with("output: Some(Pruned) }"));
+    }
+
+    #[test]
+    fn display_output_and_assumption() {
+        let assumption = Assumption {
+            claim: Digest::from([1; 8]),
+            control_root: Digest::ZERO,
+        };
+        let output = Output {
+            journal: alloc::vec![1_u8; 1024].into(),
+            assumptions: Assumptions(alloc::vec![assumption.clone().into()]).into(),
+        };
+
+        assert_eq!(
+            "Assumption { claim: 010000000100..., control_root: 000000000000... }",
+            std::format!("{assumption}")
+        );
+        assert_eq!(
+            "Output { journal: 1024B, assumptions: 1 }",
+            std::format!("{output}")
+        );
+        assert!(std::format!(
+            "{}",
+            Output {
+                journal: MaybePruned::Pruned(Digest::ZERO),
+                ..output
+            }
+        )
+        .starts_with("Output { journal: Pruned(000000000000...), assumptions: 1"));
+        assert!(
+            std::format!("{}", MaybePruned::<Output>::Pruned(Digest::ZERO))
+                .starts_with("Pruned(000000000000...)")
+        );
+    }
+
+    #[test]
+    fn prune_output_keeps_the_digest() {
+        let ok = ReceiptClaim::ok([1_u32; 8], alloc::vec![1_u8; 1024]);
+        let journal_digest = *sha::Impl::hash_bytes(&[1_u8; 1024]);
+
+        let pruned = ok.clone().prune_output();
+
+        assert_eq!(ok.digest::<sha::Impl>(), pruned.digest::<sha::Impl>());
+        assert_eq!(Some(journal_digest), ok.output_journal_digest());
+        assert!(ok.output_assumptions_digest().is_some());
+        assert!(matches!(pruned.output, MaybePruned::Pruned(_)));
+        assert_eq!(None, pruned.output_journal_digest());
+        assert_eq!(None, pruned.output_assumptions_digest());
+    }
+
+    #[test]
+    fn absent_output_and_input_are_not_pruned() {
+        let split = claim(state(1), state(2), ExitCode::SystemSplit);
+
+        assert_eq!(None, split.output_journal_digest());
+        assert_eq!(split, split.clone().prune_output());
+        assert_eq!(split, split.clone().prune_input());
+    }
+
+    #[test]
+    fn output_journal_accessors() {
+        let journal = alloc::vec![1_u8, 2, 3];
+        let digest = *sha::Impl::hash_bytes(&journal);
+        let output = Output {
+            journal: journal.clone().into(),
+            assumptions: MaybePruned::Value(Default::default()),
+        };
+        let pruned = Output {
+            journal: MaybePruned::Pruned(digest),
+            assumptions: MaybePruned::Pruned(Digest::from([1; 8])),
+        };
+
+        assert_eq!(Some(journal.as_slice()), output.journal_bytes());
+        assert_eq!(digest, output.journal_digest());
+        assert!(output.is_assumptions_empty());
+        assert_eq!(None, pruned.journal_bytes());
+        assert_eq!(digest, pruned.journal_digest());
+        assert!(!pruned.is_assumptions_empty());
+    }
+
+    #[test]
+    fn assumptions_from_values_and_from_digests_have_the_same_digest() {
+        let values = [
+            Assumption::self_composed(Digest::from([1; 8])),
+            Assumption {
+                claim: Digest::from([2; 8]),
+                control_root: Digest::from([3; 8]),
+            },
+        ];
+
+        let from_values = Assumptions::from_values(values.clone());
+        let from_digests =
+            Assumptions::from_digests(values.iter().map(|a| a.digest::<sha::Impl>()));
+
+        assert_eq!(
+            from_values.digest::<sha::Impl>(),
+            from_digests.digest::<sha::Impl>()
+        );
+        assert!(values[0].is_self_composed());
+        assert!(!values[1].is_self_composed());
+    }
+
+    #[test]
+    fn detect_system_split() {
+        assert!(claim(state(1), state(2), ExitCode::SystemSplit).is_system_split());
+        assert!(!claim(state(1), state(2), ExitCode::Halted(0)).is_system_split());
+        assert!(!claim(state(1), state(2), ExitCode::Paused(0)).is_system_split());
+    }
+
+    #[test]
+    fn claims_match_with_different_pruning() {
+        let ok = ReceiptClaim::ok([1_u32; 8], alloc::vec![1_u8; 32]);
+        let mut pruned = ok.clone();
+        pruned.post = MaybePruned::Pruned(ok.post_state_digest());
+        pruned.output = MaybePruned::Pruned(ok.output.digest::<sha::Impl>());
+
+        assert!(ok != pruned);
+        assert!(ok.claims_match(&pruned));
+        assert!(pruned.claims_match(&ok));
+    }
+
+    #[test]
+    fn claims_do_not_match_with_different_content() {
+        let ok = ReceiptClaim::ok([1_u32; 8], alloc::vec![1_u8; 32]);
+
+        assert!(!ok.claims_match(&ReceiptClaim::ok([1_u32; 8], alloc::vec![2_u8; 32])));
+        assert!(!ok.claims_match(&ReceiptClaim::paused([1_u32; 8], alloc::vec![1_u8; 32])));
+    }
+
+    #[test]
+    fn check_halted_ok() {
+        assert_eq!(
+            Ok(()),
+            claim(state(1), state(2), ExitCode::Halted(0)).check_halted_ok()
+        );
+        for exit_code in [
+            ExitCode::Halted(1),
+            ExitCode::Paused(0),
+            ExitCode::SystemSplit,
+            ExitCode::SessionLimit,
+        ] {
+            assert_eq!(
+                Err(VerificationError::UnexpectedExitCode),
+                claim(state(1), state(2), exit_code).check_halted_ok()
+            );
+        }
+    }
+
+    #[test]
+    fn check_pre_matches() {
+        let image_id = Digest::from([1_u32; 8]);
+        let ok = ReceiptClaim::ok(image_id, alloc::vec![1_u8; 32]);
+        let not_pruned = claim(state(1), state(2), ExitCode::Halted(0));
+
+        assert_eq!(Ok(()), ok.check_pre_matches(image_id));
+        assert_eq!(
+            Err(VerificationError::ImageVerificationError),
+            ok.check_pre_matches(Digest::from([2_u32; 8]))
+        );
+        assert_eq!(
+            Ok(()),
+            not_pruned.check_pre_matches(state(1).digest::<sha::Impl>())
+        );
+    }
+
+    #[rstest::rstest]
+    #[case::ok(ReceiptClaim::ok([1_u32; 8], alloc::vec![1_u8; 1024]))]
+    #[case::paused(ReceiptClaim::paused([2_u32; 8], alloc::vec![2_u8; 32]))]
+    fn digest_tuple_round_trip(#[case] claim: ReceiptClaim) {
+        let pruned = ReceiptClaim::from_digest_tuple(claim.to_digest_tuple(), claim.exit_code);
+
+        assert_eq!(claim.digest::<sha::Impl>(), pruned.digest::<sha::Impl>());
+        assert_eq!(claim.to_digest_tuple(), pruned.to_digest_tuple());
+    }
+
+    #[test]
+    fn digest_tuple_is_stable() {
+        use risc0_zkp_v1::core::digest::digest;
+        let claim = ReceiptClaim::ok([1_u32; 8], alloc::vec![1_u8; 1024]);
+
+        assert_eq!(
+            [
+                Digest::ZERO,
+                Digest::from([1_u32; 8]),
+                digest!("a3acc27117418996340b84e5a90f3ef4c49d22c79e44aad822ec9c313e1eb8e2"),
+                digest!("2d9ff38e5f10b74d9ffcd2d0f981993d7c3c80988af0c9399942774278fc4f51"),
+            ],
+            claim.to_digest_tuple()
+        );
+    }
+
+    #[test]
+    fn builder_builds_the_ok_claim() {
+        let (id, j) = (Digest::from([1_u32; 8]), Digest::from([2_u32; 8]));
+
+        let claim = ReceiptClaimBuilder::default()
+            .halted(0)
+            .pre(id)
+            .journal_digest(j)
+            .build();
+
+        assert_eq!(
+            ReceiptClaim::ok(id, MaybePruned::Pruned(j)).digest::<sha::Impl>(),
+            claim.digest::<sha::Impl>()
+        );
+        assert!(matches!(
+            claim,
+            ReceiptClaim {
+                pre: MaybePruned::Pruned(_),
+                post: MaybePruned::Pruned(_),
+                input: MaybePruned::Pruned(_),
+                output: MaybePruned::Pruned(_),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn builder_builds_the_paused_claim() {
+        let (id, j) = (Digest::from([1_u32; 8]), Digest::from([2_u32; 8]));
+
+        assert_eq!(
+            ReceiptClaim::paused(id, MaybePruned::Pruned(j)).digest::<sha::Impl>(),
+            ReceiptClaimBuilder::default()
+                .paused(0)
+                .pre(id)
+                .journal_digest(j)
+                .build()
+                .digest::<sha::Impl>()
+        );
+    }
+
+    #[test]
+    fn builder_default_is_a_system_split() {
+        let built = ReceiptClaimBuilder::default()
+            .pre(state(1).digest::<sha::Impl>())
+            .post(state(2).digest::<sha::Impl>())
+            .build();
+
+        assert!(built.is_system_split());
+        assert!(built.claims_match(&claim(state(1), state(2), ExitCode::SystemSplit)));
+    }
+
+    #[rstest::rstest]
+    #[case::empty(alloc::vec![])]
+    #[case::short(alloc::vec![1, 2, 3])]
+    #[case::long(alloc::vec![42; 1024])]
+    fn ok_with_journal_bytes_and_vec_have_the_same_digest(#[case] journal: Vec<u8>) {
+        let id = Digest::from([1_u32; 8]);
+
+        let pruned = ReceiptClaim::ok_with_journal_bytes(id, &journal);
+        let full = ReceiptClaim::ok_with_journal_vec(id, journal.clone());
+
+        assert_eq!(full.digest::<sha::Impl>(), pruned.digest::<sha::Impl>());
+        assert_eq!(
+            ReceiptClaim::ok(id, journal.clone()).digest::<sha::Impl>(),
+            full.digest::<sha::Impl>()
+        );
+        assert_eq!(
+            Some(journal.as_slice()),
+            full.output
+                .as_value()
+                .unwrap()
+                .as_ref()
+                .unwrap()
+                .journal_bytes()
+        );
+    }
+}
@@ -16,9 +16,16 @@
 // limitations under the License.
 //
 
-use crate::{context::VerifierContext, receipt::DEFAULT_MAX_PO2, receipt_claim::ReceiptClaim, sha};
+use crate::{
+    context::{CircuitInfo, VerifierContext},
+    receipt::DEFAULT_MAX_PO2,
+    receipt_claim::ReceiptClaim,
+    sha,
+    verifier::extract_po2::extract_segment_po2,
+    HashFunction, SegmentInfo, Verifier,
+};
 use alloc::{collections::BTreeSet, string::String, vec::Vec};
-use core::marker::PhantomData;
+use core::{fmt, marker::PhantomData};
 use risc0_binfmt_v1::{tagged_iter, tagged_struct, Digestible};
 use risc0_zkp_v1::{
     adapter::{ProtocolInfo, PROOF_SYSTEM_INFO},
@@ -34,18 +41,60 @@ use serde::{Deserialize, Serialize};
 pub struct SegmentReceipt {
     pub seal: Vec<u32>,
     pub index: u32,
-    pub hashfn: String,
+    pub hashfn: HashFunction,
     pub verifier_parameters: Digest,
     pub claim: ReceiptClaim,
 }
 
 impl SegmentReceipt {
+    /// Build a [SegmentReceipt] checking that `hashfn` is one of the supported hash functions
+    /// and that `seal` is not empty.
+    pub fn new(
+        seal: Vec<u32>,
+        index: u32,
+        hashfn: impl Into<String>,
+        verifier_parameters: Digest,
+        claim: ReceiptClaim,
+    ) -> Result<Self, SegmentReceiptNewError> {
+        let hashfn = hashfn.into();
+        let hashfn = HashFunction::try_from(hashfn.as_str())
+            .map_err(|_| SegmentReceiptNewError::UnknownHashFn(hashfn))?;
+        if seal.is_empty() {
+            return Err(SegmentReceiptNewError::EmptySeal);
+        }
+        Ok(Self {
+            seal,
+            index,
+            hashfn,
+            verifier_parameters,
+            claim,
+        })
+    }
+
+    /// Return `true` if `name` is the name of a hash function supported by the segment receipts.
+    pub fn is_valid_hashfn(name: &str) -> bool {
+        HashFunction::try_from(name).is_ok()
+    }
+
+    /// Wrap this segment in a single-segment composite [Proof](crate::Proof).
+    pub fn into_proof(self) -> crate::Proof {
+        crate::Proof::from_single_segment(self)
+    }
+
     /// Verify the integrity of this receipt, ensuring the claim is attested
     /// to by the seal.
     pub fn verify_integrity_with_context(
         &self,
         ctx: &impl VerifierContext,
     ) -> Result<(), VerificationError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            "risc0::verify_segment",
+            segment_index = self.index,
+            hashfn = %self.hashfn
+        )
+        .entered();
+
         let params = ctx
             .verifier_parameters()
             .segment_verifier_parameters()
@@ -74,8 +123,8 @@ impl SegmentReceipt {
         // let decoded_claim = decode_from_seal_v2(&self.seal, None)?;
         let decoded_claim = ctx.decode_from_seal(&self.seal)?;
         if decoded_claim.digest::<sha::Impl>() != self.claim.digest::<sha::Impl>() {
-            log::debug!(
-                "decoded segment receipt claim does not match claim field:\ndecoded: {:#?},\nexpected: {:#?}",
+            debug!(
+                "decoded segment receipt claim does not match claim field:\ndecoded: {},\nexpected: {}",
                 decoded_claim,
                 self.claim,
             );
@@ -88,15 +137,145 @@ impl SegmentReceipt {
         Ok(())
     }
 
+    /// Check that the claim decoded from the seal matches the `claim` field of this receipt,
+    /// without performing the cryptographic verification of the seal. Returns
+    /// [VerificationError::ReceiptFormatError] if the seal is too short to be decoded.
+    pub fn claim_is_consistent(
+        &self,
+        ctx: &impl VerifierContext,
+    ) -> Result<bool, VerificationError> {
+        if self.seal.len() < ctx.segment_seal_offset() + ctx.verifier_parameters().segment.size() {
+            return Err(VerificationError::ReceiptFormatError);
+        }
+        let decoded_claim = ctx.decode_from_seal(&self.seal)?;
+        Ok(decoded_claim.digest::<sha::Impl>() == self.claim.digest::<sha::Impl>())
+    }
+
     /// Return the seal for this receipt, as a vector of bytes.
     pub fn get_seal_bytes(&self) -> Vec<u8> {
         self.seal.iter().flat_map(|x| x.to_le_bytes()).collect()
     }
 
+    /// Return the seal for this receipt, without copying it.
+    pub fn get_seal_words(&self) -> &[u32] {
+        &self.seal
+    }
+
+    /// Return the seal for this receipt as bytes, without copying it.
+    pub fn get_seal_bytes_ref(&self) -> &[u8] {
+        bytemuck::cast_slice(&self.seal)
+    }
+
+    /// Return the seal words starting from `offset`: empty if the seal is shorter.
+    pub fn get_seal_words_after_offset(&self, offset: usize) -> &[u32] {
+        self.seal.get(offset..).unwrap_or_default()
+    }
+
+    /// Extract the [SegmentInfo] of this segment, reading its po2 from the seal with the layout
+    /// used by `verifier`.
+    pub fn segment_info(&self, verifier: &impl Verifier) -> Result<SegmentInfo, VerificationError> {
+        self.segment_info_at(
+            verifier.seal_offset(),
+            verifier.segment_circuit_output_size(),
+        )
+    }
+
+    pub(crate) fn segment_info_at(
+        &self,
+        seal_offset: usize,
+        output_size: usize,
+    ) -> Result<SegmentInfo, VerificationError> {
+        let seal = self.get_seal_words_after_offset(seal_offset);
+        extract_segment_po2(seal, output_size).map(|po2| SegmentInfo {
+            hash: self.hashfn.as_str().into(),
+            po2,
+        })
+    }
+
     /// Number of bytes used by the seal for this receipt.
     pub fn seal_size(&self) -> usize {
         size_of_val(self.seal.as_slice())
     }
+
+    /// Check that the `verifier_parameters` digest of this receipt matches the segment verifier
+    /// parameters of `verifier`: a cheap check to run before the seal verification.
+    pub fn verifier_parameters_matches(&self, verifier: &impl Verifier) -> bool {
+        verifier
+            .segment_verifier_parameters()
+            .is_some_and(|params| params.digest::<sha::Impl>() == self.verifier_parameters)
+    }
+
+    /// Convert this receipt into a [PrunedSegmentReceipt], replacing the seal with its SHA-256
+    /// digest.
+    pub fn into_pruned(self) -> PrunedSegmentReceipt {
+        PrunedSegmentReceipt {
+            seal_sha2_digest: *sha::Impl::hash_words(&self.seal),
+            index: self.index,
+            hashfn: self.hashfn,
+            verifier_parameters: self.verifier_parameters,
+            claim: self.claim,
+        }
+    }
+}
+
+impl Digestible for SegmentReceipt {
+    /// Canonical fingerprint of the receipt: it commits to the seal (through its SHA-256
+    /// digest), the claim, the verifier parameters and the segment index.
+    fn digest<S: Sha256>(&self) -> Digest {
+        tagged_struct::<S>(
+            "risc0.SegmentReceipt",
+            &[
+                *S::hash_words(&self.seal),
+                self.claim.digest::<S>(),
+                self.verifier_parameters,
+            ],
+            &[self.index],
+        )
+    }
+}
+
+/// Extract the [SegmentInfo] of many segments at once.
+pub trait SegmentReceiptsExt {
+    /// Extract the [SegmentInfo] of every segment, in order, like
+    /// [SegmentReceipt::segment_info]. Fail at the first segment that cannot be read.
+    fn segment_infos(
+        &self,
+        verifier: &impl Verifier,
+    ) -> Result<Vec<SegmentInfo>, VerificationError>;
+}
+
+impl SegmentReceiptsExt for [SegmentReceipt] {
+    fn segment_infos(
+        &self,
+        verifier: &impl Verifier,
+    ) -> Result<Vec<SegmentInfo>, VerificationError> {
+        self.iter().map(|s| s.segment_info(verifier)).collect()
+    }
+}
+
+/// A [SegmentReceipt] where the seal is replaced by its SHA-256 digest. The seal is no longer
+/// needed once the receipt is verified: this is a compact representation to store it.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PrunedSegmentReceipt {
+    /// SHA-256 digest of the seal words of the original receipt.
+    pub seal_sha2_digest: Digest,
+    /// Index of the segment in the execution.
+    pub index: u32,
+    /// Hash function used by the seal of the original receipt.
+    pub hashfn: HashFunction,
+    /// Digest of the parameters with which the original receipt was verified.
+    pub verifier_parameters: Digest,
+    /// Claim proven by the original receipt.
+    pub claim: ReceiptClaim,
+}
+
+impl PrunedSegmentReceipt {
+    /// Number of bytes saved by pruning the seal of the `original` receipt.
+    pub fn size_saved(&self, original: &SegmentReceipt) -> usize {
+        original
+            .seal_size()
+            .saturating_sub(size_of_val(&self.seal_sha2_digest))
+    }
 }
 
 /// Verifier parameters used to verify a [SegmentReceipt].
@@ -110,6 +289,17 @@ pub struct SegmentReceiptVerifierParameters {
     pub circuit_info: ProtocolInfo,
 }
 
+impl fmt::Display for SegmentReceiptVerifierParameters {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "SegmentParams(circuit={}, ids={})",
+            self.circuit_info,
+            self.control_id_count()
+        )
+    }
+}
+
 impl Digestible for SegmentReceiptVerifierParameters {
     /// Hash the [SegmentReceiptVerifierParameters] to get a digest of the struct.
     fn digest<S: Sha256>(&self) -> Digest {
@@ -164,6 +354,50 @@ impl SegmentReceiptVerifierParameters {
         )
     }
 
+    /// The biggest po2 of the known control ids in this set of parameters, if any.
+    pub fn max_po2(&self) -> Option<u32> {
+        self.control_ids
+            .iter()
+            .filter_map(known_control_id_po2)
+            .max()
+    }
+
+    /// The smallest po2 of the known control ids in this set of parameters, if any.
+    pub fn min_po2(&self) -> Option<u32> {
+        self.control_ids
+            .iter()
+            .filter_map(known_control_id_po2)
+            .min()
+    }
+
+    /// Number of control ids in this set of parameters.
+    pub fn control_id_count(&self) -> usize {
+        self.control_ids.len()
+    }
+
+    /// Multi-line diagnostic description of these parameters: the protocol infos, the number of
+    /// control ids and the first and last ones, with their hash function and po2 when known.
+    pub fn dump(&self) -> String {
+        use core::fmt::Write;
+
+        let mut out = String::new();
+        let _ = writeln!(out, "circuit_info: {}", self.circuit_info);
+        let _ = writeln!(out, "proof_system_info: {}", self.proof_system_info);
+        let _ = writeln!(out, "control_ids: {}", self.control_id_count());
+        let ends = [
+            ("first", self.control_ids.first()),
+            ("last", self.control_ids.last()),
+        ];
+        for (name, id) in ends {
+            let Some(id) = id else { continue };
+            let _ = match known_control_id(id) {
+                Some((hash, po2)) => writeln!(out, "{name}_control_id: {id} ({hash}, po2 {po2})"),
+                None => writeln!(out, "{name}_control_id: {id}"),
+            };
+        }
+        out
+    }
+
     fn v2_x<C: risc0_zkp_v2::adapter::CircuitInfo>(_circuit_info: PhantomData<C>) -> Self {
         let p_info = ProtocolInfo(PROOF_SYSTEM_INFO.0);
         fn fake_control_id(_hash_name: &str, _po2: usize) -> Option<Digest> {
@@ -223,9 +457,9 @@ impl SegmentReceiptVerifierParameters {
     ) -> Self {
         Self {
             control_ids: BTreeSet::from_iter(
-                ["poseidon2", "sha-256", "blake2b"]
-                    .into_iter()
-                    .flat_map(|hash_name| control_ids(resolver, hash_name, max_po2)),
+                HashFunction::ALL
+                    .iter()
+                    .flat_map(|hash| control_ids(resolver, hash.as_str(), max_po2)),
             ),
             proof_system_info,
             circuit_info,
@@ -233,6 +467,69 @@ impl SegmentReceiptVerifierParameters {
     }
 }
 
+/// Error returned by [SegmentReceipt::new] when the given fields are not valid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SegmentReceiptNewError {
+    /// The hash function name is not one of the supported ones.
+    UnknownHashFn(String),
+    /// The seal is empty.
+    EmptySeal,
+}
+
+impl fmt::Display for SegmentReceiptNewError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownHashFn(name) => write!(f, "unknown hash function: {name}"),
+            Self::EmptySeal => write!(f, "empty seal"),
+        }
+    }
+}
+
+/// Look up the po2 of a segment control id in the control ids of all the known circuit versions.
+fn known_control_id_po2(control_id: &Digest) -> Option<u32> {
+    known_control_id(control_id).map(|(_, po2)| po2)
+}
+
+/// Look up a segment control id in the control ids of all the known circuit versions and return
+/// the hash function and the po2 it belongs to.
+fn known_control_id(control_id: &Digest) -> Option<(HashFunction, u32)> {
+    use crate::circuit::{v1_0, v1_1, v1_2};
+    let tables: [(HashFunction, &[Digest]); 9] = [
+        (HashFunction::Sha256, &v1_0::control_id::SHA256_CONTROL_IDS),
+        (
+            HashFunction::Poseidon2,
+            &v1_0::control_id::POSEIDON2_CONTROL_IDS,
+        ),
+        (
+            HashFunction::Blake2b,
+            &v1_0::control_id::BLAKE2B_CONTROL_IDS,
+        ),
+        (HashFunction::Sha256, &v1_1::control_id::SHA256_CONTROL_IDS),
+        (
+            HashFunction::Poseidon2,
+            &v1_1::control_id::POSEIDON2_CONTROL_IDS,
+        ),
+        (
+            HashFunction::Blake2b,
+            &v1_1::control_id::BLAKE2B_CONTROL_IDS,
+        ),
+        (HashFunction::Sha256, &v1_2::control_id::SHA256_CONTROL_IDS),
+        (
+            HashFunction::Poseidon2,
+            &v1_2::control_id::POSEIDON2_CONTROL_IDS,
+        ),
+        (
+            HashFunction::Blake2b,
+            &v1_2::control_id::BLAKE2B_CONTROL_IDS,
+        ),
+    ];
+    tables.into_iter().find_map(|(hash, ids)| {
+        ids.iter()
+            .position(|id| id == control_id)
+            .map(|idx| (hash, (MIN_CYCLES_PO2 + idx) as u32))
+    })
+}
+
 fn control_ids<'a, H: AsRef<str> + 'a>(
     resolver: &'a dyn Fn(&str, usize) -> Option<Digest>,
     hash_name: H,
@@ -248,9 +545,17 @@ fn control_ids<'a, H: AsRef<str> + 'a>(
 #[cfg(test)]
 mod tests {
 
-    use super::SegmentReceiptVerifierParameters;
-    use crate::sha::Digestible;
-    use risc0_zkp_v1::core::digest::{digest, Digest};
+    use super::{SegmentReceipt, SegmentReceiptNewError, SegmentReceiptVerifierParameters};
+    use crate::{
+        context::{v1::V1, v2::V2, v3::V3, VerifierContext},
+        sha::Digestible,
+        Proof, Verifier,
+    };
+    use risc0_binfmt_v1::ExitCode;
+    use risc0_zkp_v1::{
+        core::digest::{digest, Digest},
+        verify::VerificationError,
+    };
     use rstest::rstest;
 
     // Check that the verifier parameters has a stable digest (and therefore a stable value). This
@@ -261,10 +566,204 @@ mod tests {
     #[case::v1_0(SegmentReceiptVerifierParameters::v1_0().digest(), digest!("62d97bc46d0a877acb857043cbb90a6beafa21c97f01472952fd28be15b47508"))]
     #[case::v1_1(SegmentReceiptVerifierParameters::v1_1().digest(), digest!("52a27aff2de5a8206e3e88cb8dcb087c1193ede8efaf4889117bc68e704cf29a"))]
     #[case::v1_2(SegmentReceiptVerifierParameters::v1_2().digest(), digest!("52a27aff2de5a8206e3e88cb8dcb087c1193ede8efaf4889117bc68e704cf29a"))]
+    #[case::v2_0(SegmentReceiptVerifierParameters::v2_0().digest(), digest!("5a123dc5ac0a4ed69a91f746cca8453a3af36dc0803ccf36bcc5b63eb4f5e621"))]
+    #[case::v2_1(SegmentReceiptVerifierParameters::v2_1().digest(), digest!("5a123dc5ac0a4ed69a91f746cca8453a3af36dc0803ccf36bcc5b63eb4f5e621"))]
+    #[case::v2_2(SegmentReceiptVerifierParameters::v2_2().digest(), digest!("5a123dc5ac0a4ed69a91f746cca8453a3af36dc0803ccf36bcc5b63eb4f5e621"))]
+    #[case::v2_3(SegmentReceiptVerifierParameters::v2_3().digest(), digest!("5a123dc5ac0a4ed69a91f746cca8453a3af36dc0803ccf36bcc5b63eb4f5e621"))]
+    #[case::v3_0(SegmentReceiptVerifierParameters::v3_0().digest(), digest!("e7300130165ebe00f68f9301530de9d068d6f6c06f1c17817a5f7d64ce6c635d"))]
     fn succinct_receipt_verifier_parameters_is_stable(
         #[case] computed: Digest,
         #[case] hardcoded: Digest,
     ) {
         assert_eq!(computed, hardcoded);
     }
+
+    #[test]
+    fn v1_2_po2_bounds() {
+        let ctx = V1::v1_2();
+        let params = ctx
+            .verifier_parameters()
+            .segment_verifier_parameters()
+            .unwrap();
+
+        assert_eq!(Some(21), params.max_po2());
+        assert_eq!(Some(super::MIN_CYCLES_PO2 as u32), params.min_po2());
+        // One control id for each po2 and hash function.
+        assert_eq!(
+            3 * (21 - super::MIN_CYCLES_PO2 + 1),
+            params.control_id_count()
+        );
+    }
+
+    #[rstest]
+    #[case::v1_0(alloc::boxed::Box::new(crate::v1_0()), Some(23))]
+    #[case::v1_2(alloc::boxed::Box::new(crate::v1_2()), Some(21))]
+    #[case::v2_1(alloc::boxed::Box::new(crate::v2_1()), None)]
+    #[case::v3_0(alloc::boxed::Box::new(crate::v3_0()), None)]
+    fn verifier_max_po2(
+        #[case] verifier: alloc::boxed::Box<dyn Verifier>,
+        #[case] expected: Option<u32>,
+    ) {
+        assert_eq!(expected, verifier.max_po2());
+        assert_eq!(
+            expected.map(|_| super::MIN_CYCLES_PO2 as u32),
+            verifier.min_po2()
+        );
+    }
+
+    fn first_segment(path: &str) -> SegmentReceipt {
+        let proof: Proof = ciborium::from_reader(std::fs::File::open(path).unwrap()).unwrap();
+        proof.inner.composite().unwrap().segments[0].clone()
+    }
+
+    #[test]
+    fn segment_receipt_digest_is_stable() {
+        let segment = first_segment("./resources/receipts/1.2.0-1.2.0/poseidon2_16.bin");
+
+        assert_eq!(
+            digest!("7d9e599ab36a105c9510d74da78616215fe0577243f5d0f620e26955f64e2d15"),
+            segment.digest()
+        );
+    }
+
+    #[test]
+    fn segment_receipt_digest_commits_to_claim_seal_and_index() {
+        let segment = first_segment("./resources/receipts/1.2.0-1.2.0/poseidon2_16.bin");
+        let digest = segment.digest();
+
+        let mut claim = segment.clone();
+        claim.claim.exit_code = ExitCode::SystemSplit;
+        let mut seal = segment.clone();
+        seal.seal[0] ^= 1;
+        let mut index = segment.clone();
+        index.index += 1;
+
+        for changed in [claim, seal, index] {
+            assert_ne!(digest, changed.digest());
+        }
+    }
+
+    #[test]
+    fn borrowed_seal_accessors() {
+        let segment = first_segment("./resources/receipts/1.2.0-1.2.0/poseidon2_16.bin");
+
+        assert_eq!(segment.get_seal_bytes(), segment.get_seal_bytes_ref());
+        assert_eq!(segment.seal.as_slice(), segment.get_seal_words());
+        assert_eq!(&segment.seal[10..], segment.get_seal_words_after_offset(10));
+        assert!(segment
+            .get_seal_words_after_offset(segment.seal.len() + 1)
+            .is_empty());
+    }
+
+    #[test]
+    fn into_pruned() {
+        let segment = first_segment("./resources/receipts/1.2.0-1.2.0/poseidon2_16.bin");
+
+        let pruned = segment.clone().into_pruned();
+
+        assert_eq!(
+            *<crate::sha::Impl as crate::sha::Sha256>::hash_bytes(&segment.get_seal_bytes()),
+            pruned.seal_sha2_digest
+        );
+        assert_eq!(segment.index, pruned.index);
+        assert_eq!(segment.hashfn, pruned.hashfn);
+        assert_eq!(segment.verifier_parameters, pruned.verifier_parameters);
+        assert_eq!(segment.claim, pruned.claim);
+        assert_eq!(segment.seal_size() - 32, pruned.size_saved(&segment));
+    }
+
+    #[rstest]
+    #[case::v1_2(V1::v1_2(), "./resources/receipts/1.2.0-1.2.0/poseidon2_16.bin")]
+    #[case::v2_1(V2::v2_1(), "./resources/receipts/2.1.0-2.1.0/poseidon2_16.bin")]
+    #[case::v3_0(V3::v3_0(), "./resources/receipts/3.0.0-3.0.0/poseidon2_16.bin")]
+    fn claim_is_consistent(#[case] ctx: impl VerifierContext, #[case] path: &str) {
+        let segment = first_segment(path);
+        assert_eq!(Ok(true), segment.claim_is_consistent(&ctx));
+
+        let mut mutated = segment.clone();
+        mutated.claim.exit_code = ExitCode::Paused(42);
+        assert_eq!(Ok(false), mutated.claim_is_consistent(&ctx));
+
+        let mut truncated = segment;
+        truncated.seal.truncate(ctx.segment_seal_offset());
+        assert_eq!(
+            Err(VerificationError::ReceiptFormatError),
+            truncated.claim_is_consistent(&ctx)
+        );
+    }
+
+    #[rstest]
+    #[case::poseidon2("poseidon2")]
+    #[case::sha_256("sha-256")]
+    #[case::blake2b("blake2b")]
+    fn new_segment_receipt(#[case] hashfn: &str) {
+        let segment = first_segment("./resources/receipts/1.2.0-1.2.0/poseidon2_16.bin");
+
+        let built = SegmentReceipt::new(
+            segment.seal.clone(),
+            3,
+            hashfn,
+            segment.verifier_parameters,
+            segment.claim.clone(),
+        )
+        .unwrap();
+
+        assert_eq!(segment.seal, built.seal);
+        assert_eq!(3, built.index);
+        assert_eq!(built.hashfn, hashfn);
+        assert_eq!(segment.verifier_parameters, built.verifier_parameters);
+        assert_eq!(segment.claim, built.claim);
+    }
+
+    #[test]
+    fn new_segment_receipt_rejects_unknown_hashfn() {
+        let segment = first_segment("./resources/receipts/1.2.0-1.2.0/poseidon2_16.bin");
+
+        assert!(!SegmentReceipt::is_valid_hashfn("keccak"));
+        assert_eq!(
+            SegmentReceiptNewError::UnknownHashFn("keccak".into()),
+            SegmentReceipt::new(
+                segment.seal,
+                0,
+                "keccak",
+                segment.verifier_parameters,
+                segment.claim
+            )
+            .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn new_segment_receipt_rejects_empty_seal() {
+        let segment = first_segment("./resources/receipts/1.2.0-1.2.0/poseidon2_16.bin");
+
+        assert_eq!(
+            SegmentReceiptNewError::EmptySeal,
+            SegmentReceipt::new(
+                alloc::vec::Vec::new(),
+                0,
+                "poseidon2",
+                segment.verifier_parameters,
+                segment.claim
+            )
+            .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn v1_2_dump() {
+        let params = SegmentReceiptVerifierParameters::v1_2();
+
+        let dump = params.dump();
+
+        assert!(dump.contains("circuit_info: RV32IM:rev1v1___"));
+        assert!(dump.contains("control_ids: 27"));
+        assert!(["poseidon2", "sha-256", "blake2b"]
+            .iter()
+            .any(|name| dump.contains(name)));
+        assert_eq!(
+            "SegmentParams(circuit=RV32IM:rev1v1___, ids=27)",
+            std::format!("{params}")
+        );
+    }
 }
@@ -50,7 +50,10 @@
 //! assert_eq!(hash_hash, hash_hash_words);
 //! ```
 
-pub use risc0_zkp_v1::core::{digest::Digest, hash::sha::Sha256};
+pub use risc0_zkp_v1::core::{
+    digest::{Digest, DIGEST_BYTES, DIGEST_WORDS},
+    hash::sha::Sha256,
+};
 
 // This Impl selects the appropriate implementation of SHA-256 depending on whether we are
 // in the zkVM guest. Users can simply `use risc0_zkvm::sha::Impl`.
@@ -68,3 +71,58 @@ impl<D: ?Sized + risc0_binfmt_v1::Digestible> Digestible for D {
         self.digest::<Impl>()
     }
 }
+
+/// Hex encoding of a [Digest].
+pub trait DigestExt {
+    /// The 64 characters lowercase hex encoding of the digest bytes.
+    fn to_hex(&self) -> alloc::string::String;
+
+    /// The 64 characters uppercase hex encoding of the digest bytes.
+    fn to_hex_upper(&self) -> alloc::string::String;
+}
+
+impl DigestExt for Digest {
+    fn to_hex(&self) -> alloc::string::String {
+        hex::encode(self.as_bytes())
+    }
+
+    fn to_hex_upper(&self) -> alloc::string::String {
+        hex::encode_upper(self.as_bytes())
+    }
+}
+
+/// Parse a [Digest] from its 64 characters hex encoding (see [DigestExt::to_hex]).
+pub fn digest_from_hex(s: &str) -> Result<Digest, hex::FromHexError> {
+    let mut bytes = [0_u8; DIGEST_BYTES];
+    hex::decode_to_slice(s, &mut bytes)?;
+    Ok(Digest::from(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digest_hex_round_trip() {
+        let digest = *Impl::hash_bytes(b"risc0");
+
+        let hex = digest.to_hex();
+
+        assert_eq!(2 * DIGEST_BYTES, hex.len());
+        assert_eq!(hex.to_uppercase(), digest.to_hex_upper());
+        assert_eq!(Ok(digest), digest_from_hex(&hex));
+        assert_eq!(Ok(digest), digest_from_hex(&digest.to_hex_upper()));
+    }
+
+    #[test]
+    fn digest_from_invalid_hex() {
+        assert_eq!(
+            Err(hex::FromHexError::InvalidStringLength),
+            digest_from_hex("00ff")
+        );
+        assert_eq!(
+            Err(hex::FromHexError::InvalidHexCharacter { c: 'z', index: 0 }),
+            digest_from_hex(&"z".repeat(64))
+        );
+    }
+}
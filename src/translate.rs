@@ -123,7 +123,12 @@ impl Translate<VerificationError> for risc0_zkp_v2::verify::VerificationError {
                     digest: digest.translate(),
                 }
             }
-            _ => unreachable!("unknown VerificationError variant: {:?}", self),
+            // The source enum is `#[non_exhaustive]`, so the compiler requires this arm even if
+            // all its current variants are matched above. `Cargo.toml` pins the upstream crate
+            // to its minor release so a new variant cannot be picked up without revisiting this
+            // match.
+            #[allow(unreachable_patterns)]
+            _ => VerificationError::InvalidProof,
         }
     }
 }
@@ -180,7 +185,117 @@ impl Translate<VerificationError> for risc0_zkp_v3::verify::VerificationError {
                     digest: digest.translate(),
                 }
             }
-            _ => unreachable!("unknown VerificationError variant: {:?}", self),
+            // The source enum is `#[non_exhaustive]`, so the compiler requires this arm even if
+            // all its current variants are matched above. `Cargo.toml` pins the upstream crate
+            // to its minor release so a new variant cannot be picked up without revisiting this
+            // match.
+            #[allow(unreachable_patterns)]
+            _ => VerificationError::InvalidProof,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    type DigestV1 = risc0_zkp_v1::core::digest::Digest;
+    type DigestV2 = risc0_zkp_v2::core::digest::Digest;
+    type DigestV3 = risc0_zkp_v3::core::digest::Digest;
+    use risc0_zkp_v1::adapter::ProtocolInfo as ProtocolInfoV1;
+
+    /// Build all the known variants of the [VerificationError] of the given risc0 zkp crate
+    /// from the same field values.
+    macro_rules! all_variants {
+        ($zkp:ident, $d1:expr, $d2:expr, $i1:expr, $i2:expr, $idx:expr, $rows:expr) => {{
+            use $zkp::{adapter::ProtocolInfo, core::digest::Digest, verify::VerificationError};
+            let d1 = Digest::from($d1);
+            let d2 = Digest::from($d2);
+            let i1 = ProtocolInfo($i1);
+            let i2 = ProtocolInfo($i2);
+            [
+                VerificationError::ReceiptFormatError,
+                VerificationError::ControlVerificationError { control_id: d1 },
+                VerificationError::ImageVerificationError,
+                VerificationError::MerkleQueryOutOfRange {
+                    idx: $idx,
+                    rows: $rows,
+                },
+                VerificationError::InvalidProof,
+                VerificationError::JournalDigestMismatch,
+                VerificationError::ClaimDigestMismatch {
+                    expected: d1,
+                    received: d2,
+                },
+                VerificationError::UnexpectedExitCode,
+                VerificationError::InvalidHashSuite,
+                VerificationError::VerifierParametersMissing,
+                VerificationError::VerifierParametersMismatch {
+                    expected: d1,
+                    received: d2,
+                },
+                VerificationError::ProofSystemInfoMismatch {
+                    expected: i1,
+                    received: i2,
+                },
+                VerificationError::CircuitInfoMismatch {
+                    expected: i1,
+                    received: i2,
+                },
+                VerificationError::UnresolvedAssumption { digest: d2 },
+            ]
+        }};
+    }
+
+    proptest! {
+        #[test]
+        fn digest_round_trips_through_v2(words: [u32; 8]) {
+            let digest = DigestV1::from(words);
+            let v2: DigestV2 = digest.translate();
+
+            prop_assert_eq!(&words, v2.as_words());
+            prop_assert_eq!(digest, v2.translate());
+        }
+
+        #[test]
+        fn digest_round_trips_through_v3(words: [u32; 8]) {
+            let digest = DigestV1::from(words);
+            let v3: DigestV3 = digest.translate();
+
+            prop_assert_eq!(&words, v3.as_words());
+            prop_assert_eq!(digest, v3.translate());
+        }
+
+        #[test]
+        fn protocol_info_round_trips(bytes: [u8; 16]) {
+            let info = ProtocolInfoV1(bytes);
+            let v2: risc0_zkp_v2::adapter::ProtocolInfo = info.translate();
+            let v3: risc0_zkp_v3::adapter::ProtocolInfo = info.translate();
+
+            prop_assert_eq!(bytes, v2.0);
+            prop_assert_eq!(bytes, v3.0);
+            prop_assert_eq!(info, v2.translate());
+            prop_assert_eq!(info, v3.translate());
+        }
+
+        #[test]
+        fn verification_error_translates_preserving_fields(
+            d1: [u32; 8],
+            d2: [u32; 8],
+            i1: [u8; 16],
+            i2: [u8; 16],
+            idx: usize,
+            rows: usize,
+        ) {
+            let expected = all_variants!(risc0_zkp_v1, d1, d2, i1, i2, idx, rows);
+            let v2 = all_variants!(risc0_zkp_v2, d1, d2, i1, i2, idx, rows);
+            let v3 = all_variants!(risc0_zkp_v3, d1, d2, i1, i2, idx, rows);
+
+            for ((expected, v2), v3) in expected.into_iter().zip(v2).zip(v3) {
+                prop_assert_eq!(&expected, &v2.translate());
+                prop_assert_eq!(&expected, &v3.translate());
+            }
         }
     }
 }
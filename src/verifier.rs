@@ -18,13 +18,104 @@
 use crate::{
     context::{CircuitInfo, VerifierContext},
     poseidon2_injection::Poseidon2Mix,
+    receipt::groth16::Groth16ReceiptVerifierParameters,
     receipt::succinct::SuccinctReceiptVerifierParameters,
-    CompositeReceipt, Digestible, Journal, Proof, SegmentInfo,
+    receipt_claim::{Assumption, ExitCode, MaybePruned, ReceiptClaim},
+    segment::SegmentReceiptVerifierParameters,
+    CompositeReceipt, Digestible, HashFunction, InnerAssumptionReceipt, InnerReceipt, Journal,
+    Proof, ReceiptKind, SegmentInfo, SuccinctReceipt,
 };
-use alloc::boxed::Box;
-use risc0_zkp_v1::{core::digest::Digest, verify::VerificationError};
+use alloc::{boxed::Box, collections::BTreeSet, format, string::String, vec::Vec};
+use core::{cmp::Ordering, fmt};
+use risc0_zkp_v1::{adapter::ProtocolInfo, core::digest::Digest, verify::VerificationError};
+use serde::{Deserialize, Serialize};
 
-mod extract_po2;
+pub(crate) mod extract_po2;
+
+/// Estimated cost of verifying a [Proof], as returned by [Verifier::estimate_verify_cost].
+///
+/// The costs are ordered by `seal_words`; different costs with the same number of seal words are
+/// not comparable.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ProofCost {
+    /// Total number of words of the seals to verify: a proxy of the verification time.
+    pub seal_words: u64,
+    /// Number of seals to verify: the segments of a composite proof or 1 for a succinct one.
+    pub segment_count: usize,
+    /// `true` if the proof is a succinct or a Groth16 one.
+    pub is_succinct: bool,
+}
+
+impl PartialOrd for ProofCost {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.seal_words.cmp(&other.seal_words) {
+            Ordering::Equal if self != other => None,
+            ordering => Some(ordering),
+        }
+    }
+}
+
+impl fmt::Display for ProofCost {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} seal words, {} segments",
+            self.seal_words, self.segment_count
+        )
+    }
+}
+
+/// What a successful verification checked, as returned by [Verifier::verify_with_report]:
+/// useful to audit the verified proofs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VerificationReport {
+    /// The kind of the verified receipt.
+    pub kind: ReceiptKind,
+    /// The info of every segment of a composite receipt, in order: empty for the other kinds.
+    pub segments: Vec<SegmentInfo>,
+    /// The digest of the verified [ReceiptClaim].
+    pub claim_digest: Digest,
+    /// The digest of the journal committed in the claim.
+    pub journal_digest: Digest,
+    /// The digest of the verifier parameters used to verify the receipt: the ones of its kind.
+    pub verifier_parameters: Digest,
+    /// The control root with which a succinct receipt was verified: `None` for the other kinds.
+    pub control_root: Option<Digest>,
+}
+
+impl VerificationReport {
+    fn new(
+        verifier: &(impl Verifier + ?Sized),
+        proof: &Proof,
+        pubs: &Journal,
+    ) -> Result<Self, VerificationError> {
+        let kind = proof.inner.kind();
+        let verifier_parameters = match kind {
+            ReceiptKind::Composite => verifier.segment_verifier_parameters().map(|p| p.digest()),
+            ReceiptKind::Succinct => verifier.succinct_verifier_parameters().map(|p| p.digest()),
+            ReceiptKind::Groth16 => verifier.groth16_verifier_parameters().map(|p| p.digest()),
+            #[cfg(feature = "dev-mode")]
+            ReceiptKind::Fake => Some(Digest::ZERO),
+        }
+        .ok_or(VerificationError::VerifierParametersMissing)?;
+        Ok(Self {
+            kind,
+            segments: match &proof.inner {
+                InnerReceipt::Composite(composite) => {
+                    verifier.extract_composite_segments_info(composite)?
+                }
+                _ => Vec::new(),
+            },
+            claim_digest: proof.claim()?.digest(),
+            journal_digest: pubs.digest(),
+            verifier_parameters,
+            control_root: match kind {
+                ReceiptKind::Succinct => verifier.succinct_control_root(),
+                _ => None,
+            },
+        })
+    }
+}
 
 /// Dynamic verifier trait. It's implemented by all verifier context and can be
 /// used with dynamic dispatching. Expose just the functionalities that can be
@@ -39,20 +130,435 @@ pub trait Verifier {
         pubs: Journal,
     ) -> Result<(), VerificationError>;
 
+    /// Verify the proof like [Verifier::verify] against the SHA digest of the journal: useful
+    /// when only the digest is available (e.g. on-chain). It's the same as [Verifier::verify]
+    /// with `journal.digest()`, except that the journal size limit cannot be enforced. The
+    /// default implementation cannot verify a proof without its journal and rejects every proof
+    /// with [VerificationError::ReceiptFormatError].
+    fn verify_digest(
+        &self,
+        image_id: Digest,
+        proof: Proof,
+        journal_digest: Digest,
+    ) -> Result<(), VerificationError> {
+        let _ = (image_id, proof, journal_digest);
+        Err(VerificationError::ReceiptFormatError)
+    }
+
+    /// Verify the proof like [Verifier::verify] but expecting the execution to end with
+    /// `exit_code` instead of `Halted(0)` (see [Proof::verify_with_exit_code]). The default
+    /// implementation only accepts `Halted(0)`.
+    fn verify_with_exit_code(
+        &self,
+        image_id: Digest,
+        proof: Proof,
+        pubs: Journal,
+        exit_code: ExitCode,
+    ) -> Result<(), VerificationError> {
+        if exit_code != ExitCode::Halted(0) {
+            return Err(VerificationError::UnexpectedExitCode);
+        }
+        self.verify(image_id, proof, pubs)
+    }
+
+    /// Verify the proof like [Verifier::verify], but accept a conditional proof and return the
+    /// claim digests of its unresolved assumptions (see [Proof::verify_conditional]). The
+    /// default implementation only accepts unconditional proofs, with no assumptions to return.
+    fn verify_conditional(
+        &self,
+        image_id: Digest,
+        proof: Proof,
+        pubs: Journal,
+    ) -> Result<Vec<Digest>, VerificationError> {
+        self.verify(image_id, proof, pubs)?;
+        Ok(Vec::new())
+    }
+
+    /// Verify the integrity of just the segment at `index` of `composite`, without the checks
+    /// that chain it to the other segments: useful to find which segment of a bad proof fails.
+    /// The default implementation checks the index and then rejects the segment with
+    /// [VerificationError::ReceiptFormatError].
+    fn verify_segment_at(
+        &self,
+        composite: &CompositeReceipt,
+        index: usize,
+    ) -> Result<(), SegmentVerificationError> {
+        let len = composite.segments.len();
+        if index >= len {
+            return Err(SegmentVerificationError::IndexOutOfRange { index, len });
+        }
+        Err(VerificationError::ReceiptFormatError.into())
+    }
+
+    /// Verify an assumption receipt on its own, e.g. one produced out of band for a guest
+    /// assumption: the receipt must verify and its claim digest must be `expected_claim`. With
+    /// a `control_root` the receipt is verified against it, like an assumption resolved by a
+    /// different set of recursion programs (e.g. keccak); without it (or with the all zeroes
+    /// root) it's verified with the parameters of this verifier, like a self-composed one. The
+    /// default implementation rejects every receipt with [VerificationError::ReceiptFormatError].
+    fn verify_assumption(
+        &self,
+        receipt: &InnerAssumptionReceipt,
+        expected_claim: Digest,
+        control_root: Option<Digest>,
+    ) -> Result<(), VerificationError> {
+        let _ = (receipt, expected_claim, control_root);
+        Err(VerificationError::ReceiptFormatError)
+    }
+
+    /// Verify the proof like [Verifier::verify] with a verifier built by `new_verifier`. With the
+    /// `parallel` feature the segments of a composite proof are verified in parallel on the
+    /// `rayon` thread pool, each thread with its own verifier built by `new_verifier`. Without
+    /// the feature the proof is verified sequentially.
+    fn verify_parallel(
+        new_verifier: impl Fn() -> Self + Send + Sync,
+        image_id: Digest,
+        proof: Proof,
+        pubs: Journal,
+    ) -> Result<(), VerificationError>
+    where
+        Self: Sized,
+    {
+        new_verifier().verify(image_id, proof, pubs)
+    }
+
+    /// Verify the proof like [Verifier::verify] and, on success, return the decoded
+    /// [ReceiptClaim] of the proof.
+    fn verify_and_decode(
+        &self,
+        image_id: Digest,
+        proof: Proof,
+        pubs: Journal,
+    ) -> Result<ReceiptClaim, VerificationError> {
+        let claim = proof.claim();
+        self.verify(image_id, proof, pubs)?;
+        claim?
+            .value()
+            .map_err(|_| VerificationError::ReceiptFormatError)
+    }
+
+    /// Extract the [ReceiptClaim] from the proof without verifying it (see
+    /// [Proof::inspect_claim]): useful to inspect the image id, the exit code and the output
+    /// before paying for the verification. The claim cannot be trusted until the proof is
+    /// verified.
+    fn extract_claim(&self, proof: &Proof) -> Result<MaybePruned<ReceiptClaim>, VerificationError> {
+        proof.inspect_claim()
+    }
+
+    /// Verify the proof like [Verifier::verify] and, on success, return a [VerificationReport]
+    /// of what was verified. A failure is the same one that [Verifier::verify] would return.
+    fn verify_with_report(
+        &self,
+        image_id: Digest,
+        proof: Proof,
+        pubs: Journal,
+    ) -> Result<VerificationReport, VerificationError> {
+        let report = VerificationReport::new(self, &proof, &pubs);
+        self.verify(image_id, proof, pubs)?;
+        report
+    }
+
+    /// Verify the given cases in order like [Verifier::verify_and_decode] and return their
+    /// decoded claims in the same order. Stop at the first failure and return it together with
+    /// the zero-based index of the failed case.
+    fn verify_multiple(
+        &self,
+        cases: impl IntoIterator<Item = (crate::Vk, Proof, Journal)>,
+    ) -> Result<alloc::vec::Vec<ReceiptClaim>, (usize, VerificationError)>
+    where
+        Self: Sized,
+    {
+        cases
+            .into_iter()
+            .enumerate()
+            .map(|(i, (vk, proof, pubs))| {
+                self.verify_and_decode(vk.into(), proof, pubs)
+                    .map_err(|err| (i, err))
+            })
+            .collect()
+    }
+
+    /// Verify every item like [Verifier::verify] and return the results in the same order:
+    /// the result at index `i` is the one of `items[i]`, and a failing item doesn't stop the
+    /// verification of the others. All the items are verified with the hash suites and the
+    /// verifier parameters of this verifier.
+    fn verify_batch(
+        &self,
+        items: &[(crate::Vk, Proof, Journal)],
+    ) -> alloc::vec::Vec<Result<(), VerificationError>> {
+        items
+            .iter()
+            .map(|(vk, proof, pubs)| self.verify(vk.0, proof.clone(), pubs.clone()))
+            .collect()
+    }
+
+    /// Verify the proof like [Verifier::verify] inside a `risc0::verify` [tracing] span. Every
+    /// segment is verified in a `risc0::verify_segment` span (with `segment_index` and `hashfn`
+    /// fields) and every succinct receipt in a `risc0::verify_succinct` one.
+    #[cfg(feature = "tracing")]
+    fn verify_traced(
+        &self,
+        vk: crate::Vk,
+        proof: Proof,
+        pubs: Journal,
+    ) -> Result<(), VerificationError> {
+        let image_id: Digest = vk.into();
+        let _span = tracing::debug_span!("risc0::verify", %image_id).entered();
+        let result = self.verify(image_id, proof, pubs);
+        tracing::debug!(ok = result.is_ok(), "verification completed");
+        result
+    }
+
     /// The offset at which the seal is stored in the proof.
     fn seal_offset(&self) -> usize;
 
     /// The segment circuit size.
     fn segment_circuit_output_size(&self) -> usize;
 
+    /// The recursion circuit size.
+    fn succinct_circuit_output_size(&self) -> usize {
+        crate::receipt::succinct::RECURSION_OUTPUT_SIZE
+    }
+
     /// Inject a poseidon2 implementation
     fn set_poseidon2_mix_impl(&mut self, poseidon2: Box<dyn Poseidon2Mix + Send + Sync + 'static>);
 
+    /// Return this verifier with the given poseidon2 implementation injected.
+    fn with_poseidon2(mut self, poseidon2: impl Poseidon2Mix + Send + Sync + 'static) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_poseidon2_mix_impl(Box::new(poseidon2));
+        self
+    }
+
+    /// Remove the hash suite named `hashfn` from the ones accepted by this verifier: the
+    /// receipts that use it will be rejected with [VerificationError::InvalidHashSuite].
+    fn remove_hash_suite(&mut self, hashfn: &str);
+
+    /// Return `true` if this verifier has a hash suite named `hashfn`. The default implementation
+    /// reports a suite for every [HashFunction]: override it together with
+    /// [Verifier::remove_hash_suite].
+    fn has_hash_suite(&self, hashfn: &str) -> bool {
+        HashFunction::ALL.iter().any(|hash| *hash == hashfn)
+    }
+
+    /// Return this verifier that rejects the receipts that use `sha-256`.
+    fn reject_sha256(mut self) -> Self
+    where
+        Self: Sized,
+    {
+        self.remove_hash_suite("sha-256");
+        self
+    }
+
+    /// Return this verifier that rejects the receipts that use `blake2b`.
+    fn reject_blake2b(mut self) -> Self
+    where
+        Self: Sized,
+    {
+        self.remove_hash_suite("blake2b");
+        self
+    }
+
+    /// Return this verifier that rejects the receipts that use `poseidon2`.
+    fn reject_poseidon2(mut self) -> Self
+    where
+        Self: Sized,
+    {
+        self.remove_hash_suite("poseidon2");
+        self
+    }
+
+    /// Set the maximum number of bytes of the seals of the proofs accepted by this verifier
+    /// ([DEFAULT_MAX_PROOF_BYTES](crate::DEFAULT_MAX_PROOF_BYTES) by default). Tighten it where
+    /// the cost of handling a proof grows with its size, like on-chain.
+    fn set_max_proof_bytes(&mut self, max: usize);
+
+    /// Return the maximum number of bytes of the seals of the proofs accepted by this verifier.
+    fn max_proof_bytes(&self) -> usize;
+
+    /// Set the maximum number of bytes of the journals accepted by this verifier: bigger
+    /// journals are rejected with [VerificationError::ReceiptFormatError]. There is no limit by
+    /// default; [DEFAULT_MAX_JOURNAL_BYTES](crate::DEFAULT_MAX_JOURNAL_BYTES) is a reasonable one.
+    fn set_max_journal_bytes(&mut self, n: usize);
+
+    /// Return the maximum number of bytes of the journals accepted by this verifier, if set.
+    fn max_journal_bytes(&self) -> Option<usize>;
+
+    /// Set whether this verifier accepts the fake receipts of the provers in dev mode: they are
+    /// rejected by default. Available only with the `dev-mode` feature.
+    #[cfg(feature = "dev-mode")]
+    fn set_fake_receipts_allowed(&mut self, allowed: bool);
+
+    /// Return whether this verifier accepts the fake receipts of the provers in dev mode.
+    #[cfg(feature = "dev-mode")]
+    fn fake_receipts_allowed(&self) -> bool;
+
+    /// Return this verifier that accepts the fake receipts of the provers in dev mode. They
+    /// carry no proof at all: never use it in production.
+    #[cfg(feature = "dev-mode")]
+    fn with_fake_receipts_allowed(mut self) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_fake_receipts_allowed(true);
+        self
+    }
+
+    /// Return the [SegmentReceiptVerifierParameters] used to verify the segments, if any.
+    fn segment_verifier_parameters(&self) -> Option<&SegmentReceiptVerifierParameters>;
+
+    /// Return the [SuccinctReceiptVerifierParameters] used to verify the succinct receipts, if
+    /// any.
+    fn succinct_verifier_parameters(&self) -> Option<&SuccinctReceiptVerifierParameters>;
+
+    /// Return the [Groth16ReceiptVerifierParameters] used to verify the Groth16 receipts, if
+    /// any. The default implementation returns `None`.
+    fn groth16_verifier_parameters(&self) -> Option<&Groth16ReceiptVerifierParameters> {
+        None
+    }
+
+    /// Call `f` with the segment and succinct verifier parameters of this verifier.
+    fn inspect_parameters<F>(&self, f: F)
+    where
+        Self: Sized,
+        F: FnOnce(
+            Option<&SegmentReceiptVerifierParameters>,
+            Option<&SuccinctReceiptVerifierParameters>,
+        ),
+    {
+        f(
+            self.segment_verifier_parameters(),
+            self.succinct_verifier_parameters(),
+        )
+    }
+
+    /// Return `true` if this verifier has the [SegmentReceiptVerifierParameters] set.
+    fn has_segment_params(&self) -> bool {
+        self.segment_verifier_parameters().is_some()
+    }
+
+    /// Return `true` if this verifier has the [SuccinctReceiptVerifierParameters] set.
+    fn has_succinct_params(&self) -> bool {
+        self.succinct_verifier_parameters().is_some()
+    }
+
+    /// The circuit protocol info with which the segments should verify.
+    fn segment_protocol_info(&self) -> Option<ProtocolInfo> {
+        self.segment_verifier_parameters().map(|p| p.circuit_info)
+    }
+
+    /// The circuit protocol info with which the succinct receipts should verify.
+    fn succinct_protocol_info(&self) -> Option<ProtocolInfo> {
+        self.succinct_verifier_parameters().map(|p| p.circuit_info)
+    }
+
+    /// The control root with which the succinct receipts should verify.
+    fn succinct_control_root(&self) -> Option<Digest> {
+        self.succinct_verifier_parameters().map(|p| p.control_root)
+    }
+
+    /// The control ids of the circuits accepted when verifying the segments.
+    fn control_ids(&self) -> Option<&BTreeSet<Digest>> {
+        self.segment_verifier_parameters().map(|p| &p.control_ids)
+    }
+
+    /// The number of accepted segment control ids: 0 if there are no segment parameters.
+    fn control_id_count(&self) -> usize {
+        self.control_ids().map_or(0, BTreeSet::len)
+    }
+
+    /// The biggest segment po2 accepted by this verifier, if known (see
+    /// [SegmentReceiptVerifierParameters::max_po2]).
+    fn max_po2(&self) -> Option<u32> {
+        self.segment_verifier_parameters()
+            .and_then(SegmentReceiptVerifierParameters::max_po2)
+    }
+
+    /// The smallest segment po2 accepted by this verifier, if known (see
+    /// [SegmentReceiptVerifierParameters::min_po2]).
+    fn min_po2(&self) -> Option<u32> {
+        self.segment_verifier_parameters()
+            .and_then(SegmentReceiptVerifierParameters::min_po2)
+    }
+
+    /// A cheap estimate of the cost of verifying the given proof, computed without checking any
+    /// seal: useful to rate limit expensive verification requests before verifying them.
+    fn estimate_verify_cost(&self, proof: &Proof) -> ProofCost {
+        match &proof.inner {
+            InnerReceipt::Composite(inner) => ProofCost {
+                seal_words: inner
+                    .segments
+                    .iter()
+                    .map(|s| s.seal.len() as u64)
+                    .fold(0, u64::saturating_add),
+                segment_count: inner.segments.len(),
+                is_succinct: false,
+            },
+            InnerReceipt::Succinct(inner) => ProofCost {
+                seal_words: inner.seal.len() as u64,
+                segment_count: 1,
+                is_succinct: true,
+            },
+            InnerReceipt::Groth16(inner) => ProofCost {
+                seal_words: inner.seal.len().div_ceil(core::mem::size_of::<u32>()) as u64,
+                segment_count: 1,
+                is_succinct: true,
+            },
+            #[cfg(feature = "dev-mode")]
+            InnerReceipt::Fake(_) => ProofCost {
+                seal_words: 0,
+                segment_count: 0,
+                is_succinct: false,
+            },
+        }
+    }
+
+    /// A rough estimate of the work represented by the given proof: the sum of the cycles (see
+    /// [SegmentInfo::cycles]) of the segments of a composite receipt, saturating to `u64::MAX`.
+    /// A succinct or Groth16 receipt doesn't retain its segment sizes, so it's rejected with
+    /// [VerificationError::ReceiptFormatError].
+    fn estimate_cycles(&self, proof: &Proof) -> Result<u64, VerificationError> {
+        match &proof.inner {
+            InnerReceipt::Composite(inner) => Ok(self
+                .extract_composite_segments_info(inner)?
+                .iter()
+                .map(SegmentInfo::cycles)
+                .fold(0, u64::saturating_add)),
+            _ => Err(VerificationError::ReceiptFormatError),
+        }
+    }
+
     /// Return the modifiable succinct parameters (useful for testing)
     fn mut_succinct_verifier_parameters(
         &mut self,
     ) -> Option<&mut SuccinctReceiptVerifierParameters>;
 
+    /// Set the [SegmentReceiptVerifierParameters] used to verify the segments.
+    fn set_segment_verifier_parameters(&mut self, params: SegmentReceiptVerifierParameters);
+
+    /// Set the [SuccinctReceiptVerifierParameters] used to verify the succinct receipts.
+    fn set_succinct_verifier_parameters(&mut self, params: SuccinctReceiptVerifierParameters);
+
+    /// Return this verifier with the given [SegmentReceiptVerifierParameters] set.
+    fn with_segment_params(mut self, params: SegmentReceiptVerifierParameters) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_segment_verifier_parameters(params);
+        self
+    }
+
+    /// Return this verifier with the given [SuccinctReceiptVerifierParameters] set.
+    fn with_succinct_params(mut self, params: SuccinctReceiptVerifierParameters) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_succinct_verifier_parameters(params);
+        self
+    }
+
     /// Returns the power of 2 size of the segment circuit output.
     fn extract_composite_segments_info(
         &self,
@@ -61,18 +567,77 @@ pub trait Verifier {
         composite
             .segments
             .iter()
-            .map(|s| {
-                let (_, seal) = s.seal.split_at(self.seal_offset());
-                extract_po2::extract_segment_po2(seal, self.segment_circuit_output_size()).map(
-                    |po2| SegmentInfo {
-                        hash: s.hashfn.clone(),
-                        po2,
-                    },
-                )
-            })
+            .map(|s| s.segment_info_at(self.seal_offset(), self.segment_circuit_output_size()))
             .collect()
     }
 
+    /// Returns the power of 2 size of the recursion circuit output and the hash function of a
+    /// succinct receipt.
+    fn extract_succinct_info(
+        &self,
+        succinct: &SuccinctReceipt<ReceiptClaim>,
+    ) -> Result<SegmentInfo, VerificationError> {
+        succinct.succinct_info_at(self.succinct_circuit_output_size())
+    }
+
+    /// The zkVM version label of this verifier, e.g. `risc0/v1.2`.
+    fn version(&self) -> &str;
+
+    /// The protocol info of the segment circuit of this verifier.
+    fn circuit_info(&self) -> ProtocolInfo;
+
+    /// Human readable description of this verifier: its version, circuit, the verifier parameters
+    /// it holds and its hash suites.
+    fn describe(&self) -> String {
+        fn presence(present: bool) -> &'static str {
+            if present {
+                "present"
+            } else {
+                "missing"
+            }
+        }
+
+        let suites = HashFunction::ALL
+            .iter()
+            .map(|h| h.as_str())
+            .filter(|h| self.has_hash_suite(h))
+            .collect::<Vec<_>>();
+        format!(
+            "{} {{ circuit: {}, segment_params: {}, succinct_params: {}, groth16_params: {}, \
+             suites: {suites:?} }}",
+            self.version(),
+            self.circuit_info(),
+            presence(self.has_segment_params()),
+            presence(self.has_succinct_params()),
+            presence(self.groth16_verifier_parameters().is_some()),
+        )
+    }
+
+    /// Return this verifier wrapped in a [FilteredVerifier] that rejects the proofs for which
+    /// `predicate` returns `true` with [VerificationError::ReceiptFormatError], before verifying
+    /// them. For instance, to reject composite proofs with more than 10 segments:
+    ///
+    /// ```
+    /// use risc0_verifier::Verifier;
+    ///
+    /// let verifier = risc0_verifier::v2_1().reject_if(|p| {
+    ///     p.inner
+    ///         .composite()
+    ///         .map(|c| c.segments.len() > 10)
+    ///         .unwrap_or(false)
+    /// });
+    /// ```
+    fn reject_if<F>(self, predicate: F) -> FilteredVerifier<Self, F>
+    where
+        Self: Sized,
+        F: Fn(&Proof) -> bool + Send + Sync + 'static,
+    {
+        FilteredVerifier {
+            inner: self,
+            predicate,
+        }
+    }
+
     /// Box this Verifier into a `Box<dyn Verifier>`.
     fn boxed(self) -> Box<dyn Verifier>
     where
@@ -82,6 +647,12 @@ pub trait Verifier {
     }
 }
 
+impl fmt::Debug for dyn Verifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
 impl Verifier for Box<dyn Verifier> {
     fn verify(
         &self,
@@ -92,6 +663,60 @@ impl Verifier for Box<dyn Verifier> {
         self.as_ref().verify(image_id, proof, journal)
     }
 
+    fn verify_digest(
+        &self,
+        image_id: Digest,
+        proof: Proof,
+        journal_digest: Digest,
+    ) -> Result<(), VerificationError> {
+        self.as_ref().verify_digest(image_id, proof, journal_digest)
+    }
+
+    fn verify_with_exit_code(
+        &self,
+        image_id: Digest,
+        proof: Proof,
+        pubs: Journal,
+        exit_code: ExitCode,
+    ) -> Result<(), VerificationError> {
+        self.as_ref()
+            .verify_with_exit_code(image_id, proof, pubs, exit_code)
+    }
+
+    fn verify_conditional(
+        &self,
+        image_id: Digest,
+        proof: Proof,
+        pubs: Journal,
+    ) -> Result<Vec<Digest>, VerificationError> {
+        self.as_ref().verify_conditional(image_id, proof, pubs)
+    }
+
+    fn verify_segment_at(
+        &self,
+        composite: &CompositeReceipt,
+        index: usize,
+    ) -> Result<(), SegmentVerificationError> {
+        self.as_ref().verify_segment_at(composite, index)
+    }
+
+    fn verify_assumption(
+        &self,
+        receipt: &InnerAssumptionReceipt,
+        expected_claim: Digest,
+        control_root: Option<Digest>,
+    ) -> Result<(), VerificationError> {
+        self.as_ref()
+            .verify_assumption(receipt, expected_claim, control_root)
+    }
+
+    fn verify_batch(
+        &self,
+        items: &[(crate::Vk, Proof, Journal)],
+    ) -> alloc::vec::Vec<Result<(), VerificationError>> {
+        self.as_ref().verify_batch(items)
+    }
+
     fn seal_offset(&self) -> usize {
         self.as_ref().seal_offset()
     }
@@ -100,15 +725,350 @@ impl Verifier for Box<dyn Verifier> {
         self.as_ref().segment_circuit_output_size()
     }
 
+    fn succinct_circuit_output_size(&self) -> usize {
+        self.as_ref().succinct_circuit_output_size()
+    }
+
     fn set_poseidon2_mix_impl(&mut self, poseidon2: Box<dyn Poseidon2Mix + Send + Sync + 'static>) {
         self.as_mut().set_poseidon2_mix_impl(poseidon2)
     }
 
+    fn remove_hash_suite(&mut self, hashfn: &str) {
+        self.as_mut().remove_hash_suite(hashfn)
+    }
+
+    fn has_hash_suite(&self, hashfn: &str) -> bool {
+        self.as_ref().has_hash_suite(hashfn)
+    }
+
+    fn version(&self) -> &str {
+        self.as_ref().version()
+    }
+
+    fn circuit_info(&self) -> ProtocolInfo {
+        self.as_ref().circuit_info()
+    }
+
+    fn describe(&self) -> String {
+        self.as_ref().describe()
+    }
+
+    fn set_max_proof_bytes(&mut self, max: usize) {
+        self.as_mut().set_max_proof_bytes(max)
+    }
+
+    fn max_proof_bytes(&self) -> usize {
+        self.as_ref().max_proof_bytes()
+    }
+
+    fn set_max_journal_bytes(&mut self, n: usize) {
+        self.as_mut().set_max_journal_bytes(n)
+    }
+
+    fn max_journal_bytes(&self) -> Option<usize> {
+        self.as_ref().max_journal_bytes()
+    }
+
+    #[cfg(feature = "dev-mode")]
+    fn set_fake_receipts_allowed(&mut self, allowed: bool) {
+        self.as_mut().set_fake_receipts_allowed(allowed)
+    }
+
+    #[cfg(feature = "dev-mode")]
+    fn fake_receipts_allowed(&self) -> bool {
+        self.as_ref().fake_receipts_allowed()
+    }
+
+    fn segment_verifier_parameters(&self) -> Option<&SegmentReceiptVerifierParameters> {
+        self.as_ref().segment_verifier_parameters()
+    }
+
+    fn succinct_verifier_parameters(&self) -> Option<&SuccinctReceiptVerifierParameters> {
+        self.as_ref().succinct_verifier_parameters()
+    }
+
+    fn groth16_verifier_parameters(&self) -> Option<&Groth16ReceiptVerifierParameters> {
+        self.as_ref().groth16_verifier_parameters()
+    }
+
     fn mut_succinct_verifier_parameters(
         &mut self,
     ) -> Option<&mut SuccinctReceiptVerifierParameters> {
         self.as_mut().mut_succinct_verifier_parameters()
     }
+
+    fn set_segment_verifier_parameters(&mut self, params: SegmentReceiptVerifierParameters) {
+        self.as_mut().set_segment_verifier_parameters(params)
+    }
+
+    fn set_succinct_verifier_parameters(&mut self, params: SuccinctReceiptVerifierParameters) {
+        self.as_mut().set_succinct_verifier_parameters(params)
+    }
+}
+
+/// A [Verifier] that rejects the proofs matching a predicate before verifying them with the
+/// inner verifier: see [Verifier::reject_if].
+pub struct FilteredVerifier<V, F> {
+    inner: V,
+    predicate: F,
+}
+
+impl<V, F> FilteredVerifier<V, F> {
+    /// Return the inner verifier, dropping the predicate.
+    pub fn into_inner(self) -> V {
+        self.inner
+    }
+}
+
+impl<V: Verifier, F: Fn(&Proof) -> bool> Verifier for FilteredVerifier<V, F> {
+    fn verify(
+        &self,
+        image_id: Digest,
+        proof: Proof,
+        journal: Journal,
+    ) -> Result<(), VerificationError> {
+        if (self.predicate)(&proof) {
+            debug!("proof rejected by the filter predicate");
+            return Err(VerificationError::ReceiptFormatError);
+        }
+        self.inner.verify(image_id, proof, journal)
+    }
+
+    fn verify_digest(
+        &self,
+        image_id: Digest,
+        proof: Proof,
+        journal_digest: Digest,
+    ) -> Result<(), VerificationError> {
+        if (self.predicate)(&proof) {
+            debug!("proof rejected by the filter predicate");
+            return Err(VerificationError::ReceiptFormatError);
+        }
+        self.inner.verify_digest(image_id, proof, journal_digest)
+    }
+
+    fn verify_with_exit_code(
+        &self,
+        image_id: Digest,
+        proof: Proof,
+        pubs: Journal,
+        exit_code: ExitCode,
+    ) -> Result<(), VerificationError> {
+        if (self.predicate)(&proof) {
+            debug!("proof rejected by the filter predicate");
+            return Err(VerificationError::ReceiptFormatError);
+        }
+        self.inner
+            .verify_with_exit_code(image_id, proof, pubs, exit_code)
+    }
+
+    fn verify_conditional(
+        &self,
+        image_id: Digest,
+        proof: Proof,
+        pubs: Journal,
+    ) -> Result<Vec<Digest>, VerificationError> {
+        if (self.predicate)(&proof) {
+            debug!("proof rejected by the filter predicate");
+            return Err(VerificationError::ReceiptFormatError);
+        }
+        self.inner.verify_conditional(image_id, proof, pubs)
+    }
+
+    fn verify_segment_at(
+        &self,
+        composite: &CompositeReceipt,
+        index: usize,
+    ) -> Result<(), SegmentVerificationError> {
+        self.inner.verify_segment_at(composite, index)
+    }
+
+    fn verify_assumption(
+        &self,
+        receipt: &InnerAssumptionReceipt,
+        expected_claim: Digest,
+        control_root: Option<Digest>,
+    ) -> Result<(), VerificationError> {
+        self.inner
+            .verify_assumption(receipt, expected_claim, control_root)
+    }
+
+    fn seal_offset(&self) -> usize {
+        self.inner.seal_offset()
+    }
+
+    fn segment_circuit_output_size(&self) -> usize {
+        self.inner.segment_circuit_output_size()
+    }
+
+    fn succinct_circuit_output_size(&self) -> usize {
+        self.inner.succinct_circuit_output_size()
+    }
+
+    fn set_poseidon2_mix_impl(&mut self, poseidon2: Box<dyn Poseidon2Mix + Send + Sync + 'static>) {
+        self.inner.set_poseidon2_mix_impl(poseidon2)
+    }
+
+    fn remove_hash_suite(&mut self, hashfn: &str) {
+        self.inner.remove_hash_suite(hashfn)
+    }
+
+    fn has_hash_suite(&self, hashfn: &str) -> bool {
+        self.inner.has_hash_suite(hashfn)
+    }
+
+    fn version(&self) -> &str {
+        self.inner.version()
+    }
+
+    fn circuit_info(&self) -> ProtocolInfo {
+        self.inner.circuit_info()
+    }
+
+    fn describe(&self) -> String {
+        format!("Filtered({})", self.inner.describe())
+    }
+
+    fn set_max_proof_bytes(&mut self, max: usize) {
+        self.inner.set_max_proof_bytes(max)
+    }
+
+    fn max_proof_bytes(&self) -> usize {
+        self.inner.max_proof_bytes()
+    }
+
+    fn set_max_journal_bytes(&mut self, n: usize) {
+        self.inner.set_max_journal_bytes(n)
+    }
+
+    fn max_journal_bytes(&self) -> Option<usize> {
+        self.inner.max_journal_bytes()
+    }
+
+    #[cfg(feature = "dev-mode")]
+    fn set_fake_receipts_allowed(&mut self, allowed: bool) {
+        self.inner.set_fake_receipts_allowed(allowed)
+    }
+
+    #[cfg(feature = "dev-mode")]
+    fn fake_receipts_allowed(&self) -> bool {
+        self.inner.fake_receipts_allowed()
+    }
+
+    fn segment_verifier_parameters(&self) -> Option<&SegmentReceiptVerifierParameters> {
+        self.inner.segment_verifier_parameters()
+    }
+
+    fn succinct_verifier_parameters(&self) -> Option<&SuccinctReceiptVerifierParameters> {
+        self.inner.succinct_verifier_parameters()
+    }
+
+    fn groth16_verifier_parameters(&self) -> Option<&Groth16ReceiptVerifierParameters> {
+        self.inner.groth16_verifier_parameters()
+    }
+
+    fn mut_succinct_verifier_parameters(
+        &mut self,
+    ) -> Option<&mut SuccinctReceiptVerifierParameters> {
+        self.inner.mut_succinct_verifier_parameters()
+    }
+
+    fn set_segment_verifier_parameters(&mut self, params: SegmentReceiptVerifierParameters) {
+        self.inner.set_segment_verifier_parameters(params)
+    }
+
+    fn set_succinct_verifier_parameters(&mut self, params: SuccinctReceiptVerifierParameters) {
+        self.inner.set_succinct_verifier_parameters(params)
+    }
+}
+
+/// Error returned by [detect_verifier](crate::detect_verifier) when the verifier parameters of
+/// a proof don't match the ones of any supported prover version.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct UnknownProverVersion {
+    /// The `verifier_parameters` digest of the proof.
+    pub verifier_parameters: Digest,
+}
+
+impl fmt::Display for UnknownProverVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unknown prover version: no verifier has parameters digest {}",
+            self.verifier_parameters
+        )
+    }
+}
+
+/// Error returned by [Verifier::verify_segment_at].
+#[derive(Debug, PartialEq)]
+pub enum SegmentVerificationError {
+    /// The composite receipt has no segment at `index`: it has only `len` segments.
+    IndexOutOfRange {
+        /// The requested segment index.
+        index: usize,
+        /// The number of segments of the composite receipt.
+        len: usize,
+    },
+    /// The segment doesn't verify.
+    Invalid(VerificationError),
+}
+
+impl fmt::Display for SegmentVerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::IndexOutOfRange { index, len } => write!(
+                f,
+                "segment index {index} out of range: the receipt has {len} segments"
+            ),
+            Self::Invalid(e) => write!(f, "invalid segment: {e}"),
+        }
+    }
+}
+
+impl From<VerificationError> for SegmentVerificationError {
+    fn from(e: VerificationError) -> Self {
+        Self::Invalid(e)
+    }
+}
+
+fn check_image_id(image_id: Digest) -> Result<(), VerificationError> {
+    crate::Vk(image_id).verify_well_formed().map_err(|err| {
+        debug!("malformed image id {image_id}: {err}");
+        VerificationError::ImageVerificationError
+    })
+}
+
+fn check_journal(
+    pubs: &Journal,
+    max_journal_bytes: Option<usize>,
+) -> Result<(), VerificationError> {
+    match max_journal_bytes {
+        Some(limit) if pubs.bytes.len() > limit => {
+            debug!(
+                "{}",
+                crate::JournalTooLargeError {
+                    limit,
+                    got: pubs.bytes.len()
+                }
+            );
+            Err(VerificationError::ReceiptFormatError)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Verify the proof like [Verifier::verify] without taking the ownership of the proof and
+/// the journal.
+fn verify_borrowed(
+    ctx: &impl VerifierContext,
+    image_id: Digest,
+    proof: &Proof,
+    pubs: &Journal,
+) -> Result<(), VerificationError> {
+    check_image_id(image_id)?;
+    check_journal(pubs, ctx.verifier_parameters().options.max_journal_bytes)?;
+    proof.verify_with_journal_digest(ctx, image_id, pubs.digest())
 }
 
 impl<Seg: 'static, Suc: 'static, HashSuite: 'static, T> Verifier for T
@@ -123,7 +1083,94 @@ where
         proof: Proof,
         pubs: Journal,
     ) -> Result<(), VerificationError> {
-        proof.verify(self, image_id, pubs.digest())
+        verify_borrowed(self, image_id, &proof, &pubs)
+    }
+
+    fn verify_digest(
+        &self,
+        image_id: Digest,
+        proof: Proof,
+        journal_digest: Digest,
+    ) -> Result<(), VerificationError> {
+        check_image_id(image_id)?;
+        proof.verify_with_journal_digest(self, image_id, journal_digest)
+    }
+
+    fn verify_with_exit_code(
+        &self,
+        image_id: Digest,
+        proof: Proof,
+        pubs: Journal,
+        exit_code: ExitCode,
+    ) -> Result<(), VerificationError> {
+        check_image_id(image_id)?;
+        check_journal(&pubs, self.verifier_parameters().options.max_journal_bytes)?;
+        proof.verify_with_exit_code(self, image_id, pubs.digest(), exit_code)
+    }
+
+    fn verify_conditional(
+        &self,
+        image_id: Digest,
+        proof: Proof,
+        pubs: Journal,
+    ) -> Result<Vec<Digest>, VerificationError> {
+        check_image_id(image_id)?;
+        check_journal(&pubs, self.verifier_parameters().options.max_journal_bytes)?;
+        proof.verify_conditional(self, image_id, pubs.digest())
+    }
+
+    fn verify_segment_at(
+        &self,
+        composite: &CompositeReceipt,
+        index: usize,
+    ) -> Result<(), SegmentVerificationError> {
+        let segment =
+            composite
+                .segments
+                .get(index)
+                .ok_or(SegmentVerificationError::IndexOutOfRange {
+                    index,
+                    len: composite.segments.len(),
+                })?;
+        Ok(segment.verify_integrity_with_context(self)?)
+    }
+
+    fn verify_assumption(
+        &self,
+        receipt: &InnerAssumptionReceipt,
+        expected_claim: Digest,
+        control_root: Option<Digest>,
+    ) -> Result<(), VerificationError> {
+        let assumption = Assumption {
+            claim: expected_claim,
+            control_root: control_root.unwrap_or(Digest::ZERO),
+        };
+        receipt.verify_with_assumption(self, &assumption)
+    }
+
+    fn verify_batch(
+        &self,
+        items: &[(crate::Vk, Proof, Journal)],
+    ) -> alloc::vec::Vec<Result<(), VerificationError>> {
+        items
+            .iter()
+            .map(|(vk, proof, pubs)| verify_borrowed(self, vk.0, proof, pubs))
+            .collect()
+    }
+
+    fn verify_parallel(
+        new_verifier: impl Fn() -> Self + Send + Sync,
+        image_id: Digest,
+        proof: Proof,
+        pubs: Journal,
+    ) -> Result<(), VerificationError> {
+        check_image_id(image_id)?;
+        let verifier = new_verifier();
+        check_journal(
+            &pubs,
+            verifier.verifier_parameters().options.max_journal_bytes,
+        )?;
+        proof.verify_parallel_with(&verifier, new_verifier, image_id, pubs.digest())
     }
 
     fn seal_offset(&self) -> usize {
@@ -133,10 +1180,68 @@ where
         self.verifier_parameters().segment.size()
     }
 
+    fn succinct_circuit_output_size(&self) -> usize {
+        self.succinct_output_size()
+    }
+
     fn set_poseidon2_mix_impl(&mut self, poseidon2: Box<dyn Poseidon2Mix + Send + Sync + 'static>) {
         <Self as VerifierContext>::set_poseidon2_mix_impl(self, poseidon2)
     }
 
+    fn remove_hash_suite(&mut self, hashfn: &str) {
+        self.mut_verifier_parameters().suites.remove(hashfn);
+    }
+
+    fn has_hash_suite(&self, hashfn: &str) -> bool {
+        self.verifier_parameters().suite(hashfn).is_some()
+    }
+
+    fn version(&self) -> &str {
+        VerifierContext::version(self)
+    }
+
+    fn circuit_info(&self) -> ProtocolInfo {
+        self.segment_circuit_info()
+    }
+
+    fn set_max_proof_bytes(&mut self, max: usize) {
+        self.mut_verifier_parameters().options.max_proof_bytes = max;
+    }
+
+    fn max_proof_bytes(&self) -> usize {
+        self.verifier_parameters().options.max_proof_bytes
+    }
+
+    fn set_max_journal_bytes(&mut self, n: usize) {
+        self.mut_verifier_parameters().options.max_journal_bytes = Some(n);
+    }
+
+    fn max_journal_bytes(&self) -> Option<usize> {
+        self.verifier_parameters().options.max_journal_bytes
+    }
+
+    #[cfg(feature = "dev-mode")]
+    fn set_fake_receipts_allowed(&mut self, allowed: bool) {
+        self.mut_verifier_parameters().options.allow_fake_receipts = allowed;
+    }
+
+    #[cfg(feature = "dev-mode")]
+    fn fake_receipts_allowed(&self) -> bool {
+        self.verifier_parameters().options.allow_fake_receipts
+    }
+
+    fn segment_verifier_parameters(&self) -> Option<&SegmentReceiptVerifierParameters> {
+        self.verifier_parameters().segment_verifier_parameters()
+    }
+
+    fn succinct_verifier_parameters(&self) -> Option<&SuccinctReceiptVerifierParameters> {
+        self.verifier_parameters().succinct_verifier_parameters()
+    }
+
+    fn groth16_verifier_parameters(&self) -> Option<&Groth16ReceiptVerifierParameters> {
+        self.verifier_parameters().groth16_verifier_parameters()
+    }
+
     fn mut_succinct_verifier_parameters(
         &mut self,
     ) -> Option<&mut SuccinctReceiptVerifierParameters> {
@@ -144,4 +1249,12 @@ where
             .succinct_verifier_parameters
             .as_mut()
     }
+
+    fn set_segment_verifier_parameters(&mut self, params: SegmentReceiptVerifierParameters) {
+        self.mut_verifier_parameters().segment_verifier_parameters = Some(params);
+    }
+
+    fn set_succinct_verifier_parameters(&mut self, params: SuccinctReceiptVerifierParameters) {
+        self.mut_verifier_parameters().succinct_verifier_parameters = Some(params);
+    }
 }
@@ -22,12 +22,16 @@ use risc0_zkp_v1::core::hash::{Rng, RngFactory};
 use risc0_zkp_v1::verify::{ReadIOP, VerificationError};
 
 pub fn extract_segment_po2(seal: &[u32], output_size: usize) -> Result<u32, VerificationError> {
+    use risc0_zkp_v1::field::Elem;
+    if seal.len() < (output_size + 1) * BabyBearElem::WORDS {
+        debug!("seal too short to contain the circuit output and the po2");
+        return Err(VerificationError::ReceiptFormatError);
+    }
     let mut iop = ReadIOP::<risc0_zkp_v1::field::baby_bear::BabyBear>::new(seal, &FakeRngFactory);
     let slice: &[BabyBearElem] = iop.read_field_elem_slice(output_size + 1);
     let (_, &[po2_elem]) = slice.split_at(output_size) else {
         unreachable!()
     };
-    use risc0_zkp_v1::field::Elem;
     let (&[po2], &[]) = po2_elem.to_u32_words().split_at(1) else {
         // That means BabyBear field is more than one u32
         core::panic!("po2 elem is larger than u32");
@@ -14,8 +14,10 @@
 // limitations under the License.
 
 use risc0_verifier::{
-    v1_0, v1_1, v1_2, v2_0, v2_1, v2_2, v2_3, v3_0, verify, CompositeReceipt, Journal, Proof,
-    SegmentInfo, SuccinctReceipt, Verifier, Vk,
+    v1_0, v1_1, v1_2, v2_0, v2_1, v2_2, v2_3, v3_0, verify, CompositeReceipt,
+    InnerAssumptionReceipt, Journal, Proof, SegmentInfo, SegmentReceipt,
+    SegmentReceiptVerifierParameters, SegmentReceiptsExt, SegmentVerificationError,
+    SuccinctReceipt, SuccinctReceiptVerifierParameters, Verifier, Vk,
 };
 use risc0_zkp_v1::verify::VerificationError;
 use rstest::rstest;
@@ -43,6 +45,21 @@ mod legacy {
         verifier.verify(vk.into(), proof, journal).unwrap()
     }
 
+    #[cfg(feature = "migration")]
+    #[rstest]
+    fn should_verify_migrated_proof(#[files("./resources/old/valid_proof_*.json")] path: PathBuf) {
+        use risc0_verifier::{migrate_journal_bincode_to_cbor, migrate_proof_bincode_to_cbor};
+
+        let (vk, proof, pubs) = load_data(&path);
+
+        let proof = migrate_proof_bincode_to_cbor(&proof).unwrap();
+        let pubs = migrate_journal_bincode_to_cbor(&pubs).unwrap();
+        let proof: Proof = ciborium::from_reader(proof.as_slice()).unwrap();
+        let journal: Journal = ciborium::from_reader(pubs.as_slice()).unwrap();
+
+        v1_0().verify(vk.into(), proof, journal).unwrap()
+    }
+
     fn load_data(path: &Path) -> ([u32; 8], Vec<u8>, Vec<u8>) {
         #[derive(Deserialize)]
         struct Data {
@@ -122,7 +139,7 @@ fn read_po2_segment_v1(
         .extract_composite_segments_info(proof.inner.composite().unwrap())
         .unwrap();
 
-    assert_eq!(vec![SegmentInfo::new(hash.to_owned(), expected_po2)], po2s)
+    assert_eq!(vec![SegmentInfo::dummy(hash, expected_po2)], po2s)
 }
 
 #[rstest]
@@ -191,6 +208,32 @@ fn read_po2_segments(
     assert_eq!(expected, po2s)
 }
 
+#[rstest]
+#[case(v1_0(), "./resources/cases/prover_1.0.3/vm_1.0.5/sha_22.json", 2 * (1 << 20) + (1 << 17))]
+#[case(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json", 2 * (1 << 20) + (1 << 17))]
+#[case(v2_1(), "./resources/cases/prover_2.1.0/vm_2.1.0/poseidon2_16.json", 1 << 16)]
+#[case(v2_1(), "./resources/cases/prover_2.1.0/vm_2.1.0/poseidon2_22.json", 4 * (1 << 20))]
+#[case(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/poseidon2_22.json", 4 * (1 << 20))]
+fn estimate_cycles(#[case] verifier: impl Verifier, #[case] path: &str, #[case] expected: u64) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+
+    assert_eq!(Ok(expected), verifier.estimate_cycles(&proof));
+}
+
+#[rstest]
+#[case(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/succinct_22.json")]
+#[case(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/succinct_22.json")]
+fn estimate_cycles_rejects_succinct_proofs(#[case] verifier: impl Verifier, #[case] path: &str) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+
+    assert_eq!(
+        Err(VerificationError::ReceiptFormatError),
+        verifier.estimate_cycles(&proof)
+    );
+}
+
 #[rstest]
 #[case(v1_2(), "./resources/cases/poseidon2_22_segment_20.json")]
 #[case(v2_1(), "./resources/cases/prover_2.1.0/vm_2.1.0/poseidon2_22.json")]
@@ -214,7 +257,7 @@ mod v1_0 {
 
     #[rstest]
     #[case::should_pass(v1_0())]
-    #[should_panic(expected = "control_id mismatch")]
+    #[should_panic(expected = "receipt was produced for a version of the verifier")]
     #[case::should_fails_with_new_verifier(v1_1())]
     fn verify_valid_proof(
         #[case] verifier: impl Verifier,
@@ -228,6 +271,23 @@ mod v1_0 {
             .verify(case.vk.into(), proof, case.journal)
             .unwrap()
     }
+
+    #[rstest]
+    #[case::should_pass(v1_0())]
+    #[should_panic(expected = "control_id mismatch")]
+    #[case::should_fails_with_new_verifier(v1_1())]
+    fn verify_valid_proof_with_matching_parameters(
+        #[case] verifier: impl Verifier,
+        #[files("./resources/cases/prover_1.0.*/**/*.json")] path: PathBuf,
+    ) {
+        let case: Case = read_all(path).unwrap();
+
+        let proof = with_verifier_parameters_of(case.get_proof().unwrap(), &verifier);
+
+        verifier
+            .verify(case.vk.into(), proof, case.journal)
+            .unwrap()
+    }
 }
 
 mod v1_1 {
@@ -235,7 +295,7 @@ mod v1_1 {
 
     #[rstest]
     #[case::should_pass(v1_1())]
-    #[should_panic(expected = "control_id mismatch")]
+    #[should_panic(expected = "receipt was produced for a version of the verifier")]
     #[case::should_fails_with_old_verifier(v1_0())]
     fn verify_valid_proof(
         #[case] verifier: impl Verifier,
@@ -249,6 +309,23 @@ mod v1_1 {
             .verify(case.vk.into(), proof, case.journal)
             .unwrap()
     }
+
+    #[rstest]
+    #[case::should_pass(v1_1())]
+    #[should_panic(expected = "control_id mismatch")]
+    #[case::should_fails_with_old_verifier(v1_0())]
+    fn verify_valid_proof_with_matching_parameters(
+        #[case] verifier: impl Verifier,
+        #[files("./resources/cases/prover_1.1.*/**/*.json")] path: PathBuf,
+    ) {
+        let case: Case = read_all(path).unwrap();
+
+        let proof = with_verifier_parameters_of(case.get_proof().unwrap(), &verifier);
+
+        verifier
+            .verify(case.vk.into(), proof, case.journal)
+            .unwrap()
+    }
 }
 
 mod v1_2 {
@@ -256,7 +333,7 @@ mod v1_2 {
 
     #[rstest]
     #[case::should_pass(v1_2())]
-    #[should_panic(expected = "control_id mismatch")]
+    #[should_panic(expected = "receipt was produced for a version of the verifier")]
     #[case::should_fails_with_old_verifier(v1_0())]
     fn verify_valid_proof(
         #[case] verifier: impl Verifier,
@@ -270,6 +347,23 @@ mod v1_2 {
             .verify(case.vk.into(), proof, case.journal)
             .unwrap()
     }
+
+    #[rstest]
+    #[case::should_pass(v1_2())]
+    #[should_panic(expected = "control_id mismatch")]
+    #[case::should_fails_with_old_verifier(v1_0())]
+    fn verify_valid_proof_with_matching_parameters(
+        #[case] verifier: impl Verifier,
+        #[files("./resources/cases/prover_1.2.*/**/*.json")] path: PathBuf,
+    ) {
+        let case: Case = read_all(path).unwrap();
+
+        let proof = with_verifier_parameters_of(case.get_proof().unwrap(), &verifier);
+
+        verifier
+            .verify(case.vk.into(), proof, case.journal)
+            .unwrap()
+    }
 }
 
 mod v2_0 {
@@ -376,6 +470,8 @@ mod v3_0 {
     #[case::should_pass(v3_0())]
     #[should_panic]
     #[case::should_fails_with_old_verifier(v2_1().boxed())]
+    #[should_panic]
+    #[case::should_fails_with_v2_2_verifier(v2_2().boxed())]
     fn verify_valid_proof(
         #[case] verifier: impl Verifier,
         #[files("./resources/cases/prover_3.0.*/**/*.json")] path: PathBuf,
@@ -390,11 +486,149 @@ mod v3_0 {
     }
 }
 
+// The Groth16 fixtures are generated with `generate_proofs` (`--provers groth16`) and saved in
+// `resources/cases/groth16`: proving them needs the Groth16 prover, so these tests are ignored till
+// the fixtures are committed.
+mod groth16 {
+    use super::*;
+    use risc0_verifier::{Digestible, Groth16ReceiptVerifierParameters, InnerReceipt};
+
+    #[rstest_reuse::template]
+    #[rstest]
+    #[case::v1_2(
+        v1_2().boxed(),
+        Groth16ReceiptVerifierParameters::v1_2(),
+        "./resources/cases/groth16/prover_1.2.0/vm_1.2.0/groth16_16.json"
+    )]
+    #[case::v2_3(
+        v2_3().boxed(),
+        Groth16ReceiptVerifierParameters::v2_3(),
+        "./resources/cases/groth16/prover_2.3.0/vm_2.3.0/groth16_16.json"
+    )]
+    #[case::v3_0(
+        v3_0().boxed(),
+        Groth16ReceiptVerifierParameters::v3_0(),
+        "./resources/cases/groth16/prover_3.0.0/vm_3.0.0/groth16_16.json"
+    )]
+    fn groth16_cases(
+        #[case] verifier: Box<dyn Verifier>,
+        #[case] params: Groth16ReceiptVerifierParameters,
+        #[case] path: &str,
+    ) {
+    }
+
+    #[rstest_reuse::apply(groth16_cases)]
+    #[ignore = "needs the Groth16 fixtures"]
+    fn should_verify_valid_proof(
+        #[case] verifier: Box<dyn Verifier>,
+        #[case] params: Groth16ReceiptVerifierParameters,
+        #[case] path: &str,
+    ) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+        assert_eq!(
+            params.digest(),
+            proof.inner.groth16().unwrap().verifier_parameters
+        );
+
+        verifier
+            .verify(case.vk.into(), proof, case.journal)
+            .unwrap()
+    }
+
+    #[rstest_reuse::apply(groth16_cases)]
+    #[ignore = "needs the Groth16 fixtures"]
+    fn should_reject_wrong_journal(
+        #[case] verifier: Box<dyn Verifier>,
+        #[case] _params: Groth16ReceiptVerifierParameters,
+        #[case] path: &str,
+    ) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+        let mut journal = case.journal.bytes;
+        journal[0] ^= 1;
+
+        let res = verifier.verify(case.vk.into(), proof, Journal::new(journal));
+
+        assert!(
+            matches!(res, Err(VerificationError::JournalDigestMismatch)),
+            "Invalid err {res:?}"
+        );
+    }
+
+    #[rstest_reuse::apply(groth16_cases)]
+    #[ignore = "needs the Groth16 fixtures"]
+    fn should_reject_wrong_image_id(
+        #[case] verifier: Box<dyn Verifier>,
+        #[case] _params: Groth16ReceiptVerifierParameters,
+        #[case] path: &str,
+    ) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+        let mut vk = case.vk.0;
+        vk.as_mut_words()[0] ^= 1;
+
+        let res = verifier.verify(vk, proof, case.journal);
+
+        assert!(
+            matches!(res, Err(VerificationError::ClaimDigestMismatch { .. })),
+            "Invalid err {res:?}"
+        );
+    }
+
+    #[rstest_reuse::apply(groth16_cases)]
+    #[ignore = "needs the Groth16 fixtures"]
+    fn should_reject_tampered_seal(
+        #[case] verifier: Box<dyn Verifier>,
+        #[case] _params: Groth16ReceiptVerifierParameters,
+        #[case] path: &str,
+    ) {
+        let case: Case = read_all(path).unwrap();
+        let mut proof = case.get_proof().unwrap();
+        match &mut proof.inner {
+            InnerReceipt::Groth16(inner) => inner.seal[0] ^= 1,
+            _ => unreachable!("not a Groth16 case"),
+        }
+
+        assert!(verifier
+            .verify(case.vk.into(), proof, case.journal)
+            .is_err());
+    }
+
+    #[rstest]
+    #[case::v1_2_with_v3_0(
+        v3_0().boxed(),
+        "./resources/cases/groth16/prover_1.2.0/vm_1.2.0/groth16_16.json"
+    )]
+    #[case::v3_0_with_v1_2(
+        v1_2().boxed(),
+        "./resources/cases/groth16/prover_3.0.0/vm_3.0.0/groth16_16.json"
+    )]
+    #[ignore = "needs the Groth16 fixtures"]
+    fn should_reject_proof_of_other_prover_version(
+        #[case] verifier: Box<dyn Verifier>,
+        #[case] path: &str,
+    ) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+
+        let res = verifier.verify(case.vk.into(), proof, case.journal);
+
+        assert!(
+            matches!(
+                res,
+                Err(VerificationError::VerifierParametersMismatch { .. })
+            ),
+            "Invalid err {res:?}"
+        );
+    }
+}
+
 mod use_custom_local_implemented_hash_function {
     use super::*;
 
     use risc0_verifier::poseidon2_injection::{
-        poseidon2_mix, BabyBearElem, Poseidon2Mix, POSEIDON2_CELLS,
+        poseidon2_mix, BabyBearElem, DefaultPoseidon2, NoOpPoseidon2, Poseidon2Mix, POSEIDON2_CELLS,
     };
 
     pub struct LocPoseidon2;
@@ -495,107 +729,2430 @@ mod use_custom_local_implemented_hash_function {
             .verify(case.vk.into(), proof, case.journal)
             .unwrap()
     }
-}
 
-#[rstest_reuse::apply(segments)]
-fn fails_on_invalid_segment(
-    #[case] verifier: impl Verifier,
-    #[case] path: &str,
-    #[values(0, 1, 2)] segment: usize,
-) {
-    let case: Case = read_all(path).unwrap();
-    let mut proof = case.get_proof().unwrap();
+    #[rstest]
+    #[case::v1(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_22.json")]
+    #[case::v2(v2_1(), "./resources/cases/prover_2.1.0/vm_2.1.0/poseidon2_22.json")]
+    #[case::v3(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/poseidon2_22.json")]
+    fn should_verify_with_default_poseidon2(
+        #[case] mut verifier: impl Verifier,
+        #[case] path: &str,
+    ) {
+        verifier.set_poseidon2_mix_impl(Box::new(DefaultPoseidon2));
 
-    let seal = proof.inner.mut_composite().unwrap().segments[segment]
-        .seal
-        .as_mut_slice();
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
 
-    seal[seal.len() / 2] = seal[seal.len() / 2].wrapping_add(1);
+        verifier
+            .verify(case.vk.into(), proof, case.journal)
+            .unwrap()
+    }
 
-    let res = verifier.verify(case.vk.into(), proof, case.journal);
+    #[rstest]
+    #[case::v1(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_22.json")]
+    #[case::v2(v2_1(), "./resources/cases/prover_2.1.0/vm_2.1.0/poseidon2_22.json")]
+    #[case::v3(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/poseidon2_22.json")]
+    fn should_reject_with_no_op_poseidon2(#[case] mut verifier: impl Verifier, #[case] path: &str) {
+        verifier.set_poseidon2_mix_impl(Box::new(NoOpPoseidon2));
 
-    assert!(res.is_err());
-    assert!(matches!(res, Err(VerificationError::InvalidProof)));
-}
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
 
-#[rstest_reuse::apply(succinct)]
-fn fails_on_invalid_succinct(#[case] verifier: impl Verifier, #[case] path: &str) {
-    let case: Case = read_all(path).unwrap();
-    let mut proof = case.get_proof().unwrap();
+        let res = verifier.verify(case.vk.into(), proof, case.journal);
 
-    let seal = proof.inner.mut_succinct().unwrap().seal.as_mut_slice();
+        assert!(
+            matches!(res, Err(VerificationError::InvalidProof)),
+            "Invalid err {res:?}"
+        );
+    }
+}
 
-    seal[seal.len() / 2] = seal[seal.len() / 2].wrapping_add(1);
+mod parameters_introspection {
+    use super::*;
 
-    let res = verifier.verify(case.vk.into(), proof, case.journal);
+    #[rstest]
+    fn should_expose_the_verifier_parameters(
+        #[values(v1_0(), v1_1(), v1_2(), v2_0(), v2_1(), v2_2(), v2_3(), v3_0())]
+        verifier: impl Verifier,
+    ) {
+        assert!(verifier.has_segment_params());
+        assert!(verifier.has_succinct_params());
+        assert!(verifier.segment_protocol_info().is_some());
+        assert!(verifier.succinct_control_root().is_some());
+
+        let segment_info = verifier.segment_protocol_info();
+        let control_root = verifier.succinct_control_root();
+        verifier.inspect_parameters(|segment, succinct| {
+            assert_eq!(segment_info, segment.map(|p| p.circuit_info));
+            assert_eq!(control_root, succinct.map(|p| p.control_root));
+        });
+    }
 
-    assert!(res.is_err());
-    assert!(matches!(res, Err(VerificationError::InvalidProof)));
+    #[test]
+    fn should_expose_the_parameters_of_a_boxed_verifier() {
+        let verifier = v1_2().boxed();
+
+        assert_eq!(
+            Some(SegmentReceiptVerifierParameters::v1_2().circuit_info),
+            verifier.segment_protocol_info()
+        );
+        assert_eq!(
+            Some(SuccinctReceiptVerifierParameters::v1_2().control_root),
+            verifier.succinct_control_root()
+        );
+    }
+
+    #[rstest]
+    #[case::v1_0(v1_0().boxed(), b"RV32IM:rev1v1___")]
+    #[case::v1_1(v1_1().boxed(), b"RV32IM:rev1v1___")]
+    #[case::v1_2(v1_2().boxed(), b"RV32IM:rev1v1___")]
+    #[case::v2_0(v2_0().boxed(), b"RV32IM:v2_______")]
+    #[case::v2_1(v2_1().boxed(), b"RV32IM:v2_______")]
+    #[case::v2_2(v2_2().boxed(), b"RV32IM:v2_______")]
+    #[case::v2_3(v2_3().boxed(), b"RV32IM:v2_______")]
+    #[case::v3_0(v3_0().boxed(), b"RV32IM:v2rev2___")]
+    fn should_expose_the_protocol_info(
+        #[case] verifier: Box<dyn Verifier>,
+        #[case] segment_info: &[u8; 16],
+    ) {
+        assert_eq!(
+            Some(*segment_info),
+            verifier.segment_protocol_info().map(|p| p.0)
+        );
+        assert_eq!(
+            Some(*b"RECURSION:rev1v1"),
+            verifier.succinct_protocol_info().map(|p| p.0)
+        );
+    }
 }
 
-#[rstest_reuse::apply(all)]
-fn fails_on_invalid_vk(#[case] verifier: impl Verifier, #[case] path: &str) {
-    let mut case: Case = read_all(path).unwrap();
-    let proof = case.get_proof().unwrap();
+mod estimate_verify_cost {
+    use super::*;
+    use risc0_verifier::ProofCost;
 
-    if let Some(l) = case.vk.0.as_mut_words().last_mut() {
-        *l = l.wrapping_add(1);
+    fn cost(path: &str) -> ProofCost {
+        let case: Case = read_all(path).unwrap();
+        v1_2().estimate_verify_cost(&case.get_proof().unwrap())
     }
 
-    let res = verifier.verify(case.vk.into(), proof, case.journal);
+    #[test]
+    fn should_cost_more_for_bigger_composite_proofs() {
+        let small = cost("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json");
+        let big = cost("./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json");
+
+        assert_eq!(1, small.segment_count);
+        assert_eq!(3, big.segment_count);
+        assert!(!small.is_succinct && !big.is_succinct);
+        assert!(big.seal_words > small.seal_words);
+        assert!(big > small);
+    }
 
-    assert!(res.is_err());
-    assert!(
-        matches!(res, Err(VerificationError::ClaimDigestMismatch { .. })),
-        "Invalid err {res:?}"
-    );
+    #[test]
+    fn should_estimate_succinct_proofs() {
+        let case: Case =
+            read_all("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json").unwrap();
+        let proof = case.get_proof().unwrap();
+
+        let cost = v1_2().boxed().estimate_verify_cost(&proof);
+
+        assert_eq!(
+            ProofCost {
+                seal_words: proof.inner.succinct().unwrap().seal.len() as u64,
+                segment_count: 1,
+                is_succinct: true,
+            },
+            cost
+        );
+        assert_eq!(
+            format!("{} seal words, 1 segments", cost.seal_words),
+            cost.to_string()
+        );
+    }
+
+    #[test]
+    fn should_not_compare_different_costs_with_the_same_seal_words() {
+        let composite = ProofCost {
+            seal_words: 10,
+            segment_count: 2,
+            is_succinct: false,
+        };
+        let succinct = ProofCost {
+            seal_words: 10,
+            segment_count: 1,
+            is_succinct: true,
+        };
+
+        assert_eq!(None, composite.partial_cmp(&succinct));
+        assert_eq!(
+            Some(std::cmp::Ordering::Equal),
+            composite.partial_cmp(&composite)
+        );
+    }
 }
 
-#[rstest_reuse::apply(all)]
-fn fails_on_invalid_pubs(#[case] verifier: impl Verifier, #[case] path: &str) {
-    let mut case: Case = read_all(path).unwrap();
-    let proof = case.get_proof().unwrap();
+mod hash_suites_builders {
+    use super::*;
+    use risc0_verifier::{poseidon2_injection::DefaultPoseidon2, HashFunction};
 
-    if let Some(l) = case.journal.bytes.last_mut() {
-        *l = l.wrapping_add(1);
+    #[test]
+    fn should_reject_blake2b_segments() {
+        let case: Case = read_all("./resources/cases/prover_1.2.0/vm_1.2.0/sha_16.json").unwrap();
+        let mut proof = case.get_proof().unwrap();
+        for segment in proof.inner.mut_composite().unwrap().segments.iter_mut() {
+            segment.hashfn = HashFunction::Blake2b;
+        }
+
+        let res = v1_2()
+            .reject_blake2b()
+            .verify(case.vk.into(), proof, case.journal);
+
+        assert!(
+            matches!(res, Err(VerificationError::InvalidHashSuite)),
+            "Invalid err {res:?}"
+        );
     }
 
-    let res = verifier.verify(case.vk.into(), proof, case.journal);
+    #[rstest]
+    #[case::v1_2(
+        v1_2(),
+        "./resources/cases/prover_1.2.0/vm_1.2.0/sha_16.json",
+        VerificationError::InvalidHashSuite
+    )]
+    #[case::v2_0(
+        v2_0(),
+        "./resources/cases/reject/prover_2.0.0/vm_2.0.0/sha_16.json",
+        VerificationError::ReceiptFormatError
+    )]
+    fn should_reject_sha256_segments(
+        #[case] verifier: impl Verifier,
+        #[case] path: &str,
+        #[case] expected: VerificationError,
+    ) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
 
-    assert!(res.is_err());
-    assert!(
-        matches!(res, Err(VerificationError::ClaimDigestMismatch { .. })),
-        "Invalid err {res:?}"
-    );
+        let res = verifier
+            .reject_sha256()
+            .verify(case.vk.into(), proof, case.journal);
+
+        assert_eq!(Err(expected), res);
+    }
+
+    #[rstest]
+    #[case::composite("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json")]
+    #[case::succinct("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")]
+    fn should_reject_poseidon2(#[case] path: &str) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+
+        let res = v1_2()
+            .reject_poseidon2()
+            .verify(case.vk.into(), proof, case.journal);
+
+        assert_eq!(Err(VerificationError::InvalidHashSuite), res);
+    }
+
+    #[test]
+    fn should_verify_with_injected_poseidon2() {
+        let case: Case =
+            read_all("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json").unwrap();
+        let proof = case.get_proof().unwrap();
+
+        v1_2()
+            .reject_sha256()
+            .with_poseidon2(DefaultPoseidon2)
+            .verify(case.vk.into(), proof, case.journal)
+            .unwrap();
+    }
 }
 
 #[rstest_reuse::apply(segments)]
-fn fails_on_invalid_claim(#[case] verifier: impl Verifier, #[case] path: &str) {
+fn prune_composite_seals(#[case] _verifier: impl Verifier, #[case] path: &str) {
     let case: Case = read_all(path).unwrap();
-    let mut proof = case.get_proof().unwrap();
-
-    proof.inner.mut_composite().unwrap().segments[0]
-        .claim
-        .exit_code = risc0_binfmt_v1::ExitCode::Halted(0);
+    let composite = case.get_proof().unwrap().inner.composite().unwrap().clone();
 
-    let res = verifier.verify(case.vk.into(), proof, case.journal);
+    let pruned = composite.clone().prune_seals();
 
-    assert!(res.is_err());
+    assert_eq!(composite.segments.len(), pruned.segments.len());
+    let saved: usize = pruned
+        .segments
+        .iter()
+        .zip(composite.segments.iter())
+        .map(|(p, s)| p.size_saved(s))
+        .sum();
+    assert_eq!(composite.seal_size() - 32 * composite.segments.len(), saved);
     assert!(
-        matches!(res, Err(VerificationError::ClaimDigestMismatch { .. })),
-        "Invalid err {res:?}"
+        bincode::serialize(&pruned).unwrap().len() + saved
+            <= bincode::serialize(&composite).unwrap().len()
     );
 }
 
-#[rstest_reuse::apply(succinct)]
-fn fails_on_invalid_inner_control_root(#[case] mut verifier: impl Verifier, #[case] path: &str) {
+mod verify_and_decode {
+    use super::*;
+    use risc0_verifier::Digestible;
+
+    #[rstest]
+    #[case::composite_v1_2(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json")]
+    #[case::succinct_v1_2(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")]
+    #[case::composite_v2_1(v2_1(), "./resources/cases/prover_2.1.0/vm_2.1.0/poseidon2_16.json")]
+    #[case::succinct_v2_1(v2_1(), "./resources/cases/prover_2.1.0/vm_2.1.0/succinct_16.json")]
+    #[case::composite_v3_0(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/poseidon2_16.json")]
+    #[case::succinct_v3_0(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json")]
+    fn should_return_the_verified_claim(#[case] verifier: impl Verifier, #[case] path: &str) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+        let journal_digest = case.journal.digest();
+
+        let claim = verifier
+            .verify_and_decode(case.vk.clone().into(), proof, case.journal)
+            .unwrap();
+
+        assert_eq!(risc0_binfmt_v1::ExitCode::Halted(0), claim.exit_code);
+        assert_eq!(case.vk.0, claim.pre.digest());
+        let output = claim.output.value().unwrap().unwrap();
+        assert_eq!(journal_digest, output.journal.digest());
+    }
+
+    #[test]
+    fn should_fail_on_invalid_pubs() {
+        let verifier = v1_2();
+        let case: Case =
+            read_all("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json").unwrap();
+        let proof = case.get_proof().unwrap();
+
+        let res = verifier.verify_and_decode(case.vk.into(), proof, Journal::default());
+
+        assert!(
+            matches!(res, Err(VerificationError::ClaimDigestMismatch { .. })),
+            "Invalid err {res:?}"
+        );
+    }
+
+    fn multiple_case(path: &str) -> (Vk, Proof, Journal) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+        (case.vk, proof, case.journal)
+    }
+
+    #[test]
+    fn should_return_all_the_verified_claims() {
+        let paths = [
+            "./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json",
+            "./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json",
+            "./resources/cases/prover_1.2.0/vm_1.2.0/sha_16.json",
+        ];
+        let cases = paths.map(multiple_case);
+        let expected = cases
+            .iter()
+            .map(|(_, proof, _)| proof.claim().unwrap().digest())
+            .collect::<Vec<_>>();
+
+        let claims = v1_2().verify_multiple(cases).unwrap();
+
+        assert_eq!(
+            expected,
+            claims.iter().map(|c| c.digest()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn should_return_the_index_of_the_first_failure() {
+        let valid = multiple_case("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json");
+        let (_, proof, journal) = valid.clone();
+        let invalid = (Vk::from([1_u32; 8]), proof, journal);
+
+        let res = v1_2().verify_multiple([valid.clone(), valid.clone(), valid, invalid]);
+
+        assert!(
+            matches!(res, Err((3, VerificationError::ClaimDigestMismatch { .. }))),
+            "Invalid err {res:?}"
+        );
+    }
+}
+
+mod verify_digest {
+    use super::*;
+    use risc0_verifier::{Digest, Digestible};
+
+    #[rstest]
+    #[case::composite_v1_2(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json")]
+    #[case::succinct_v1_2(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")]
+    #[case::composite_v3_0(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/poseidon2_16.json")]
+    #[case::succinct_v3_0(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json")]
+    fn should_verify_like_verify(#[case] verifier: impl Verifier, #[case] path: &str) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+
+        verifier
+            .verify_digest(case.vk.0, proof.clone(), case.journal.digest())
+            .unwrap();
+        verifier.verify(case.vk.0, proof, case.journal).unwrap();
+    }
+
+    #[rstest]
+    #[case::composite("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json")]
+    #[case::succinct("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")]
+    fn should_reject_tampered_digest(#[case] path: &str) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+        let mut digest = case.journal.digest();
+        digest.as_mut_words()[0] ^= 1;
+
+        let res = v1_2().verify_digest(case.vk.0, proof.clone(), digest);
+
+        assert!(
+            matches!(res, Err(VerificationError::ClaimDigestMismatch { .. })),
+            "Invalid err {res:?}"
+        );
+        assert_eq!(
+            v1_2()
+                .verify(case.vk.0, proof.clone(), Journal::new(vec![1]))
+                .unwrap_err(),
+            v1_2()
+                .verify_digest(case.vk.0, proof, Journal::new(vec![1]).digest())
+                .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn should_reject_malformed_image_id() {
+        let case: Case =
+            read_all("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json").unwrap();
+        let proof = case.get_proof().unwrap();
+
+        let res = v1_2()
+            .boxed()
+            .verify_digest(Digest::ZERO, proof, case.journal.digest());
+
+        assert!(
+            matches!(res, Err(VerificationError::ImageVerificationError)),
+            "Invalid err {res:?}"
+        );
+    }
+
+    #[test]
+    fn should_apply_the_filter() {
+        let case: Case =
+            read_all("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json").unwrap();
+        let proof = case.get_proof().unwrap();
+
+        let res = v1_2().reject_if(Proof::is_succinct).verify_digest(
+            case.vk.0,
+            proof,
+            case.journal.digest(),
+        );
+
+        assert_eq!(Err(VerificationError::ReceiptFormatError), res);
+    }
+}
+
+mod verify_with_exit_code {
+    use super::*;
+    use risc0_verifier::receipt_claim::ExitCode;
+
+    #[rstest]
+    #[case::composite_v1_2(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json")]
+    #[case::succinct_v1_2(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")]
+    #[case::composite_v3_0(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/poseidon2_16.json")]
+    #[case::succinct_v3_0(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json")]
+    fn should_verify_halted_ok_proof(#[case] verifier: impl Verifier, #[case] path: &str) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+
+        verifier
+            .verify_with_exit_code(case.vk.0, proof, case.journal, ExitCode::Halted(0))
+            .unwrap();
+    }
+
+    #[rstest]
+    fn should_reject_other_exit_codes(
+        #[values(ExitCode::Halted(1), ExitCode::Paused(0), ExitCode::Paused(1))]
+        exit_code: ExitCode,
+    ) {
+        let case: Case =
+            read_all("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json").unwrap();
+        let proof = case.get_proof().unwrap();
+
+        let res = v1_2().verify_with_exit_code(case.vk.0, proof, case.journal, exit_code);
+
+        assert!(
+            matches!(res, Err(VerificationError::ClaimDigestMismatch { .. })),
+            "Invalid err {res:?}"
+        );
+    }
+
+    // The paused fixture is generated with `generate_proofs` (`--pause`) from a method compiled
+    // from the current guest source: the test is ignored till it's committed.
+    #[rstest]
+    #[case::composite("./resources/cases/paused/prover_3.0.0/vm_3.0.0/poseidon2_16.json")]
+    #[case::succinct("./resources/cases/paused/prover_3.0.0/vm_3.0.0/succinct_16.json")]
+    #[ignore = "needs the paused fixtures"]
+    fn should_verify_paused_proof(#[case] path: &str) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+        assert_eq!(
+            ExitCode::Paused(0),
+            proof.claim().unwrap().value().unwrap().exit_code
+        );
+        let verifier = v3_0();
+
+        verifier
+            .verify_with_exit_code(
+                case.vk.0,
+                proof.clone(),
+                case.journal.clone(),
+                ExitCode::Paused(0),
+            )
+            .unwrap();
+        assert!(matches!(
+            verifier.verify(case.vk.into(), proof, case.journal),
+            Err(VerificationError::ClaimDigestMismatch { .. })
+        ));
+    }
+
+    #[rstest]
+    fn should_reject_not_final_exit_codes(
+        #[values(ExitCode::SystemSplit, ExitCode::SessionLimit)] exit_code: ExitCode,
+    ) {
+        let case: Case = read_all("./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json").unwrap();
+        // The first segment of the execution is a valid receipt that ends with `SystemSplit`.
+        let (first, _) = case.get_proof().unwrap().split_composite_at(1).unwrap();
+        let verifier = v1_2();
+        verifier
+            .verify_segment_at(first.inner.composite().unwrap(), 0)
+            .unwrap();
+        assert_eq!(
+            ExitCode::SystemSplit,
+            first.claim().unwrap().value().unwrap().exit_code
+        );
+
+        let res = verifier.verify_with_exit_code(case.vk.0, first, case.journal, exit_code);
+
+        assert_eq!(Err(VerificationError::UnexpectedExitCode), res);
+    }
+}
+
+mod verify_conditional {
+    use super::*;
+
+    #[rstest]
+    #[case::composite_v1_2(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json")]
+    #[case::succinct_v1_2(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")]
+    #[case::composite_v3_0(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/poseidon2_16.json")]
+    #[case::succinct_v3_0(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json")]
+    fn should_verify_unconditional_proof(#[case] verifier: impl Verifier, #[case] path: &str) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+
+        assert_eq!(
+            Ok(vec![]),
+            verifier.verify_conditional(case.vk.0, proof.clone(), case.journal)
+        );
+        assert!(matches!(
+            verifier.verify_conditional(case.vk.0, proof, Journal::default()),
+            Err(VerificationError::ClaimDigestMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn should_reject_extra_assumption_receipts() {
+        let case: Case =
+            read_all("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json").unwrap();
+        let proof = case.get_proof().unwrap();
+        let succinct = read_all::<Case>("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")
+            .unwrap()
+            .get_proof()
+            .unwrap();
+        let proof = Proof::from_composite(
+            proof
+                .try_into_composite()
+                .unwrap()
+                .with_assumption_receipt(succinct.inner.into()),
+        );
+
+        // The claim has no assumption to resolve with the extra receipt.
+        assert_eq!(
+            Err(VerificationError::ReceiptFormatError),
+            v1_2().verify_conditional(case.vk.0, proof.clone(), case.journal.clone())
+        );
+        assert_eq!(
+            Err(VerificationError::ReceiptFormatError),
+            v1_2().verify(case.vk.0, proof, case.journal)
+        );
+    }
+}
+
+mod verify_assumption {
+    use super::*;
+    use risc0_verifier::Digest;
+
+    fn assumption(path: &str) -> (InnerAssumptionReceipt, Digest) {
+        let receipt: InnerAssumptionReceipt = read_all::<Case>(path)
+            .unwrap()
+            .get_proof()
+            .unwrap()
+            .inner
+            .into();
+        let claim = receipt.claim_digest().unwrap();
+        (receipt, claim)
+    }
+
+    #[rstest]
+    #[case::succinct_v1_2(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")]
+    #[case::succinct_v3_0(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json")]
+    #[case::composite_v1_2(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json")]
+    #[case::composite_v3_0(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/poseidon2_16.json")]
+    fn should_verify_self_composed_assumption(#[case] verifier: impl Verifier, #[case] path: &str) {
+        let (receipt, claim) = assumption(path);
+
+        assert_eq!(Ok(()), verifier.verify_assumption(&receipt, claim, None));
+        assert_eq!(
+            Ok(()),
+            verifier.verify_assumption(&receipt, claim, Some(Digest::ZERO))
+        );
+        assert_eq!(
+            Err(VerificationError::ClaimDigestMismatch {
+                expected: Digest::ZERO,
+                received: claim
+            }),
+            verifier.verify_assumption(&receipt, Digest::ZERO, None)
+        );
+    }
+
+    #[rstest]
+    #[case::v1_2(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")]
+    #[case::v3_0(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json")]
+    fn should_verify_assumption_with_explicit_control_root(
+        #[case] verifier: impl Verifier,
+        #[case] path: &str,
+    ) {
+        let (receipt, claim) = assumption(path);
+        let control_root = verifier.succinct_control_root();
+
+        assert!(control_root.is_some());
+        assert_eq!(
+            Ok(()),
+            verifier.verify_assumption(&receipt, claim, control_root)
+        );
+        let res = verifier.verify_assumption(&receipt, claim, Some(Digest::from([1; 8])));
+        assert!(
+            matches!(res, Err(VerificationError::ControlVerificationError { .. })),
+            "Invalid err {res:?}"
+        );
+    }
+}
+
+mod extract_claim {
+    use super::*;
+    use risc0_verifier::{receipt_claim::MaybePruned, Digestible};
+
+    #[rstest]
+    #[case::composite_v1_2(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json")]
+    #[case::succinct_v1_2(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")]
+    #[case::composite_v3_0(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/poseidon2_16.json")]
+    #[case::succinct_v3_0(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json")]
+    #[case::dynamic_dispatch(
+        v3_0().boxed(),
+        "./resources/cases/prover_3.0.0/vm_3.0.0/poseidon2_16.json"
+    )]
+    fn should_extract_the_claim(#[case] verifier: impl Verifier, #[case] path: &str) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+
+        let claim = verifier.extract_claim(&proof).unwrap().value().unwrap();
+
+        assert_eq!(risc0_binfmt_v1::ExitCode::Halted(0), claim.exit_code);
+        assert_eq!(case.vk.0, claim.pre_state_digest());
+        assert_eq!(Some(case.journal.digest()), claim.output_journal_digest());
+        assert_eq!(
+            verifier
+                .verify_and_decode(case.vk.0, proof, case.journal)
+                .unwrap()
+                .digest(),
+            claim.digest()
+        );
+    }
+
+    #[rstest]
+    #[case::v1_2(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json")]
+    #[case::v3_0(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/poseidon2_16.json")]
+    fn should_extract_the_claim_with_pruned_composite_output(
+        #[case] verifier: impl Verifier,
+        #[case] path: &str,
+    ) {
+        let case: Case = read_all(path).unwrap();
+        let mut proof = case.get_proof().unwrap();
+        let expected = verifier.extract_claim(&proof).unwrap();
+        let output = &mut proof
+            .inner
+            .mut_composite()
+            .unwrap()
+            .segments
+            .last_mut()
+            .unwrap()
+            .claim
+            .output;
+        *output = MaybePruned::Pruned(output.digest());
+
+        let claim = verifier.extract_claim(&proof).unwrap();
+
+        assert_eq!(expected.digest(), claim.digest());
+        let claim = claim.value().unwrap();
+        assert_eq!(case.vk.0, claim.pre_state_digest());
+        assert!(matches!(claim.output, MaybePruned::Pruned(_)));
+    }
+
+    #[rstest]
+    #[case::v1_2(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")]
+    #[case::v3_0(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json")]
+    fn should_extract_a_pruned_claim(#[case] verifier: impl Verifier, #[case] path: &str) {
+        let case: Case = read_all(path).unwrap();
+        let mut proof = case.get_proof().unwrap();
+        let claim = &mut proof.inner.mut_succinct().unwrap().claim;
+        let digest = claim.digest();
+        *claim = MaybePruned::Pruned(digest);
+
+        assert!(matches!(
+            verifier.extract_claim(&proof),
+            Ok(MaybePruned::Pruned(d)) if d == digest
+        ));
+    }
+
+    #[test]
+    fn should_extract_the_claim_without_verifying() {
+        let case: Case =
+            read_all("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json").unwrap();
+        let mut proof = case.get_proof().unwrap();
+        let expected = proof.claim().unwrap();
+        let seal = proof.inner.mut_succinct().unwrap().seal.as_mut_slice();
+        seal[seal.len() / 2] = seal[seal.len() / 2].wrapping_add(1);
+
+        assert_eq!(
+            expected.digest(),
+            v1_2().extract_claim(&proof).unwrap().digest()
+        );
+        assert!(v1_2().verify(case.vk.0, proof, case.journal).is_err());
+    }
+}
+
+mod verify_with_report {
+    use super::*;
+    use risc0_verifier::{Digestible, ReceiptKind, VerificationReport};
+
+    #[rstest]
+    #[case::v1_2(v1_2(), "./resources/cases/single_full_segment_v1/poseidon2_16.json")]
+    #[case::v3_0(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/poseidon2_16.json")]
+    fn should_report_composite_receipt(#[case] verifier: impl Verifier, #[case] path: &str) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+        let segments = verifier
+            .extract_composite_segments_info(proof.inner.composite().unwrap())
+            .unwrap();
+
+        let report = verifier
+            .verify_with_report(case.vk.into(), proof.clone(), case.journal.clone())
+            .unwrap();
+
+        assert_eq!(
+            VerificationReport {
+                kind: ReceiptKind::Composite,
+                segments,
+                claim_digest: proof.claim().unwrap().digest(),
+                journal_digest: case.journal.digest(),
+                verifier_parameters: verifier.segment_verifier_parameters().unwrap().digest(),
+                control_root: None,
+            },
+            report
+        );
+        assert!(!report.segments.is_empty());
+    }
+
+    #[rstest]
+    #[case::v1_2(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")]
+    #[case::v3_0(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json")]
+    fn should_report_succinct_receipt(#[case] verifier: impl Verifier, #[case] path: &str) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+
+        let report = verifier
+            .verify_with_report(case.vk.into(), proof.clone(), case.journal.clone())
+            .unwrap();
+
+        assert_eq!(
+            VerificationReport {
+                kind: ReceiptKind::Succinct,
+                segments: vec![],
+                claim_digest: proof.claim().unwrap().digest(),
+                journal_digest: case.journal.digest(),
+                verifier_parameters: proof.inner.verifier_parameters(),
+                control_root: verifier.succinct_control_root(),
+            },
+            report
+        );
+        assert!(report.control_root.is_some());
+    }
+
+    #[test]
+    fn should_fail_like_verify() {
+        let case: Case =
+            read_all("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json").unwrap();
+        let proof = case.get_proof().unwrap();
+
+        assert_eq!(
+            v1_2()
+                .verify(case.vk.clone().into(), proof.clone(), Journal::default())
+                .unwrap_err(),
+            v1_2()
+                .verify_with_report(case.vk.into(), proof, Journal::default())
+                .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn should_serialize_the_report() {
+        let case: Case =
+            read_all("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json").unwrap();
+        let proof = case.get_proof().unwrap();
+        let report = v1_2()
+            .boxed()
+            .verify_with_report(case.vk.into(), proof, case.journal)
+            .unwrap();
+
+        let json = serde_json::to_string(&report).unwrap();
+
+        assert_eq!(
+            report,
+            serde_json::from_str::<VerificationReport>(&json).unwrap()
+        );
+    }
+}
+
+mod verify_batch {
+    use super::*;
+
+    fn batch_item(path: &str) -> (Vk, Proof, Journal) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+        (case.vk, proof, case.journal)
+    }
+
+    #[rstest]
+    #[case::context(v1_2().boxed())]
+    #[case::filtered(v1_2().reject_if(|_| false).boxed())]
+    fn should_report_every_item(#[case] verifier: Box<dyn Verifier>) {
+        let valid = batch_item("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json");
+        let succinct = batch_item("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json");
+        let (vk, proof, _) = valid.clone();
+        let wrong_journal = (vk, proof.clone(), Journal::default());
+        let wrong_vk = (Vk::from([1_u32; 8]), proof, valid.2.clone());
+
+        let res = verifier.verify_batch(&[valid.clone(), wrong_journal, succinct, wrong_vk, valid]);
+
+        assert_eq!(5, res.len());
+        assert!(res[0].is_ok());
+        assert!(matches!(
+            res[1],
+            Err(VerificationError::ClaimDigestMismatch { .. })
+        ));
+        assert!(res[2].is_ok());
+        assert!(matches!(
+            res[3],
+            Err(VerificationError::ClaimDigestMismatch { .. })
+        ));
+        assert!(res[4].is_ok());
+    }
+
+    #[test]
+    fn should_return_the_same_results_of_verify() {
+        let verifier = v1_2().reject_sha256();
+        let items = [
+            batch_item("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json"),
+            batch_item("./resources/cases/prover_1.2.0/vm_1.2.0/sha_16.json"),
+        ];
+
+        let res = verifier.verify_batch(&items);
+
+        assert_eq!(
+            items
+                .iter()
+                .map(|(vk, proof, pubs)| verifier.verify(vk.0, proof.clone(), pubs.clone()))
+                .collect::<Vec<_>>(),
+            res
+        );
+        assert_eq!(Err(VerificationError::InvalidHashSuite), res[1]);
+    }
+
+    #[test]
+    fn should_apply_the_journal_limit_to_every_item() {
+        let mut verifier = v1_2();
+        let item = batch_item("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json");
+        verifier.set_max_journal_bytes(item.2.bytes.len() - 1);
+
+        let res = verifier.verify_batch(&[item.clone(), item]);
+
+        assert_eq!(
+            vec![
+                Err(VerificationError::ReceiptFormatError),
+                Err(VerificationError::ReceiptFormatError)
+            ],
+            res
+        );
+    }
+
+    #[test]
+    fn should_return_nothing_for_an_empty_batch() {
+        assert!(v1_2().verify_batch(&[]).is_empty());
+    }
+}
+
+#[rstest]
+#[case::zero([0; 8])]
+#[case::all_ones([u32::MAX; 8])]
+fn should_reject_malformed_image_id(#[case] vk: [u32; 8]) {
+    let case: Case = read_all("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json").unwrap();
+    let proof = case.get_proof().unwrap();
+
+    let res = v1_2().boxed().verify(vk.into(), proof, case.journal);
+
+    assert!(
+        matches!(res, Err(VerificationError::ImageVerificationError)),
+        "Invalid err {res:?}"
+    );
+}
+
+// Before 0.12 the image id wasn't checked on its own, so a fake receipt for a malformed image id
+// was accepted: now it's rejected before looking at the proof.
+#[cfg(feature = "dev-mode")]
+#[rstest]
+#[case::zero([0; 8])]
+#[case::all_ones([u32::MAX; 8])]
+fn should_reject_fake_receipt_for_malformed_image_id(#[case] vk: [u32; 8]) {
+    use risc0_verifier::{receipt_claim::ReceiptClaim, FakeReceipt, InnerReceipt};
+
+    let journal = Journal::new(vec![1, 2, 3, 4]);
+    let fake = |image_id: [u32; 8]| {
+        Proof::new(InnerReceipt::Fake(FakeReceipt::new(
+            ReceiptClaim::ok_with_journal_bytes(image_id, &journal.bytes),
+        )))
+    };
+    let verifier = v3_0().with_fake_receipts_allowed();
+    verifier
+        .verify([1; 8].into(), fake([1; 8]), journal.clone())
+        .unwrap();
+
+    let res = verifier.verify(vk.into(), fake(vk), journal);
+
+    assert_eq!(Err(VerificationError::ImageVerificationError), res);
+}
+
+mod verifier_parameters_matches {
+    use super::*;
+
+    fn proof(path: &str) -> Proof {
+        let case: Case = read_all(path).unwrap();
+        case.get_proof().unwrap()
+    }
+
+    #[rstest]
+    #[case::same_version(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/sha_16.json", true)]
+    #[case::other_version(v1_2(), "./resources/cases/prover_1.0.3/vm_1.0.1/sha_16.json", false)]
+    #[case::boxed(
+        v3_0().boxed(),
+        "./resources/cases/prover_3.0.0/vm_3.0.0/poseidon2_16.json",
+        true
+    )]
+    fn segments(#[case] verifier: impl Verifier, #[case] path: &str, #[case] expected: bool) {
+        let proof = proof(path);
+
+        for segment in &proof.inner.composite().unwrap().segments {
+            assert_eq!(expected, segment.verifier_parameters_matches(&verifier));
+        }
+    }
+
+    #[rstest]
+    #[case::same_version(
+        v1_2(),
+        "./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json",
+        true
+    )]
+    #[case::other_version(
+        v1_2(),
+        "./resources/cases/prover_1.0.3/vm_1.0.1/succinct_16.json",
+        false
+    )]
+    #[case::boxed(
+        v3_0().boxed(),
+        "./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json",
+        true
+    )]
+    fn succinct(#[case] verifier: impl Verifier, #[case] path: &str, #[case] expected: bool) {
+        let proof = proof(path);
+
+        assert_eq!(
+            expected,
+            proof
+                .inner
+                .succinct()
+                .unwrap()
+                .verifier_parameters_matches(&verifier)
+        );
+    }
+
+    #[test]
+    fn should_reject_other_version_before_verifying_the_seals() {
+        let case: Case = read_all("./resources/cases/prover_1.0.3/vm_1.0.1/sha_16.json").unwrap();
+        let proof = case.get_proof().unwrap();
+
+        let res = v1_2().verify(case.vk.into(), proof, case.journal);
+
+        assert!(
+            matches!(
+                res,
+                Err(VerificationError::VerifierParametersMismatch { .. })
+            ),
+            "Invalid err {res:?}"
+        );
+    }
+}
+
+mod custom_verifier_parameters {
+    use super::*;
+
+    #[rstest]
+    #[case::static_dispatch(v1_2())]
+    #[case::dynamic_dispatch(v1_2().boxed())]
+    fn should_reject_segments_with_other_version_parameters(#[case] verifier: impl Verifier) {
+        let verifier = verifier.with_segment_params(SegmentReceiptVerifierParameters::v1_0());
+        let case: Case = read_all("./resources/cases/prover_1.2.0/vm_1.2.0/sha_16.json").unwrap();
+        let proof = case.get_proof().unwrap();
+
+        let res = verifier.verify(case.vk.clone().into(), proof.clone(), case.journal.clone());
+
+        assert!(
+            matches!(
+                res,
+                Err(VerificationError::VerifierParametersMismatch { .. })
+            ),
+            "Invalid err {res:?}"
+        );
+
+        // With matching digests the seal verification fails as before the parameters check.
+        let proof = with_verifier_parameters_of(proof, &verifier);
+        let res = verifier.verify(case.vk.into(), proof, case.journal);
+
+        assert!(
+            matches!(res, Err(VerificationError::ControlVerificationError { .. })),
+            "Invalid err {res:?}"
+        );
+    }
+
+    #[rstest]
+    #[case::static_dispatch(v1_2())]
+    #[case::dynamic_dispatch(v1_2().boxed())]
+    fn should_reject_succinct_with_other_version_parameters(#[case] verifier: impl Verifier) {
+        let verifier = verifier.with_succinct_params(SuccinctReceiptVerifierParameters::v1_0());
+        let case: Case =
+            read_all("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json").unwrap();
+        let proof = case.get_proof().unwrap();
+
+        let res = verifier.verify(case.vk.clone().into(), proof.clone(), case.journal.clone());
+
+        assert!(
+            matches!(
+                res,
+                Err(VerificationError::VerifierParametersMismatch { .. })
+            ),
+            "Invalid err {res:?}"
+        );
+
+        // With matching digests the seal verification fails as before the parameters check.
+        let proof = with_verifier_parameters_of(proof, &verifier);
+        let res = verifier.verify(case.vk.into(), proof, case.journal);
+
+        assert!(
+            matches!(res, Err(VerificationError::ControlVerificationError { .. })),
+            "Invalid err {res:?}"
+        );
+    }
+
+    #[rstest]
+    #[case::static_dispatch(v1_2())]
+    #[case::dynamic_dispatch(v1_2().boxed())]
+    fn should_reject_succinct_with_custom_control_root(#[case] verifier: impl Verifier) {
+        use risc0_verifier::Digestible;
+
+        let v1_2 = SuccinctReceiptVerifierParameters::v1_2();
+        let params = SuccinctReceiptVerifierParameters::builder()
+            .control_root(risc0_verifier::Digest::from([1; 8]))
+            .proof_system_info(v1_2.proof_system_info)
+            .circuit_info(v1_2.circuit_info)
+            .build();
+        let case: Case =
+            read_all("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json").unwrap();
+        let mut proof = case.get_proof().unwrap();
+        // Match the parameters digest to reach the control root check.
+        proof.inner.mut_succinct().unwrap().verifier_parameters = params.digest();
+        let verifier = verifier.with_succinct_params(params);
+
+        let res = verifier.verify(case.vk.into(), proof, case.journal);
+
+        assert!(
+            matches!(res, Err(VerificationError::ControlVerificationError { .. })),
+            "Invalid err {res:?}"
+        );
+    }
+}
+
+mod max_journal_bytes {
+    use super::*;
+    use risc0_verifier::{JournalTooLargeError, DEFAULT_MAX_JOURNAL_BYTES};
+
+    const PATH: &str = "./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json";
+
+    #[test]
+    fn should_have_no_limit_by_default() {
+        assert_eq!(None, v1_2().max_journal_bytes());
+
+        let mut verifier = v3_0().boxed();
+        verifier.set_max_journal_bytes(DEFAULT_MAX_JOURNAL_BYTES);
+
+        assert_eq!(
+            Some(DEFAULT_MAX_JOURNAL_BYTES),
+            verifier.max_journal_bytes()
+        );
+    }
+
+    #[rstest]
+    #[case::below(1, true)]
+    #[case::at(0, true)]
+    #[case::above(-1, false)]
+    fn should_honor_the_limit(#[case] margin: isize, #[case] valid: bool) {
+        let case: Case = read_all(PATH).unwrap();
+        let proof = case.get_proof().unwrap();
+        let mut verifier = v1_2();
+
+        verifier.set_max_journal_bytes(case.journal.bytes.len().saturating_add_signed(margin));
+        let result = verifier.verify(case.vk.into(), proof, case.journal);
+
+        if valid {
+            result.unwrap();
+        } else {
+            assert_eq!(Err(VerificationError::ReceiptFormatError), result);
+        }
+    }
+
+    #[test]
+    fn should_build_journal_within_limit() {
+        assert_eq!(
+            Journal::new(vec![1; 4]),
+            Journal::try_from_bytes(vec![1; 4], 4).unwrap()
+        );
+        let err = Journal::try_from_bytes(vec![1; 5], 4).unwrap_err();
+        assert_eq!(JournalTooLargeError { limit: 4, got: 5 }, err);
+        assert_eq!(
+            "journal size 5 exceeds the limit of 4 bytes",
+            err.to_string()
+        );
+        assert!(Journal::with_capacity(16).capacity() >= 16);
+    }
+}
+
+mod max_proof_bytes {
+    use super::*;
+    use risc0_verifier::DEFAULT_MAX_PROOF_BYTES;
+
+    const PATH: &str = "./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json";
+
+    /// A composite proof with 300 dummy segments of 32 KB each: about 9.4 MB of seals.
+    fn oversized_proof() -> Proof {
+        let case: Case = read_all(PATH).unwrap();
+        let mut composite = case.get_proof().unwrap().inner.composite().unwrap().clone();
+        let mut segment = composite.segments[0].clone();
+        segment.seal = vec![0; 8 * 1024];
+        composite.segments = (0..300)
+            .map(|index| SegmentReceipt {
+                index,
+                ..segment.clone()
+            })
+            .collect();
+        Proof::new(composite.into())
+    }
+
+    #[test]
+    fn should_have_default_limit() {
+        assert_eq!(DEFAULT_MAX_PROOF_BYTES, v1_2().max_proof_bytes());
+        assert_eq!(DEFAULT_MAX_PROOF_BYTES, v3_0().boxed().max_proof_bytes());
+    }
+
+    #[test]
+    fn should_set_limit() {
+        let mut verifier = v2_1().boxed();
+
+        verifier.set_max_proof_bytes(1024);
+
+        assert_eq!(1024, verifier.max_proof_bytes());
+    }
+
+    #[test]
+    fn should_reject_oversized_proof() {
+        let case: Case = read_all(PATH).unwrap();
+        let proof = oversized_proof();
+
+        assert!(proof.total_seal_bytes() > DEFAULT_MAX_PROOF_BYTES);
+        assert_eq!(
+            Err(VerificationError::ReceiptFormatError),
+            proof.check_format(DEFAULT_MAX_PROOF_BYTES)
+        );
+        assert_eq!(
+            Err(VerificationError::ReceiptFormatError),
+            v1_2().verify(case.vk.into(), proof, case.journal)
+        );
+    }
+
+    #[test]
+    fn should_honor_tightened_limit() {
+        let case: Case = read_all(PATH).unwrap();
+        let proof = case.get_proof().unwrap();
+        let size = proof.total_seal_bytes();
+        let mut verifier = v1_2();
+
+        verifier.set_max_proof_bytes(size - 1);
+        assert_eq!(
+            Err(VerificationError::ReceiptFormatError),
+            verifier.verify(case.vk.clone().into(), proof.clone(), case.journal.clone())
+        );
+
+        verifier.set_max_proof_bytes(size);
+        verifier
+            .verify(case.vk.into(), proof, case.journal)
+            .unwrap();
+    }
+
+    #[test]
+    fn should_refuse_to_decode_oversized_proof() {
+        let mut data = Vec::new();
+        ciborium::into_writer(&oversized_proof(), &mut data).unwrap();
+
+        assert!(matches!(
+            Proof::from_cbor(&data, DEFAULT_MAX_PROOF_BYTES),
+            Err(VerificationError::ReceiptFormatError)
+        ));
+    }
+
+    #[test]
+    fn should_decode_proof_within_limit() {
+        let case: Case = read_all(PATH).unwrap();
+        let proof = case.get_proof().unwrap();
+        let mut data = Vec::new();
+        ciborium::into_writer(&proof, &mut data).unwrap();
+
+        let decoded = Proof::from_cbor(&data, proof.total_seal_bytes()).unwrap();
+
+        assert_eq!(proof.total_seal_bytes(), decoded.total_seal_bytes());
+        assert!(matches!(
+            Proof::from_cbor(&data, proof.total_seal_bytes() - 1),
+            Err(VerificationError::ReceiptFormatError)
+        ));
+    }
+
+    #[test]
+    fn should_refuse_data_too_long_before_decoding() {
+        let case: Case = read_all(PATH).unwrap();
+        let proof = case.get_proof().unwrap();
+        let mut data = Vec::new();
+        ciborium::into_writer(&proof, &mut data).unwrap();
+        let max_proof_bytes = proof.total_seal_bytes();
+        Proof::from_cbor(&data, max_proof_bytes).unwrap();
+
+        // The trailing bytes are ignored by the decoder, but not by the length check.
+        data.resize(2 * max_proof_bytes, 0);
+
+        assert!(matches!(
+            Proof::from_cbor(&data, max_proof_bytes),
+            Err(VerificationError::ReceiptFormatError)
+        ));
+    }
+}
+
+#[cfg(feature = "tracing")]
+mod tracing_spans {
+    use super::*;
+    use std::{
+        collections::BTreeMap,
+        fmt::Debug,
+        sync::{Arc, Mutex},
+    };
+    use tracing::{
+        field::{Field, Visit},
+        span::{Attributes, Id},
+        Subscriber,
+    };
+    use tracing_subscriber::{layer::Context, prelude::*, Layer};
+
+    type Spans = Arc<Mutex<Vec<(String, BTreeMap<String, String>)>>>;
+
+    /// Record the name and the fields of every created span.
+    #[derive(Default, Clone)]
+    struct Recorder(Spans);
+
+    struct Fields<'a>(&'a mut BTreeMap<String, String>);
+
+    impl Visit for Fields<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0.insert(field.name().to_owned(), format!("{value:?}"));
+        }
+    }
+
+    impl<S: Subscriber> Layer<S> for Recorder {
+        fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+            let mut fields = BTreeMap::new();
+            attrs.record(&mut Fields(&mut fields));
+            self.0
+                .lock()
+                .unwrap()
+                .push((attrs.metadata().name().to_owned(), fields));
+        }
+    }
+
+    fn record_spans(f: impl FnOnce()) -> Vec<(String, BTreeMap<String, String>)> {
+        let recorder = Recorder::default();
+        let subscriber = tracing_subscriber::registry().with(recorder.clone());
+        tracing::subscriber::with_default(subscriber, f);
+        let spans = recorder.0.lock().unwrap().clone();
+        spans
+    }
+
+    #[test]
+    fn should_trace_every_segment() {
+        let case: Case = read_all("./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json").unwrap();
+        let proof = case.get_proof().unwrap();
+        assert_eq!(3, proof.inner.composite().unwrap().segments.len());
+        let image_id: risc0_verifier::Digest = case.vk.0;
+
+        let spans = record_spans(|| v1_2().verify_traced(case.vk, proof, case.journal).unwrap());
+
+        let (name, fields) = &spans[0];
+        assert_eq!("risc0::verify", name);
+        assert_eq!(image_id.to_string(), fields["image_id"]);
+        let segments = spans[1..]
+            .iter()
+            .map(|(name, fields)| {
+                assert_eq!("risc0::verify_segment", name);
+                assert_eq!("sha-256", fields["hashfn"]);
+                fields["segment_index"].clone()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["0", "1", "2"], segments);
+    }
+
+    #[test]
+    fn should_trace_succinct_receipt() {
+        let case: Case =
+            read_all("./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json").unwrap();
+        let proof = case.get_proof().unwrap();
+
+        let spans = record_spans(|| {
+            v3_0()
+                .boxed()
+                .verify_traced(case.vk, proof, case.journal)
+                .unwrap()
+        });
+
+        let names = spans
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["risc0::verify", "risc0::verify_succinct"], names);
+        assert_eq!("poseidon2", spans[1].1["hashfn"]);
+    }
+}
+
+#[cfg(feature = "compute-vk")]
+mod compute_vk {
+    use super::*;
+    use risc0_verifier::{sha::digest_from_hex, VkFromElfError};
+
+    #[test]
+    fn should_compute_the_image_id_of_the_guest() {
+        let elf = std::fs::read("./generate_proofs/host/method-1.2.0/method").unwrap();
+        let expected =
+            std::fs::read_to_string("./generate_proofs/host/method-1.2.0/info.txt").unwrap();
+
+        let vk = Vk::from_elf(&elf).unwrap();
+
+        assert_eq!(digest_from_hex(expected.trim()).unwrap(), vk.0);
+    }
+
+    #[test]
+    fn should_reject_invalid_elf() {
+        let mut elf = std::fs::read("./generate_proofs/host/method-1.2.0/method").unwrap();
+
+        assert!(matches!(
+            Vk::from_elf(b"not an elf"),
+            Err(VkFromElfError::MalformedElf(_))
+        ));
+        // Set the machine to x86-64
+        elf[18..20].copy_from_slice(&62_u16.to_le_bytes());
+        assert_eq!(
+            Some(VkFromElfError::UnsupportedIsa),
+            Vk::from_elf(&elf).err()
+        );
+    }
+}
+
+#[cfg(feature = "std")]
+mod cbor_files {
+    use super::*;
+    use risc0_verifier::{receipt_claim::ReceiptClaim, Digestible, ProofFileError};
+    use tempfile::NamedTempFile;
+
+    #[rstest]
+    #[case::composite("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json")]
+    #[case::succinct("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")]
+    fn should_round_trip_proof(#[case] path: &str) {
+        let case: Case = read_all(path).unwrap();
+        let proof = Proof::from_cbor_file(&case.receipt_path).unwrap();
+        let file = NamedTempFile::new().unwrap();
+
+        proof.to_cbor_file(file.path()).unwrap();
+        let read = Proof::from_cbor_file(file.path()).unwrap();
+
+        assert_eq!(
+            proof.claim().unwrap().digest(),
+            read.claim().unwrap().digest()
+        );
+        v1_2().verify(case.vk.into(), read, case.journal).unwrap();
+    }
+
+    #[rstest]
+    #[case::composite_16("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json")]
+    #[case::composite_22("./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json")]
+    #[case::succinct("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_22.json")]
+    fn should_encode_proof_in_preallocated_buffer(#[case] path: &str) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+
+        let encoded = proof.to_cbor_preallocated().unwrap();
+
+        assert!(encoded.len() <= proof.estimate_cbor_size());
+        let decoded = Proof::from_cbor(&encoded, usize::MAX).unwrap();
+        assert_eq!(proof.content_fingerprint(), decoded.content_fingerprint());
+        v1_2()
+            .verify(case.vk.into(), decoded, case.journal)
+            .unwrap();
+    }
+
+    #[test]
+    fn should_round_trip_succinct_receipt() {
+        let case: Case =
+            read_all("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json").unwrap();
+        let receipt = case.get_proof().unwrap().inner.succinct().unwrap().clone();
+        let file = NamedTempFile::new().unwrap();
+
+        receipt.to_cbor_file(file.path()).unwrap();
+        let read = SuccinctReceipt::<ReceiptClaim>::from_cbor_file(file.path()).unwrap();
+
+        assert_eq!(receipt.seal, read.seal);
+        assert_eq!(receipt.claim.digest(), read.claim.digest());
+    }
+
+    #[test]
+    fn should_round_trip_journal() {
+        let journal = Journal::new(vec![1, 2, 3, 4, 5]);
+        let file = NamedTempFile::new().unwrap();
+
+        journal.to_cbor_file(file.path()).unwrap();
+
+        assert_eq!(journal, Journal::from_cbor_file(file.path()).unwrap());
+    }
+
+    #[test]
+    fn should_fail_on_missing_or_invalid_file() {
+        let file = NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), b"not a cbor proof").unwrap();
+
+        assert!(matches!(
+            Proof::from_cbor_file(Path::new("./resources/not_existing.bin")),
+            Err(ProofFileError::Io(_))
+        ));
+        assert!(matches!(
+            Proof::from_cbor_file(file.path()),
+            Err(ProofFileError::Cbor(_))
+        ));
+    }
+}
+
+#[cfg(feature = "ethereum")]
+mod ethereum {
+    use super::*;
+    use risc0_verifier::decode_abi;
+
+    #[rstest]
+    #[case::composite_v1_2(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json")]
+    #[case::succinct_v1_2(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")]
+    #[case::composite_v3_0(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/poseidon2_16.json")]
+    #[case::succinct_v3_0(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json")]
+    fn should_verify_abi_round_trip_proof(#[case] verifier: impl Verifier, #[case] path: &str) {
+        let case: Case = read_all(path).unwrap();
+        let encoded = case.get_proof().unwrap().to_abi_encoded().unwrap();
+
+        let proof = decode_abi(&encoded).unwrap();
+
+        verifier
+            .verify(case.vk.into(), proof, case.journal)
+            .unwrap();
+    }
+}
+
+#[cfg(feature = "risc0-zkvm-compat")]
+mod risc0_zkvm_compat {
+    use super::*;
+    use risc0_verifier::Digestible;
+
+    fn read_receipt(path: impl AsRef<Path>) -> risc0_zkvm::Receipt {
+        read_bin_all(path).unwrap()
+    }
+
+    #[rstest]
+    #[case::composite_v1_2(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json")]
+    #[case::succinct_v1_2(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")]
+    #[case::composite_v3_0(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/poseidon2_16.json")]
+    #[case::succinct_v3_0(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json")]
+    fn should_verify_converted_receipt(#[case] verifier: impl Verifier, #[case] path: &str) {
+        let case: Case = read_all(path).unwrap();
+        let receipt = read_receipt(&case.receipt_path);
+
+        let journal = Journal::from(receipt.journal.clone());
+        let proof = Proof::try_from(receipt).unwrap();
+
+        assert_eq!(case.journal, journal);
+        verifier.verify(case.vk.into(), proof, journal).unwrap()
+    }
+
+    #[rstest]
+    #[case::composite("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json")]
+    #[case::succinct("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")]
+    fn should_round_trip_through_risc0_zkvm_types(#[case] path: &str) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+
+        let inner = risc0_zkvm::InnerReceipt::try_from(proof.clone()).unwrap();
+        let journal = risc0_zkvm::Journal::from(case.journal.clone());
+        let receipt = risc0_zkvm::Receipt::new(inner, journal.bytes);
+
+        assert_eq!(case.journal, Journal::from(receipt.journal.clone()));
+        let converted = Proof::try_from(receipt).unwrap();
+        assert_eq!(
+            proof.inner.verifier_parameters(),
+            converted.inner.verifier_parameters()
+        );
+        assert_eq!(
+            proof.claim().unwrap().digest(),
+            converted.claim().unwrap().digest()
+        );
+    }
+
+    #[test]
+    fn should_convert_groth16_receipt() {
+        use risc0_zkvm::sha::Digestible as _;
+
+        let claim = risc0_zkvm::ReceiptClaim::ok(risc0_zkvm::Digest::ZERO, vec![]);
+        let claim_digest = claim.digest();
+        let params = risc0_zkvm::Groth16ReceiptVerifierParameters::default().digest();
+        let receipt = risc0_zkvm::Receipt::new(
+            risc0_zkvm::InnerReceipt::Groth16(risc0_zkvm::Groth16Receipt::new(
+                vec![0; 256],
+                claim.into(),
+                params,
+            )),
+            vec![],
+        );
+
+        let proof = Proof::try_from(receipt).unwrap();
+
+        assert!(proof.is_groth16());
+        assert_eq!(
+            risc0_verifier::Groth16ReceiptVerifierParameters::v3_0().digest(),
+            proof.inner.verifier_parameters()
+        );
+        assert_eq!(
+            claim_digest.as_bytes(),
+            proof.claim().unwrap().digest().as_bytes()
+        );
+        assert_eq!(256, proof.total_seal_bytes());
+    }
+
+    #[test]
+    #[cfg(not(feature = "dev-mode"))]
+    fn should_reject_fake_receipt() {
+        let claim = risc0_zkvm::ReceiptClaim::ok(risc0_zkvm::Digest::ZERO, vec![]);
+        let receipt = risc0_zkvm::Receipt::new(
+            risc0_zkvm::InnerReceipt::Fake(risc0_zkvm::FakeReceipt::new(claim)),
+            vec![],
+        );
+
+        assert_eq!(
+            risc0_verifier::ReceiptConversionError::UnsupportedInnerReceipt,
+            Proof::try_from(receipt).unwrap_err()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "dev-mode")]
+    fn should_verify_fake_receipt_in_dev_mode() {
+        let journal = Journal::new(vec![1, 2, 3, 4]);
+        let claim =
+            risc0_zkvm::ReceiptClaim::ok(risc0_zkvm::Digest::from([1; 8]), journal.bytes.clone());
+        let receipt = risc0_zkvm::Receipt::new(
+            risc0_zkvm::InnerReceipt::Fake(risc0_zkvm::FakeReceipt::new(claim)),
+            journal.bytes.clone(),
+        );
+
+        let proof = Proof::try_from(receipt).unwrap();
+
+        assert_eq!(
+            Err(VerificationError::ReceiptFormatError),
+            v3_0().verify(
+                risc0_verifier::Digest::from([1; 8]),
+                proof.clone(),
+                journal.clone()
+            )
+        );
+        v3_0()
+            .with_fake_receipts_allowed()
+            .verify(risc0_verifier::Digest::from([1; 8]), proof.clone(), journal)
+            .unwrap();
+        assert!(matches!(
+            v3_0().with_fake_receipts_allowed().verify(
+                risc0_verifier::Digest::from([1; 8]),
+                proof,
+                Journal::new(vec![5])
+            ),
+            Err(VerificationError::ClaimDigestMismatch { .. })
+        ));
+    }
+}
+
+mod assumption_receipts {
+    use super::*;
+
+    #[test]
+    fn should_add_and_clear_assumption_receipts() {
+        let verifier = v1_2();
+        let case: Case =
+            read_all("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json").unwrap();
+        let proof = case.get_proof().unwrap();
+        let assumption: InnerAssumptionReceipt =
+            read_all::<Case>("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")
+                .unwrap()
+                .get_proof()
+                .unwrap()
+                .inner
+                .into();
+
+        let composite = proof
+            .inner
+            .composite()
+            .unwrap()
+            .clone()
+            .with_assumption_receipt(assumption.clone());
+
+        assert_eq!(1, composite.assumption_receipt_count());
+        assert_eq!(
+            assumption.claim_digest().unwrap(),
+            composite
+                .iter_assumption_receipts()
+                .next()
+                .unwrap()
+                .claim_digest()
+                .unwrap()
+        );
+
+        // The claim doesn't contain any assumption: the receipt is not admitted.
+        let res = verifier.verify(
+            case.vk.clone().into(),
+            Proof::new(composite.clone().into()),
+            case.journal.clone(),
+        );
+        assert!(
+            matches!(res, Err(VerificationError::ReceiptFormatError)),
+            "Invalid err {res:?}"
+        );
+
+        // No fixture has a real assumption to resolve: clearing a required receipt is covered by
+        // `clearing_a_required_assumption_receipt_fails_verification` in `receipt::composite`.
+        let composite = composite.clear_assumption_receipts();
+
+        assert_eq!(0, composite.assumption_receipt_count());
+        assert!(composite.iter_assumption_receipts().next().is_none());
+        verifier
+            .verify(case.vk.into(), Proof::new(composite.into()), case.journal)
+            .unwrap();
+    }
+}
+
+mod split_composite {
+    use super::*;
+    use risc0_verifier::Digestible;
+
+    const PATH: &str = "./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json";
+
+    #[rstest]
+    fn neither_half_verifies(#[values(1, 2)] index: usize) {
+        let verifier = v1_2();
+        let case: Case = read_all(PATH).unwrap();
+        let proof = case.get_proof().unwrap();
+        let segments = proof.inner.composite().unwrap().segments.len();
+
+        let (first, second) = proof.split_composite_at(index).unwrap();
+
+        assert_eq!(index, first.inner.composite().unwrap().segments.len());
+        assert_eq!(
+            segments - index,
+            second.inner.composite().unwrap().segments.len()
+        );
+        assert!(verifier
+            .verify(case.vk.clone().into(), first, case.journal.clone())
+            .is_err());
+        assert!(verifier
+            .verify(case.vk.into(), second, case.journal)
+            .is_err());
+    }
+
+    #[test]
+    fn split_at_zero_returns_empty_first_proof_and_original_second_proof() {
+        let verifier = v1_2();
+        let case: Case = read_all(PATH).unwrap();
+        let proof = case.get_proof().unwrap();
+        let expected_claim = proof.claim().unwrap().digest();
+
+        let (first, second) = proof.split_composite_at(0).unwrap();
+
+        assert!(first.inner.composite().unwrap().segments.is_empty());
+        assert_eq!(expected_claim, second.claim().unwrap().digest());
+        verifier
+            .verify(case.vk.into(), second, case.journal)
+            .unwrap();
+    }
+
+    #[test]
+    fn should_reject_out_of_range_index() {
+        let case: Case = read_all(PATH).unwrap();
+        let proof = case.get_proof().unwrap();
+        let segments = proof.inner.composite().unwrap().segments.len();
+
+        assert!(matches!(
+            proof.split_composite_at(segments + 1),
+            Err(VerificationError::ReceiptFormatError)
+        ));
+    }
+
+    #[test]
+    fn should_reject_succinct_proof() {
+        let case: Case =
+            read_all("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json").unwrap();
+        let proof = case.get_proof().unwrap();
+
+        assert!(matches!(
+            proof.split_composite_at(0),
+            Err(VerificationError::ReceiptFormatError)
+        ));
+    }
+}
+
+mod seal_size {
+    use super::*;
+    use risc0_verifier::InnerReceipt;
+
+    #[rstest]
+    #[case::v1_2_poseidon2_16("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json", 242652)]
+    #[case::v1_2_sha_22("./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json", 802324)]
+    #[case::v1_2_succinct_16("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json", 222668)]
+    #[case::v2_1_poseidon2_16("./resources/cases/prover_2.1.0/vm_2.1.0/poseidon2_16.json", 220840)]
+    #[case::v3_0_poseidon2_16("./resources/cases/prover_3.0.0/vm_3.0.0/poseidon2_16.json", 220904)]
+    #[case::v3_0_succinct_16("./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json", 222668)]
+    fn should_return_the_seal_size(#[case] path: &str, #[case] expected_bytes: usize) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+
+        assert_eq!(expected_bytes, proof.total_seal_bytes());
+        assert_eq!(expected_bytes / 4, proof.seal_word_count());
+    }
+
+    #[rstest]
+    #[case::composite("./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json")]
+    #[case::succinct("./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json")]
+    fn should_flatten_the_seals(#[case] path: &str) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+
+        let words = proof.seal_u32_words();
+
+        assert_eq!(proof.seal_word_count(), words.len());
+        match &proof.inner {
+            InnerReceipt::Composite(composite) => {
+                let mut seals = words.as_slice();
+                for segment in &composite.segments {
+                    let (seal, rest) = seals.split_at(segment.seal.len());
+                    assert_eq!(segment.seal.as_slice(), seal);
+                    seals = rest;
+                }
+                assert!(seals.is_empty());
+            }
+            InnerReceipt::Succinct(succinct) => assert_eq!(succinct.seal, words),
+            InnerReceipt::Groth16(_) => unreachable!("no Groth16 cases"),
+            #[cfg(feature = "dev-mode")]
+            InnerReceipt::Fake(_) => unreachable!("no fake cases"),
+        }
+    }
+
+    #[rstest]
+    #[case::single_segment("./resources/cases/single_full_segment_v1/poseidon2_16.json")]
+    #[case::composite("./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json")]
+    #[case::succinct("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")]
+    fn should_concatenate_inner_receipt_seal_bytes(#[case] path: &str) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+        let inner = &proof.inner;
+
+        let expected = match inner {
+            InnerReceipt::Composite(composite) => composite
+                .segments
+                .iter()
+                .flat_map(|s| s.get_seal_bytes())
+                .collect::<Vec<_>>(),
+            InnerReceipt::Succinct(succinct) => succinct.get_seal_bytes(),
+            InnerReceipt::Groth16(_) => unreachable!("no Groth16 cases"),
+            #[cfg(feature = "dev-mode")]
+            InnerReceipt::Fake(_) => unreachable!("no fake cases"),
+        };
+
+        assert_eq!(expected, inner.concatenated_seal_bytes());
+        assert_eq!(expected.len(), inner.seal_size());
+        assert_eq!(proof.total_seal_bytes(), inner.seal_size());
+    }
+
+    #[rstest]
+    #[case::single_segment("./resources/cases/single_full_segment_v1/poseidon2_16.json", 1)]
+    #[case::composite("./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json", 3)]
+    #[case::succinct("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json", 1)]
+    fn should_return_a_seal_slice_for_every_receipt(#[case] path: &str, #[case] expected: usize) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+
+        let slices = proof.inner.seal_word_slices();
+
+        assert_eq!(expected, slices.len());
+        assert_eq!(proof.seal_u32_words(), slices.concat());
+    }
+}
+
+mod decode_from_seal {
+    use super::*;
+    use risc0_verifier::Digestible;
+
+    fn succinct(path: &str) -> SuccinctReceipt<risc0_verifier::receipt_claim::ReceiptClaim> {
+        let case: Case = read_all(path).unwrap();
+        case.get_proof().unwrap().inner.succinct().unwrap().clone()
+    }
+
+    #[rstest]
+    #[case::v1_2(
+        "./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json",
+        SuccinctReceiptVerifierParameters::v1_2()
+    )]
+    #[case::v2_1(
+        "./resources/cases/prover_2.1.0/vm_2.1.0/succinct_16.json",
+        SuccinctReceiptVerifierParameters::v2_1()
+    )]
+    #[case::v3_0(
+        "./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json",
+        SuccinctReceiptVerifierParameters::v3_0()
+    )]
+    fn should_decode_control_root_and_output_hash(
+        #[case] path: &str,
+        #[case] params: SuccinctReceiptVerifierParameters,
+    ) {
+        let receipt = succinct(path);
+
+        assert_eq!(
+            params.inner_control_root.unwrap_or(params.control_root),
+            receipt.decode_control_root_from_seal().unwrap()
+        );
+        assert_eq!(
+            receipt.claim.digest(),
+            receipt.decode_output_hash_from_seal().unwrap()
+        );
+    }
+
+    #[test]
+    fn should_reject_truncated_seal() {
+        let mut receipt = succinct("./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json");
+        receipt.seal.truncate(20);
+
+        assert!(receipt.decode_control_root_from_seal().is_ok());
+        assert_eq!(
+            Err(VerificationError::ReceiptFormatError),
+            receipt.decode_output_hash_from_seal()
+        );
+
+        receipt.seal.truncate(10);
+
+        assert_eq!(
+            Err(VerificationError::ReceiptFormatError),
+            receipt.decode_control_root_from_seal()
+        );
+    }
+}
+
+mod verify_parallel {
+    use super::*;
+
+    #[rstest]
+    #[case::v1_2_sha(v1_2, "./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json")]
+    #[case::v1_2_poseidon2(v1_2, "./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_22.json")]
+    #[case::v2_1_poseidon2(v2_1, "./resources/cases/prover_2.1.0/vm_2.1.0/poseidon2_22.json")]
+    #[case::v3_0_poseidon2(v3_0, "./resources/cases/prover_3.0.0/vm_3.0.0/poseidon2_22.json")]
+    #[case::v3_0_succinct(v3_0, "./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json")]
+    fn should_verify<V: Verifier>(#[case] new_verifier: fn() -> V, #[case] path: &str) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+
+        V::verify_parallel(new_verifier, case.vk.into(), proof, case.journal).unwrap();
+    }
+
+    #[rstest]
+    fn should_fail_like_sequential_verification(#[values(0, 1, 2)] corrupted: usize) {
+        let case: Case = read_all("./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json").unwrap();
+        let mut proof = case.get_proof().unwrap();
+        let mut composite = proof.inner.composite().unwrap().clone();
+        composite.segments[corrupted].seal[0] ^= 1;
+        proof.inner = composite.into();
+
+        let expected = v1_2().verify(case.vk.clone().into(), proof.clone(), case.journal.clone());
+        let res = Verifier::verify_parallel(v1_2, case.vk.into(), proof, case.journal);
+
+        assert!(expected.is_err());
+        assert_eq!(expected, res);
+    }
+}
+
+mod single_segment_proof {
+    use super::*;
+    use risc0_verifier::Digestible;
+
+    #[rstest]
+    #[case::v1(v1_2(), "./resources/cases/single_full_segment_v1/poseidon2_16.json")]
+    #[case::v2(v2_1(), "./resources/cases/single_full_segment_v2/poseidon2_16.json")]
+    #[case::v3(v3_0(), "./resources/cases/single_full_segment_v3/poseidon2_16.json")]
+    fn should_verify_a_proof_built_from_a_single_segment(
+        #[case] verifier: impl Verifier,
+        #[case] path: &str,
+    ) {
+        let case: Case = read_all(path).unwrap();
+        let composite = case.get_proof().unwrap().inner.composite().unwrap().clone();
+        let segment = composite.into_first_segment().unwrap();
+        let expected_claim = segment.claim.digest();
+
+        let proof = Proof::from_single_segment(segment);
+
+        assert_eq!(expected_claim, proof.claim().unwrap().digest());
+        verifier
+            .verify(case.vk.into(), proof, case.journal)
+            .unwrap();
+    }
+}
+
+mod composite_destructors {
+    use super::*;
+    use risc0_verifier::Digest;
+
+    fn composite(path: &str) -> CompositeReceipt {
+        let case: Case = read_all(path).unwrap();
+        case.get_proof().unwrap().inner.composite().unwrap().clone()
+    }
+
+    fn empty() -> CompositeReceipt {
+        CompositeReceipt {
+            segments: Vec::new(),
+            assumption_receipts: Vec::new(),
+            verifier_parameters: Digest::ZERO,
+        }
+    }
+
+    fn single() -> CompositeReceipt {
+        composite("./resources/cases/single_full_segment_v1/poseidon2_16.json")
+    }
+
+    fn multi() -> CompositeReceipt {
+        composite("./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json")
+    }
+
+    fn indexes(composite: &CompositeReceipt) -> Vec<u32> {
+        composite.segments.iter().map(|s| s.index).collect()
+    }
+
+    #[rstest]
+    #[case::empty(empty(), 0)]
+    #[case::single(single(), 1)]
+    #[case::multi(multi(), 3)]
+    fn into_segments(#[case] composite: CompositeReceipt, #[case] expected: usize) {
+        let assumptions = composite.assumption_receipt_count();
+
+        let (segments, assumption_receipts) = composite.into_segments();
+
+        assert_eq!(expected, segments.len());
+        assert_eq!(assumptions, assumption_receipts.len());
+    }
+
+    #[rstest]
+    #[case::empty(empty(), None, None)]
+    #[case::single(single(), Some(0), Some(0))]
+    #[case::multi(multi(), Some(0), Some(2))]
+    fn into_first_and_last_segment(
+        #[case] composite: CompositeReceipt,
+        #[case] first: Option<u32>,
+        #[case] last: Option<u32>,
+    ) {
+        assert_eq!(
+            first,
+            composite.clone().into_first_segment().map(|s| s.index)
+        );
+        assert_eq!(last, composite.into_last_segment().map(|s| s.index));
+    }
+
+    #[rstest]
+    #[case::empty(empty(), None)]
+    #[case::single(single(), Some((0, vec![])))]
+    #[case::multi(multi(), Some((0, vec![1, 2])))]
+    fn split_first(#[case] composite: CompositeReceipt, #[case] expected: Option<(u32, Vec<u32>)>) {
+        let verifier_parameters = composite.verifier_parameters;
+
+        let split = composite.split_first();
+
+        assert_eq!(
+            expected,
+            split
+                .as_ref()
+                .map(|(first, rest)| (first.index, indexes(rest)))
+        );
+        if let Some((_, rest)) = split {
+            assert_eq!(verifier_parameters, rest.verifier_parameters);
+        }
+    }
+
+    #[rstest]
+    #[case::empty(empty(), None)]
+    #[case::single(single(), Some((vec![], 0)))]
+    #[case::multi(multi(), Some((vec![0, 1], 2)))]
+    fn split_last(#[case] composite: CompositeReceipt, #[case] expected: Option<(Vec<u32>, u32)>) {
+        let verifier_parameters = composite.verifier_parameters;
+
+        let split = composite.split_last();
+
+        assert_eq!(
+            expected,
+            split
+                .as_ref()
+                .map(|(rest, last)| (indexes(rest), last.index))
+        );
+        if let Some((rest, _)) = split {
+            assert_eq!(verifier_parameters, rest.verifier_parameters);
+        }
+    }
+}
+
+mod hash_function {
+    use super::*;
+    use risc0_verifier::HashFunction;
+
+    fn case_files(dir: &Path) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                files.extend(case_files(&path));
+            } else if path.extension().is_some_and(|ext| ext == "json") {
+                files.push(path);
+            }
+        }
+        files
+    }
+
+    fn hash_functions(proof: &Proof) -> Vec<HashFunction> {
+        match proof.inner.composite() {
+            Ok(c) => c.segments.iter().map(|s| s.hashfn).collect(),
+            Err(_) => vec![proof.inner.succinct().unwrap().hashfn],
+        }
+    }
+
+    #[test]
+    fn should_deserialize_all_the_case_fixtures() {
+        let files = case_files(Path::new("./resources/cases"));
+        assert!(!files.is_empty());
+
+        for path in files {
+            let case: Case = read_all(&path).unwrap();
+            let proof = case
+                .get_proof()
+                .unwrap_or_else(|e| panic!("cannot read proof of {path:?}: {e}"));
+
+            let mut buffer = Vec::new();
+            ciborium::into_writer(&proof, &mut buffer).unwrap();
+            let decoded: Proof = ciborium::from_reader(buffer.as_slice()).unwrap();
+
+            assert_eq!(hash_functions(&proof), hash_functions(&decoded));
+        }
+    }
+
+    #[test]
+    fn should_serialize_the_canonical_name() {
+        let case: Case = read_all("./resources/cases/prover_1.2.0/vm_1.2.0/sha_16.json").unwrap();
+        let segment = case
+            .get_proof()
+            .unwrap()
+            .inner
+            .composite()
+            .unwrap()
+            .segments[0]
+            .clone();
+
+        let json = serde_json::to_value(&segment).unwrap();
+
+        assert_eq!(HashFunction::Sha256, segment.hashfn);
+        assert_eq!("sha-256", json["hashfn"]);
+    }
+}
+
+mod incremental_composite {
+    use super::*;
+    use risc0_verifier::Digestible;
+
+    #[test]
+    fn should_verify_a_composite_built_by_pushing_segments() {
+        let case: Case = read_all("./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json").unwrap();
+        let original = case.get_proof().unwrap();
+        let segments = original.inner.composite().unwrap().segments.clone();
+
+        let mut composite = CompositeReceipt::with_capacity(segments.len());
+        assert!(composite.is_empty());
+        for segment in segments {
+            composite.push_segment(segment);
+        }
+        let proof = Proof::from_composite(composite.clone());
+
+        assert_eq!(3, composite.len());
+        assert_eq!(
+            vec![0, 1, 2],
+            composite
+                .iter_segments()
+                .map(|s| s.index)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            original.claim().unwrap().digest(),
+            proof.claim().unwrap().digest()
+        );
+        v1_2().verify(case.vk.into(), proof, case.journal).unwrap();
+    }
+
+    #[test]
+    fn should_pop_the_last_segment() {
+        let case: Case = read_all("./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json").unwrap();
+        let mut composite = case.get_proof().unwrap().inner.composite().unwrap().clone();
+
+        assert_eq!(Some(2), composite.pop_segment().map(|s| s.index));
+        assert_eq!(2, composite.len());
+        composite.pop_segment();
+        composite.pop_segment();
+        assert!(composite.is_empty());
+        assert!(composite.pop_segment().is_none());
+    }
+}
+
+mod expected_claim_digest {
+    use super::*;
+    use risc0_verifier::{
+        compute_expected_claim_digest, compute_paused_claim_digest,
+        receipt_claim::{MaybePruned, ReceiptClaim},
+        Digest, Digestible,
+    };
+
+    const IMAGE_ID: [u32; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+    const JOURNAL_DIGEST: [u32; 8] = [8, 7, 6, 5, 4, 3, 2, 1];
+
+    #[test]
+    fn should_compute_expected_claim_digest() {
+        let expected =
+            ReceiptClaim::ok(IMAGE_ID, MaybePruned::Pruned(JOURNAL_DIGEST.into())).digest();
+
+        assert_eq!(
+            expected,
+            compute_expected_claim_digest(IMAGE_ID.into(), JOURNAL_DIGEST.into())
+        );
+    }
+
+    #[test]
+    fn should_compute_paused_claim_digest() {
+        let expected =
+            ReceiptClaim::paused(IMAGE_ID, MaybePruned::Pruned(JOURNAL_DIGEST.into())).digest();
+
+        assert_eq!(
+            expected,
+            compute_paused_claim_digest(IMAGE_ID.into(), 0, JOURNAL_DIGEST.into())
+        );
+        assert_ne!(
+            expected,
+            compute_paused_claim_digest(IMAGE_ID.into(), 1, JOURNAL_DIGEST.into())
+        );
+    }
+
+    #[rstest]
+    #[case::v1_2("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")]
+    #[case::v3_0("./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json")]
+    fn should_match_the_proof_claim(#[case] path: &str) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+        let image_id: Digest = case.vk.0;
+
+        assert_eq!(
+            proof.claim().unwrap().digest(),
+            compute_expected_claim_digest(image_id, case.journal.digest())
+        );
+    }
+}
+
+mod extract_succinct_info {
+    use super::*;
+
+    /// Both the v1 and the v2 recursion circuits prove the recursion programs with po2 18.
+    const RECURSION_PO2: u32 = 18;
+
+    #[rstest]
+    #[case::v1_0_16(v1_0(), "./resources/cases/prover_1.0.3/vm_1.0.5/succinct_16.json")]
+    #[case::v1_0_22(v1_0(), "./resources/cases/prover_1.0.3/vm_1.0.5/succinct_22.json")]
+    #[case::v1_1_16(v1_1(), "./resources/cases/prover_1.1.3/vm_1.1.3/succinct_16.json")]
+    #[case::v1_1_22(v1_1(), "./resources/cases/prover_1.1.3/vm_1.1.3/succinct_22.json")]
+    #[case::v1_2_16(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")]
+    #[case::v1_2_22(v1_2(), "./resources/cases/prover_1.2.0/vm_1.2.0/succinct_22.json")]
+    #[case::v2_0_16(v2_0(), "./resources/cases/prover_2.0.0/vm_2.0.0/succinct_16.json")]
+    #[case::v2_0_22(v2_0(), "./resources/cases/prover_2.0.0/vm_2.0.0/succinct_22.json")]
+    #[case::v2_1_16(v2_1(), "./resources/cases/prover_2.1.0/vm_2.1.0/succinct_16.json")]
+    #[case::v2_1_22(v2_1(), "./resources/cases/prover_2.1.0/vm_2.1.0/succinct_22.json")]
+    #[case::v2_2_16(v2_2(), "./resources/cases/prover_2.2.0/vm_2.2.0/succinct_16.json")]
+    #[case::v2_2_22(v2_2(), "./resources/cases/prover_2.2.0/vm_2.2.0/succinct_22.json")]
+    #[case::v2_3_16(v2_3(), "./resources/cases/prover_2.3.0/vm_2.3.0/succinct_16.json")]
+    #[case::v2_3_22(v2_3(), "./resources/cases/prover_2.3.0/vm_2.3.0/succinct_22.json")]
+    #[case::v3_0_16(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json")]
+    #[case::v3_0_22(v3_0(), "./resources/cases/prover_3.0.0/vm_3.0.0/succinct_22.json")]
+    fn should_extract_po2_and_hashfn(#[case] verifier: impl Verifier, #[case] path: &str) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+        let succinct = proof.inner.succinct().unwrap();
+
+        let info = verifier.extract_succinct_info(succinct).unwrap();
+
+        assert_eq!(
+            SegmentInfo::new(succinct.hashfn.as_str().into(), RECURSION_PO2),
+            info,
+            "{path}"
+        );
+    }
+
+    #[test]
+    fn should_reject_truncated_seal() {
+        let case: Case =
+            read_all("./resources/cases/prover_3.0.0/vm_3.0.0/succinct_16.json").unwrap();
+        let mut proof = case.get_proof().unwrap();
+        let succinct = proof.inner.mut_succinct().unwrap();
+        succinct.seal.truncate(8);
+
+        assert_eq!(
+            Err(VerificationError::ReceiptFormatError),
+            v3_0().extract_succinct_info(succinct)
+        );
+    }
+}
+
+#[rstest_reuse::apply(segments)]
+fn per_segment_and_batch_segment_infos_match(#[case] verifier: impl Verifier, #[case] path: &str) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let composite = proof.inner.composite().unwrap();
+
+    let expected = verifier.extract_composite_segments_info(composite).unwrap();
+
+    assert_eq!(
+        expected,
+        composite.segments.segment_infos(&verifier).unwrap()
+    );
+    let per_segment = composite
+        .segments
+        .iter()
+        .map(|s| s.segment_info(&verifier).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(expected, per_segment);
+}
+
+#[rstest_reuse::apply(segments)]
+fn composite_po2_statistics(#[case] verifier: impl Verifier, #[case] path: &str) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let composite = proof.inner.composite().unwrap();
+    let infos = verifier.extract_composite_segments_info(composite).unwrap();
+
+    let histogram = composite.po2_histogram(&verifier).unwrap();
+
+    assert_eq!(composite.segments.len(), histogram.values().sum::<usize>());
+    for (po2, count) in &histogram {
+        assert_eq!(*count, infos.iter().filter(|i| i.po2 == *po2).count());
+    }
+    assert_eq!(
+        infos.iter().map(|i| i.po2).max(),
+        composite.max_segment_po2(&verifier).unwrap()
+    );
+    assert_eq!(
+        infos.iter().map(|i| i.po2).min(),
+        composite.min_segment_po2(&verifier).unwrap()
+    );
+}
+
+#[rstest_reuse::apply(segments)]
+fn fails_on_invalid_segment(
+    #[case] verifier: impl Verifier,
+    #[case] path: &str,
+    #[values(0, 1, 2)] segment: usize,
+) {
+    let case: Case = read_all(path).unwrap();
+    let mut proof = case.get_proof().unwrap();
+
+    let seal = proof.inner.mut_composite().unwrap().segments[segment]
+        .seal
+        .as_mut_slice();
+
+    seal[seal.len() / 2] = seal[seal.len() / 2].wrapping_add(1);
+
+    let composite = proof.inner.composite().unwrap();
+    for index in 0..composite.segments.len() {
+        let res = verifier.verify_segment_at(composite, index);
+        if index == segment {
+            assert_eq!(
+                Err(SegmentVerificationError::Invalid(
+                    VerificationError::InvalidProof
+                )),
+                res
+            );
+        } else {
+            assert_eq!(Ok(()), res, "segment {index}");
+        }
+    }
+    assert_eq!(
+        Err(SegmentVerificationError::IndexOutOfRange {
+            index: composite.segments.len(),
+            len: composite.segments.len()
+        }),
+        verifier.verify_segment_at(composite, composite.segments.len())
+    );
+
+    let res = verifier.verify(case.vk.into(), proof, case.journal);
+
+    assert!(res.is_err());
+    assert!(matches!(res, Err(VerificationError::InvalidProof)));
+}
+
+#[rstest_reuse::apply(succinct)]
+fn fails_on_invalid_succinct(#[case] verifier: impl Verifier, #[case] path: &str) {
     let case: Case = read_all(path).unwrap();
+    let mut proof = case.get_proof().unwrap();
+
+    let seal = proof.inner.mut_succinct().unwrap().seal.as_mut_slice();
+
+    seal[seal.len() / 2] = seal[seal.len() / 2].wrapping_add(1);
+
+    let res = verifier.verify(case.vk.into(), proof, case.journal);
+
+    assert!(res.is_err());
+    assert!(matches!(res, Err(VerificationError::InvalidProof)));
+}
+
+#[rstest_reuse::apply(all)]
+fn fails_on_invalid_vk(#[case] verifier: impl Verifier, #[case] path: &str) {
+    let mut case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+
+    if let Some(l) = case.vk.0.as_mut_words().last_mut() {
+        *l = l.wrapping_add(1);
+    }
+
+    let res = verifier.verify(case.vk.into(), proof, case.journal);
+
+    assert!(res.is_err());
+    assert!(
+        matches!(res, Err(VerificationError::ClaimDigestMismatch { .. })),
+        "Invalid err {res:?}"
+    );
+}
+
+#[rstest_reuse::apply(all)]
+fn fails_on_invalid_pubs(#[case] verifier: impl Verifier, #[case] path: &str) {
+    let mut case: Case = read_all(path).unwrap();
     let proof = case.get_proof().unwrap();
 
+    if let Some(l) = case.journal.bytes.last_mut() {
+        *l = l.wrapping_add(1);
+    }
+
+    let res = verifier.verify(case.vk.into(), proof, case.journal);
+
+    assert!(res.is_err());
+    assert!(
+        matches!(res, Err(VerificationError::ClaimDigestMismatch { .. })),
+        "Invalid err {res:?}"
+    );
+}
+
+#[rstest_reuse::apply(segments)]
+fn fails_on_invalid_claim(#[case] verifier: impl Verifier, #[case] path: &str) {
+    let case: Case = read_all(path).unwrap();
+    let mut proof = case.get_proof().unwrap();
+
+    proof.inner.mut_composite().unwrap().segments[0]
+        .claim
+        .exit_code = risc0_binfmt_v1::ExitCode::Halted(0);
+
+    let res = verifier.verify(case.vk.into(), proof, case.journal);
+
+    assert!(res.is_err());
+    assert!(
+        matches!(res, Err(VerificationError::ClaimDigestMismatch { .. })),
+        "Invalid err {res:?}"
+    );
+}
+
+#[rstest_reuse::apply(succinct)]
+fn fails_on_invalid_inner_control_root(#[case] mut verifier: impl Verifier, #[case] path: &str) {
+    use risc0_verifier::Digestible;
+
+    let case: Case = read_all(path).unwrap();
+    let mut proof = case.get_proof().unwrap();
+
     if let Some(p) = verifier.mut_succinct_verifier_parameters() {
         p.inner_control_root = Some(risc0_verifier::Digest::ZERO);
     }
 
+    // The parameters digest of the proof doesn't match anymore: rejected before the seal.
+    let res = verifier.verify(case.vk.clone().into(), proof.clone(), case.journal.clone());
+    assert!(
+        matches!(
+            res,
+            Err(VerificationError::VerifierParametersMismatch { .. })
+        ),
+        "Invalid err {res:?}"
+    );
+
+    // Match the parameters digest to reach the control root check.
+    proof.inner.mut_succinct().unwrap().verifier_parameters =
+        verifier.succinct_verifier_parameters().unwrap().digest();
     let res = verifier.verify(case.vk.into(), proof, case.journal);
 
     assert!(res.is_err());
@@ -622,6 +3179,350 @@ fn fails_on_invalid_succinct_claim(#[case] verifier: impl Verifier, #[case] path
     );
 }
 
+mod reject_if {
+    use super::*;
+
+    fn too_many_segments(proof: &Proof) -> bool {
+        proof
+            .inner
+            .composite()
+            .map(|c| c.segments.len() > 2)
+            .unwrap_or(false)
+    }
+
+    #[rstest]
+    #[case::one_segment("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json")]
+    #[case::three_segments("./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json")]
+    fn should_reject_all_when_predicate_is_always_true(#[case] path: &str) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+
+        let res = v1_2()
+            .reject_if(|_| true)
+            .verify(case.vk.into(), proof, case.journal);
+
+        assert_eq!(Err(VerificationError::ReceiptFormatError), res);
+    }
+
+    #[rstest]
+    #[case::one_segment("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json")]
+    #[case::three_segments("./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json")]
+    fn should_verify_all_when_predicate_is_always_false(#[case] path: &str) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+
+        v1_2()
+            .reject_if(|_| false)
+            .verify(case.vk.into(), proof, case.journal)
+            .unwrap();
+    }
+
+    #[rstest]
+    #[case::one_segment("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json", true)]
+    #[case::three_segments("./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json", false)]
+    #[case::succinct("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_22.json", true)]
+    fn should_reject_by_segment_count(#[case] path: &str, #[case] accepted: bool) {
+        let case: Case = read_all(path).unwrap();
+        let proof = case.get_proof().unwrap();
+        let verifier = v1_2().reject_if(too_many_segments).boxed();
+
+        let res = verifier.verify(case.vk.into(), proof, case.journal);
+
+        assert_eq!(accepted, res.is_ok(), "unexpected result {res:?}");
+        if !accepted {
+            assert_eq!(Err(VerificationError::ReceiptFormatError), res);
+        }
+    }
+
+    #[test]
+    fn should_keep_the_inner_verifier_configuration() {
+        let verifier = v1_2().reject_sha256().reject_if(|_| false);
+
+        assert!(!verifier.has_hash_suite("sha-256"));
+        assert_eq!(
+            v1_2().segment_verifier_parameters(),
+            verifier.segment_verifier_parameters()
+        );
+        assert!(verifier.describe().starts_with("Filtered(risc0/v1.2 {"));
+    }
+}
+
+mod verifier_trait_defaults {
+    use super::*;
+    use risc0_verifier::{
+        poseidon2_injection::Poseidon2Mix, receipt_claim::ExitCode, Digest, Digestible,
+    };
+    use risc0_zkp_v1::adapter::ProtocolInfo;
+
+    /// A verifier that implements just the required methods, forwarding them to `0`.
+    struct Minimal(Box<dyn Verifier>);
+
+    impl Verifier for Minimal {
+        fn verify(
+            &self,
+            image_id: Digest,
+            proof: Proof,
+            pubs: Journal,
+        ) -> Result<(), VerificationError> {
+            self.0.verify(image_id, proof, pubs)
+        }
+
+        fn seal_offset(&self) -> usize {
+            self.0.seal_offset()
+        }
+
+        fn segment_circuit_output_size(&self) -> usize {
+            self.0.segment_circuit_output_size()
+        }
+
+        fn set_poseidon2_mix_impl(
+            &mut self,
+            poseidon2: Box<dyn Poseidon2Mix + Send + Sync + 'static>,
+        ) {
+            self.0.set_poseidon2_mix_impl(poseidon2)
+        }
+
+        fn remove_hash_suite(&mut self, hashfn: &str) {
+            self.0.remove_hash_suite(hashfn)
+        }
+
+        fn set_max_proof_bytes(&mut self, max: usize) {
+            self.0.set_max_proof_bytes(max)
+        }
+
+        fn max_proof_bytes(&self) -> usize {
+            self.0.max_proof_bytes()
+        }
+
+        fn set_max_journal_bytes(&mut self, n: usize) {
+            self.0.set_max_journal_bytes(n)
+        }
+
+        fn max_journal_bytes(&self) -> Option<usize> {
+            self.0.max_journal_bytes()
+        }
+
+        #[cfg(feature = "dev-mode")]
+        fn set_fake_receipts_allowed(&mut self, allowed: bool) {
+            self.0.set_fake_receipts_allowed(allowed)
+        }
+
+        #[cfg(feature = "dev-mode")]
+        fn fake_receipts_allowed(&self) -> bool {
+            self.0.fake_receipts_allowed()
+        }
+
+        fn segment_verifier_parameters(&self) -> Option<&SegmentReceiptVerifierParameters> {
+            self.0.segment_verifier_parameters()
+        }
+
+        fn succinct_verifier_parameters(&self) -> Option<&SuccinctReceiptVerifierParameters> {
+            self.0.succinct_verifier_parameters()
+        }
+
+        fn mut_succinct_verifier_parameters(
+            &mut self,
+        ) -> Option<&mut SuccinctReceiptVerifierParameters> {
+            self.0.mut_succinct_verifier_parameters()
+        }
+
+        fn set_segment_verifier_parameters(&mut self, params: SegmentReceiptVerifierParameters) {
+            self.0.set_segment_verifier_parameters(params)
+        }
+
+        fn set_succinct_verifier_parameters(&mut self, params: SuccinctReceiptVerifierParameters) {
+            self.0.set_succinct_verifier_parameters(params)
+        }
+
+        fn version(&self) -> &str {
+            self.0.version()
+        }
+
+        fn circuit_info(&self) -> ProtocolInfo {
+            self.0.circuit_info()
+        }
+    }
+
+    const PATH: &str = "./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json";
+
+    #[test]
+    fn verify_with_exit_code_accepts_just_halted_ok() {
+        let case: Case = read_all(PATH).unwrap();
+        let verifier = Minimal(v1_2().boxed());
+
+        verifier
+            .verify_with_exit_code(
+                case.vk.0,
+                case.get_proof().unwrap(),
+                case.journal.clone(),
+                ExitCode::Halted(0),
+            )
+            .unwrap();
+        assert_eq!(
+            Err(VerificationError::UnexpectedExitCode),
+            verifier.verify_with_exit_code(
+                case.vk.0,
+                case.get_proof().unwrap(),
+                case.journal,
+                ExitCode::Paused(0)
+            )
+        );
+    }
+
+    #[test]
+    fn verify_conditional_accepts_unconditional_proof() {
+        let case: Case = read_all(PATH).unwrap();
+
+        assert_eq!(
+            Ok(vec![]),
+            Minimal(v1_2().boxed()).verify_conditional(
+                case.vk.0,
+                case.get_proof().unwrap(),
+                case.journal
+            )
+        );
+    }
+
+    #[test]
+    fn verify_digest_rejects_everything() {
+        let case: Case = read_all(PATH).unwrap();
+
+        assert_eq!(
+            Err(VerificationError::ReceiptFormatError),
+            Minimal(v1_2().boxed()).verify_digest(
+                case.vk.0,
+                case.get_proof().unwrap(),
+                case.journal.digest()
+            )
+        );
+    }
+
+    #[test]
+    fn verify_segment_at_checks_the_index_and_rejects_the_segment() {
+        let proof = read_all::<Case>(PATH).unwrap().get_proof().unwrap();
+        let composite = proof.inner.composite().unwrap();
+        let len = composite.segments.len();
+        let verifier = Minimal(v1_2().boxed());
+
+        assert_eq!(
+            Err(SegmentVerificationError::Invalid(
+                VerificationError::ReceiptFormatError
+            )),
+            verifier.verify_segment_at(composite, 0)
+        );
+        assert_eq!(
+            Err(SegmentVerificationError::IndexOutOfRange { index: len, len }),
+            verifier.verify_segment_at(composite, len)
+        );
+    }
+
+    #[test]
+    fn verify_assumption_rejects_everything() {
+        let proof = read_all::<Case>(PATH).unwrap().get_proof().unwrap();
+        let assumption =
+            InnerAssumptionReceipt::Composite(proof.inner.composite().unwrap().clone());
+
+        assert_eq!(
+            Err(VerificationError::ReceiptFormatError),
+            Minimal(v1_2().boxed()).verify_assumption(&assumption, Digest::ZERO, None)
+        );
+    }
+
+    #[test]
+    fn parameters_and_sizes_defaults() {
+        let verifier = Minimal(v1_2().boxed());
+
+        assert!(verifier.groth16_verifier_parameters().is_none());
+        assert!(verifier.has_hash_suite("blake2b"));
+        assert!(!verifier.has_hash_suite("keccak"));
+        assert_eq!(
+            v1_2().succinct_circuit_output_size(),
+            verifier.succinct_circuit_output_size()
+        );
+    }
+}
+
+mod detect_verifier {
+    use super::*;
+    use risc0_verifier::{
+        detect_verifier,
+        receipt_claim::{MaybePruned, ReceiptClaim},
+        Digest, Digestible, Groth16Receipt, Groth16ReceiptVerifierParameters, InnerReceipt,
+        UnknownProverVersion,
+    };
+
+    #[rstest]
+    fn should_detect_the_prover_version(
+        #[files("./resources/cases/prover_*/**/*.json")] path: PathBuf,
+    ) {
+        let prover = path
+            .components()
+            .find_map(|c| c.as_os_str().to_str()?.strip_prefix("prover_"))
+            .unwrap()
+            .to_owned();
+        let (major_minor, _) = prover.rsplit_once('.').unwrap();
+        // The 2.2 and 2.3 provers generate receipts with the same parameters.
+        let expected = match major_minor {
+            "2.2" => "2.3",
+            v => v,
+        };
+        let case: Case = read_all(&path).unwrap();
+        let proof = case.get_proof().unwrap();
+
+        let verifier = detect_verifier(&proof).unwrap();
+
+        assert_eq!(
+            format!("risc0/v{expected}"),
+            verifier.version(),
+            "{prover} proof detected as {verifier:?}"
+        );
+        verifier
+            .verify(case.vk.into(), proof, case.journal)
+            .unwrap();
+    }
+
+    #[rstest]
+    #[case::v1_2(Groth16ReceiptVerifierParameters::v1_2(), "1.2")]
+    #[case::v2_1(Groth16ReceiptVerifierParameters::v2_1(), "2.1")]
+    #[case::v3_0(Groth16ReceiptVerifierParameters::v3_0(), "3.0")]
+    fn should_detect_the_prover_version_of_groth16_proof(
+        #[case] params: Groth16ReceiptVerifierParameters,
+        #[case] expected: &str,
+    ) {
+        let claim = ReceiptClaim::ok(Digest::ZERO, MaybePruned::Pruned(Digest::ZERO));
+        let proof = Proof::from_groth16(Groth16Receipt::new(
+            Vec::new(),
+            MaybePruned::Value(claim),
+            params.digest(),
+        ));
+
+        let verifier = detect_verifier(&proof).unwrap();
+
+        assert_eq!(format!("risc0/v{expected}"), verifier.version());
+    }
+
+    #[rstest]
+    #[case::composite("./resources/cases/prover_1.2.0/vm_1.2.0/sha_16.json")]
+    #[case::succinct("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")]
+    fn should_reject_unknown_verifier_parameters(#[case] path: &str) {
+        let mut proof = read_all::<Case>(path).unwrap().get_proof().unwrap();
+        match &mut proof.inner {
+            InnerReceipt::Composite(inner) => inner.verifier_parameters = Digest::ZERO,
+            InnerReceipt::Succinct(inner) => inner.verifier_parameters = Digest::ZERO,
+            InnerReceipt::Groth16(inner) => inner.verifier_parameters = Digest::ZERO,
+            #[cfg(feature = "dev-mode")]
+            InnerReceipt::Fake(_) => unreachable!("no fake cases"),
+        }
+
+        assert_eq!(
+            UnknownProverVersion {
+                verifier_parameters: Digest::ZERO
+            },
+            detect_verifier(&proof).unwrap_err()
+        );
+    }
+}
+
 #[rstest_reuse::template]
 #[rstest]
 #[case::poseidon_proof_v1_0(v1_0(), "./resources/cases/prover_1.0.3/vm_1.0.5/poseidon2_22.json")]
@@ -681,6 +3582,27 @@ fn read_bin_all<T: DeserializeOwned>(path: impl AsRef<Path>) -> anyhow::Result<T
     ciborium::from_reader(buf_reader).map_err(Into::into)
 }
 
+/// Replace the `verifier_parameters` digests of the receipts of `proof` with the ones of
+/// `verifier`, to get past the parameters check and reach the seal verification.
+fn with_verifier_parameters_of(mut proof: Proof, verifier: &impl Verifier) -> Proof {
+    use risc0_verifier::Digestible;
+
+    match &mut proof.inner {
+        risc0_verifier::InnerReceipt::Composite(inner) => {
+            let digest = verifier.segment_verifier_parameters().unwrap().digest();
+            inner
+                .segments
+                .iter_mut()
+                .for_each(|s| s.verifier_parameters = digest);
+        }
+        risc0_verifier::InnerReceipt::Succinct(inner) => {
+            inner.verifier_parameters = verifier.succinct_verifier_parameters().unwrap().digest();
+        }
+        _ => unreachable!("only composite and succinct cases"),
+    }
+    proof
+}
+
 #[derive(Serialize, Deserialize)]
 struct Case {
     receipt_path: PathBuf,
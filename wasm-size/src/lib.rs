@@ -0,0 +1,35 @@
+// Copyright 2024, Horizen Labs, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Minimal wasm module that verifies a proof: CI checks its size against a budget.
+
+use risc0_verifier::{Journal, Proof, Verifier, Vk};
+
+/// Verify the JSON encoded `(vk, proof, journal)` tuple with the default `3.0.x` verifier.
+///
+/// # Safety
+///
+/// `ptr` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn verify(ptr: *const u8, len: usize) -> bool {
+    let data = core::slice::from_raw_parts(ptr, len);
+    let Ok((vk, proof, journal)) = serde_json::from_slice::<(Vk, Proof, Journal)>(data) else {
+        return false;
+    };
+    risc0_verifier::v3_0()
+        .verify(vk.into(), proof, journal)
+        .is_ok()
+}